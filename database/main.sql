@@ -10,6 +10,18 @@ CREATE TABLE `users` (
     `name` VARCHAR(255) NOT NULL
 );
 
+CREATE TABLE `user_identities` (
+	`id` BIGINT UNSIGNED NOT NULL PRIMARY KEY AUTO_INCREMENT,
+    `user_id` BIGINT UNSIGNED NOT NULL,
+    `issuer` VARCHAR(255) NOT NULL,
+    `subject` VARCHAR(255) NOT NULL,
+    
+    UNIQUE (`issuer`, `subject`),
+    
+    FOREIGN KEY (`user_id`) REFERENCES `users`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE
+);
+
 CREATE TABLE `tournaments` (
 	`id` BIGINT UNSIGNED NOT NULL PRIMARY KEY AUTO_INCREMENT,
     `user_id` BIGINT UNSIGNED NOT NULL,
@@ -25,6 +37,12 @@ CREATE TABLE `leagues` (
     `tournament_id` BIGINT UNSIGNED NOT NULL,
     `name` VARCHAR(20) NOT NULL,
     `completed` BOOLEAN NOT NULL DEFAULT FALSE,
+    -- The seed used to shuffle the teams on the latest fixtures draw, so the draw can be reproduced
+    `fixture_seed` BIGINT UNSIGNED NULL,
+    -- How the fixtures are paired: every team against each other, one swiss round at a time or on a double elimination bracket
+    `format` ENUM('round_robin', 'swiss', 'double_elimination') NOT NULL DEFAULT 'round_robin',
+    -- The rounds a swiss league plays before it's completed, NULL when the swiss league is only completed by hand
+    `swiss_rounds` SMALLINT UNSIGNED NULL,
     
     FOREIGN KEY (`tournament_id`) REFERENCES `tournaments`(`id`)
 		ON DELETE CASCADE ON UPDATE CASCADE
@@ -45,17 +63,19 @@ CREATE TABLE `fixtures` (
     `home_team_id` BIGINT UNSIGNED NOT NULL,
     `away_team_id` BIGINT UNSIGNED NOT NULL,
     `league_id` BIGINT UNSIGNED NOT NULL,
-    `playing_date` TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    -- The fixtures are only dated when they are scheduled, the calendar feeds leave the undated ones out
+    `playing_date` TIMESTAMP NULL DEFAULT NULL,
     `home_score` TINYINT UNSIGNED NOT NULL DEFAULT 0,
     `away_score`  TINYINT UNSIGNED NOT NULL DEFAULT 0,
     `played` BOOLEAN NOT NULL DEFAULT FALSE,
     `round` SMALLINT UNSIGNED NOT NULL,
     
+    -- Completed leagues are archived, so the fixtures of a deleted team can be removed. The teams playing an open league are not deleted
     FOREIGN KEY (`home_team_id`) REFERENCES `teams`(`id`)
-		ON UPDATE CASCADE,
+		ON DELETE CASCADE ON UPDATE CASCADE,
 	
     FOREIGN KEY (`away_team_id`) REFERENCES `teams`(`id`)
-		ON UPDATE CASCADE,
+		ON DELETE CASCADE ON UPDATE CASCADE,
         
 	FOREIGN KEY (`league_id`) REFERENCES `leagues`(`id`)
 		ON DELETE CASCADE ON UPDATE CASCADE
@@ -68,8 +88,122 @@ CREATE TABLE `teams_leagues` (
     PRIMARY KEY(`team_id`, `league_id`),
     
     FOREIGN KEY (`team_id`) REFERENCES `teams`(`id`)
-		ON UPDATE CASCADE,
+		ON DELETE CASCADE ON UPDATE CASCADE,
+	
+    FOREIGN KEY (`league_id`) REFERENCES `leagues`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+-- The matches of a double elimination bracket, numbered so a match always comes after the matches which feed it.
+-- A slot has either the seeded team, null being a bye, or the winner/loser of a previous match.
+-- The fixture is created once both teams of the match are known
+CREATE TABLE `bracket_matches` (
+	`league_id` BIGINT UNSIGNED NOT NULL,
+    `match_number` SMALLINT UNSIGNED NOT NULL,
+    `bracket` ENUM('winners', 'losers', 'grand_final', 'grand_final_reset') NOT NULL,
+    `bracket_round` SMALLINT UNSIGNED NOT NULL,
+    `position` SMALLINT UNSIGNED NOT NULL,
+    `home_seed_team_id` BIGINT UNSIGNED NULL,
+    `home_source_match` SMALLINT UNSIGNED NULL,
+    `home_source_result` ENUM('winner', 'loser') NULL,
+    `away_seed_team_id` BIGINT UNSIGNED NULL,
+    `away_source_match` SMALLINT UNSIGNED NULL,
+    `away_source_result` ENUM('winner', 'loser') NULL,
+    `fixture_id` BIGINT UNSIGNED NULL,
+    -- A two legged match is played as a tie, the fixture is its first leg
+    `two_legged` BOOLEAN NOT NULL DEFAULT FALSE,
+    `away_goals` BOOLEAN NOT NULL DEFAULT FALSE,
+    
+    PRIMARY KEY(`league_id`, `match_number`),
+    
+    FOREIGN KEY (`league_id`) REFERENCES `leagues`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE,
+	
+    FOREIGN KEY (`home_seed_team_id`) REFERENCES `teams`(`id`)
+		ON DELETE SET NULL ON UPDATE CASCADE,
+	
+    FOREIGN KEY (`away_seed_team_id`) REFERENCES `teams`(`id`)
+		ON DELETE SET NULL ON UPDATE CASCADE,
+	
+    FOREIGN KEY (`fixture_id`) REFERENCES `fixtures`(`id`)
+		ON DELETE SET NULL ON UPDATE CASCADE
+);
+
+-- Two fixtures between the same teams, the second leg is played at the first leg away team ground.
+-- The extra time and penalties are played on the second leg, their scores are counted by the second leg home and away teams
+CREATE TABLE `ties` (
+	`id` BIGINT UNSIGNED NOT NULL PRIMARY KEY AUTO_INCREMENT,
+    `league_id` BIGINT UNSIGNED NOT NULL,
+    `first_leg_id` BIGINT UNSIGNED NOT NULL UNIQUE,
+    `second_leg_id` BIGINT UNSIGNED NOT NULL UNIQUE,
+    `away_goals` BOOLEAN NOT NULL DEFAULT FALSE,
+    `extra_time_home_score` TINYINT UNSIGNED NULL,
+    `extra_time_away_score` TINYINT UNSIGNED NULL,
+    `penalties_home_score` TINYINT UNSIGNED NULL,
+    `penalties_away_score` TINYINT UNSIGNED NULL,
+    
+    FOREIGN KEY (`league_id`) REFERENCES `leagues`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE,
+	
+    FOREIGN KEY (`first_leg_id`) REFERENCES `fixtures`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE,
 	
+    FOREIGN KEY (`second_leg_id`) REFERENCES `fixtures`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+-- Negative positions are counted from the bottom of the standing table
+CREATE TABLE `league_zones` (
+	`id` BIGINT UNSIGNED NOT NULL PRIMARY KEY AUTO_INCREMENT,
+    `league_id` BIGINT UNSIGNED NOT NULL,
+    `label` VARCHAR(30) NOT NULL,
+    `color` VARCHAR(7) NOT NULL,
+    `start_position` SMALLINT NOT NULL,
+    `end_position` SMALLINT NOT NULL,
+    
+    FOREIGN KEY (`league_id`) REFERENCES `leagues`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+-- The standing table frozen when the league is completed, the first position is the champion.
+-- The team name is copied and there's no foreign key to the team, so the table stays the same if the team is renamed or deleted later
+CREATE TABLE `league_final_standings` (
+	`league_id` BIGINT UNSIGNED NOT NULL,
+    `position` SMALLINT UNSIGNED NOT NULL,
+    `team_id` BIGINT UNSIGNED NOT NULL,
+    `team_name` VARCHAR(40) NOT NULL,
+    `matches_played` TINYINT UNSIGNED NOT NULL,
+    `total_points` TINYINT UNSIGNED NOT NULL,
+    `win` TINYINT UNSIGNED NOT NULL,
+    `draw` TINYINT UNSIGNED NOT NULL,
+    `loss` TINYINT UNSIGNED NOT NULL,
+    `goals_scored` TINYINT UNSIGNED NOT NULL,
+    `goals_against` TINYINT UNSIGNED NOT NULL,
+    `goal_difference` SMALLINT NOT NULL,
+    `form` VARCHAR(5) NOT NULL,
+    
+    PRIMARY KEY(`league_id`, `position`),
+    
+    FOREIGN KEY (`league_id`) REFERENCES `leagues`(`id`)
+		ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+-- The fixtures frozen when the league is completed, the same way as `league_final_standings`
+CREATE TABLE `league_final_fixtures` (
+	`league_id` BIGINT UNSIGNED NOT NULL,
+    `fixture_id` BIGINT UNSIGNED NOT NULL,
+    `round` SMALLINT UNSIGNED NOT NULL,
+    `home_team_id` BIGINT UNSIGNED NOT NULL,
+    `home_team_name` VARCHAR(40) NOT NULL,
+    `away_team_id` BIGINT UNSIGNED NOT NULL,
+    `away_team_name` VARCHAR(40) NOT NULL,
+    `home_score` TINYINT UNSIGNED NOT NULL,
+    `away_score` TINYINT UNSIGNED NOT NULL,
+    `played` BOOLEAN NOT NULL,
+    `playing_date` TIMESTAMP NULL,
+    
+    PRIMARY KEY(`league_id`, `fixture_id`),
+    
     FOREIGN KEY (`league_id`) REFERENCES `leagues`(`id`)
 		ON DELETE CASCADE ON UPDATE CASCADE
 );
@@ -79,7 +213,7 @@ CREATE PROCEDURE generate_fixture(
 	IN home_team_id BIGINT UNSIGNED,
     IN away_team_id BIGINT UNSIGNED,
     IN league_id BIGINT UNSIGNED,
-    IN round TINYINT UNSIGNED,
+    IN round SMALLINT UNSIGNED,
     IN user_id BIGINT UNSIGNED
 )
 BEGIN
@@ -112,20 +246,24 @@ BEGIN
     
     SELECT COUNT(*) INTO home_team_exists
     FROM `teams` as TeamRow
+    INNER JOIN `teams_leagues` as TeamLeagueRow
+		ON TeamLeagueRow.team_id = TeamRow.id AND TeamLeagueRow.league_id = league_id
     WHERE TeamRow.id = home_team_id AND TeamRow.tournament_id = actual_tournament_id;
 
     IF home_team_exists = 0 THEN
 		SIGNAL SQLSTATE '45000'
-		SET MESSAGE_TEXT = 'Home team not found on the tournament';
+		SET MESSAGE_TEXT = 'Home team not found on the league';
     END IF;
     
     SELECT COUNT(*) INTO away_team_exists
     FROM `teams` as TeamRow
+    INNER JOIN `teams_leagues` as TeamLeagueRow
+		ON TeamLeagueRow.team_id = TeamRow.id AND TeamLeagueRow.league_id = league_id
     WHERE TeamRow.id = away_team_id AND TeamRow.tournament_id = actual_tournament_id;
     
     IF away_team_exists = 0 THEN
 		SIGNAL SQLSTATE '45000'
-		SET MESSAGE_TEXT = 'Away team not found on the tournament';
+		SET MESSAGE_TEXT = 'Away team not found on the league';
     END IF;
     
     INSERT INTO `fixtures` (home_team_id, away_team_id, league_id, round)
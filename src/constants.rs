@@ -1 +1,8 @@
-pub const MYSQL_CUSTOM_ERROR: &str = "45000";
\ No newline at end of file
+pub const MYSQL_CUSTOM_ERROR: &str = "45000";
+
+/// Goals awarded to the opponents of a withdrawn team on the fixtures it did not play, the withdrawn team is given 0
+pub const FORFEIT_GOALS: u8 = 3;
+
+/// The first characters which make a spreadsheet field a formula, the CSV exports escape them with a leading `'`.
+/// Some spreadsheet apps skip a leading tab or carriage return and run the formula after it
+pub const CSV_FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
\ No newline at end of file
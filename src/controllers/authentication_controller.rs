@@ -1,6 +1,11 @@
-use crate::models::user::{UserLoginDTO, UserSignUpDTO};
-use crate::responses::{CustomResponse, HTTPException, HTTPSuccessResponse};
-use crate::services::user_service::{create_user, user_login};
+use crate::jwt_auth_handler::{self, JwtConfig, UserToken};
+use crate::models::user::{User, UserLoginDTO, UserSignUpDTO};
+use crate::oidc_handler::{self, OidcConfig, OIDC_STATE_COOKIE};
+use crate::responses::{CustomResponse, ErrorResponse, HTTPException, HTTPSuccessResponse};
+use crate::services::user_service::{create_user, oidc_login, user_login};
+use jsonwebtoken::jwk::JwkSet;
+use rocket::http::{Cookie, CookieJar, SameSite};
+use rocket::response::Redirect;
 use rocket::serde::json::Json;
 use rocket::State;
 use serde_json::json;
@@ -16,15 +21,17 @@ pub async fn register(
     Ok(HTTPSuccessResponse::Created(CustomResponse {
         message: "Account created successfully".to_string(),
         data: serde_json::to_value("").unwrap(),
+        pagination: None,
     }))
 }
 
 #[post("/login", format = "json", data = "<login>")]
 pub async fn login(
     login: Json<UserLoginDTO>,
+    jwt_config: &State<JwtConfig>,
     db_pool: &State<Pool<MySql>>,
 ) -> Result<HTTPSuccessResponse, HTTPException> {
-    let jwt = user_login(login.0, &db_pool).await?;
+    let jwt = user_login(login.0, jwt_config, &db_pool).await?;
 
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: "Success!".to_string(),
@@ -32,5 +39,139 @@ pub async fn login(
             "token": jwt,
         }))
         .unwrap(),
+        pagination: None,
     }))
 }
+
+/// Responsible to redirect the user to the identity provider, keeping the `state` and `nonce` on a cookie.
+/// When an account is being linked, the cookie also keeps a token of the signed in user
+async fn redirect_to_identity_provider(
+    oidc_config: &OidcConfig,
+    cookies: &CookieJar<'_>,
+    link_token: Option<String>,
+) -> Result<Redirect, HTTPException> {
+    let state = oidc_handler::generate_secret(32);
+    let nonce = oidc_handler::generate_secret(32);
+
+    let discovery = oidc_handler::fetch_discovery(oidc_config).await?;
+    let authorization_url =
+        oidc_handler::authorization_url(oidc_config, &discovery, &state, &nonce)?;
+
+    // Keeping the state and nonce so the callback can check the response belongs to this browser
+    let cookie_value = match link_token {
+        Some(link_token) => format!("{}:{}:{}", state, nonce, link_token),
+        None => format!("{}:{}", state, nonce),
+    };
+    let mut state_cookie = Cookie::new(OIDC_STATE_COOKIE, cookie_value);
+    state_cookie.set_http_only(true);
+    state_cookie.set_same_site(SameSite::Lax);
+    cookies.add(state_cookie);
+
+    Ok(Redirect::to(authorization_url))
+}
+
+/// Starts the OpenID Connect login by redirecting the user to the identity provider
+#[get("/oidc/login")]
+pub async fn oidc_login_redirect(
+    oidc_config: &State<Option<OidcConfig>>,
+    cookies: &CookieJar<'_>,
+) -> Result<Redirect, HTTPException> {
+    let oidc_config = oidc_config.as_ref().ok_or_else(|| {
+        HTTPException::BadRequest(String::from("OpenID Connect login is not enabled"))
+    })?;
+
+    redirect_to_identity_provider(oidc_config, cookies, None).await
+}
+
+/// Starts linking the identity provider account to the signed in user, the callback links it instead of signing in
+#[get("/oidc/link")]
+pub async fn oidc_link_redirect(
+    token: Result<UserToken, ErrorResponse>,
+    oidc_config: &State<Option<OidcConfig>>,
+    jwt_config: &State<JwtConfig>,
+    cookies: &CookieJar<'_>,
+) -> Result<Redirect, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let oidc_config = oidc_config.as_ref().ok_or_else(|| {
+        HTTPException::BadRequest(String::from("OpenID Connect login is not enabled"))
+    })?;
+
+    // The user id is kept on a short lived signed token scoped to the link, so the cookie can't be changed to link
+    // another account and the token can't be used on the API
+    let link_token =
+        jwt_auth_handler::generate_link_token(user_id, jwt_config).or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to start linking the account. Please try again later",
+            )))
+        })?;
+
+    redirect_to_identity_provider(oidc_config, cookies, Some(link_token)).await
+}
+
+/// Finishes the OpenID Connect login or account link and returns the same token as the `/login` route
+#[get("/oidc/callback?<code>&<state>")]
+pub async fn oidc_callback(
+    code: String,
+    state: String,
+    cookies: &CookieJar<'_>,
+    oidc_config: &State<Option<OidcConfig>>,
+    jwt_config: &State<JwtConfig>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let oidc_config = oidc_config.as_ref().ok_or_else(|| {
+        HTTPException::BadRequest(String::from("OpenID Connect login is not enabled"))
+    })?;
+
+    let stored_state = cookies
+        .get(OIDC_STATE_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .unwrap_or_default();
+    cookies.remove(OIDC_STATE_COOKIE);
+
+    let invalid_request = || {
+        HTTPException::Unauthorized(String::from(
+            "The login request expired or is invalid. Please try again",
+        ))
+    };
+
+    let mut stored_values = stored_state.splitn(3, ':');
+    let nonce = match (stored_values.next(), stored_values.next()) {
+        (Some(expected_state), Some(nonce)) if expected_state == state => nonce.to_string(),
+        _ => return Err(invalid_request()),
+    };
+    let link_user_id = match stored_values.next() {
+        Some(link_token) => Some(
+            jwt_auth_handler::decode_link_token(link_token.to_string(), jwt_config)
+                .map_err(|_| invalid_request())?
+                .claims
+                .user_id,
+        ),
+        None => None,
+    };
+
+    let jwt = oidc_login(
+        &code,
+        &nonce,
+        link_user_id,
+        oidc_config,
+        jwt_config,
+        db_pool,
+    )
+    .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: "Success!".to_string(),
+        data: serde_json::to_value(json!({
+            "token": jwt,
+        }))
+        .unwrap(),
+        pagination: None,
+    }))
+}
+
+/// Publishes the public keys used to sign the tokens, so other services can verify them.
+/// The body follows the JWKS format instead of the `CustomResponse` one, as that's what JWT libraries expect
+#[get("/jwks.json")]
+pub fn jwks(jwt_config: &State<JwtConfig>) -> Json<JwkSet> {
+    Json(jwt_config.public_keys().clone())
+}
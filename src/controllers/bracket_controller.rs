@@ -0,0 +1,57 @@
+use rocket::{serde::json::Json, State};
+use sqlx::{MySql, Pool};
+
+use crate::{
+    jwt_auth_handler::UserToken,
+    models::{
+        bracket::{BracketGenerationDTO, BracketMatch},
+        user::User,
+    },
+    responses::{CustomResponse, ErrorResponse, HTTPException, HTTPSuccessResponse},
+};
+
+#[get("/<tournament_id>/leagues/<league_id>/bracket")]
+pub async fn get_league_bracket(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let bracket = BracketMatch::get_bracket(user_id, tournament_id, league_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(bracket).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[post(
+    "/<tournament_id>/leagues/<league_id>/bracket",
+    format = "json",
+    data = "<generation_data>"
+)]
+pub async fn generate_league_bracket(
+    tournament_id: u64,
+    league_id: u64,
+    generation_data: Json<BracketGenerationDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let bracket = BracketMatch::generate_bracket(
+        user_id,
+        tournament_id,
+        league_id,
+        generation_data.0,
+        db_pool,
+    )
+    .await?;
+
+    Ok(HTTPSuccessResponse::Created(CustomResponse {
+        message: String::from("Successfully generated the bracket"),
+        data: serde_json::to_value(bracket).unwrap(),
+        pagination: None,
+    }))
+}
@@ -0,0 +1,50 @@
+use rocket::{http::ContentType, State};
+use sqlx::{MySql, Pool};
+
+use crate::{
+    jwt_auth_handler::UserToken,
+    models::{league::League, team::Team, user::User},
+    responses::{ErrorResponse, FileDisposition, FileResponse, HTTPException},
+};
+
+/// The content type of the calendar feeds, with the charset since the league and team names can have any character.
+/// The feeds are sent inline, so the calendar apps can subscribe to them instead of downloading a file
+fn calendar_content_type() -> ContentType {
+    ContentType::Calendar.with_params(("charset", "utf-8"))
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/calendar.ics")]
+pub async fn get_league_calendar(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let calendar = League::get_league_calendar(user_id, tournament_id, league_id, db_pool).await?;
+
+    Ok(FileResponse {
+        file_name: format!("league-{}.ics", league_id),
+        content_type: calendar_content_type(),
+        content: calendar,
+        disposition: FileDisposition::Inline,
+    })
+}
+
+#[get("/<tournament_id>/teams/<team_id>/calendar.ics")]
+pub async fn get_team_calendar(
+    tournament_id: u64,
+    team_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let calendar = Team::get_team_calendar(user_id, tournament_id, team_id, db_pool).await?;
+
+    Ok(FileResponse {
+        file_name: format!("team-{}.ics", team_id),
+        content_type: calendar_content_type(),
+        content: calendar,
+        disposition: FileDisposition::Inline,
+    })
+}
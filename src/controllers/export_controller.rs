@@ -0,0 +1,163 @@
+use rocket::{http::ContentType, State};
+use sqlx::{MySql, Pool};
+
+use crate::{
+    jwt_auth_handler::UserToken,
+    models::{export::ExportTable, league::League, tournament::Tournament, user::User},
+    responses::{
+        CustomResponse, ErrorResponse, FileDisposition, FileResponse, HTTPException,
+        HTTPSuccessResponse,
+    },
+};
+
+#[get("/<tournament_id>/leagues/<league_id>/export")]
+pub async fn export_league(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let export = League::export_league(user_id, tournament_id, league_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(export).unwrap(),
+        pagination: None,
+    }))
+}
+
+async fn league_csv(
+    tournament_id: u64,
+    league_id: u64,
+    table: ExportTable,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &Pool<MySql>,
+) -> Result<FileResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let content =
+        League::export_league_csv(user_id, tournament_id, league_id, table, db_pool).await?;
+
+    let table_name = match table {
+        ExportTable::Teams => "teams",
+        ExportTable::Fixtures => "fixtures",
+        ExportTable::StandingTable => "standing-table",
+    };
+
+    Ok(FileResponse {
+        file_name: format!("league-{}-{}.csv", league_id, table_name),
+        content_type: ContentType::CSV,
+        content,
+        disposition: FileDisposition::Attachment,
+    })
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/export/teams.csv")]
+pub async fn export_league_teams(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    league_csv(tournament_id, league_id, ExportTable::Teams, token, db_pool).await
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/export/fixtures.csv")]
+pub async fn export_league_fixtures(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    league_csv(
+        tournament_id,
+        league_id,
+        ExportTable::Fixtures,
+        token,
+        db_pool,
+    )
+    .await
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/export/standing-table.csv")]
+pub async fn export_league_standing_table(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    league_csv(
+        tournament_id,
+        league_id,
+        ExportTable::StandingTable,
+        token,
+        db_pool,
+    )
+    .await
+}
+
+#[get("/<tournament_id>/export")]
+pub async fn export_tournament(
+    tournament_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let export = Tournament::export_tournament(user_id, tournament_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(export).unwrap(),
+        pagination: None,
+    }))
+}
+
+async fn tournament_csv(
+    tournament_id: u64,
+    table: ExportTable,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &Pool<MySql>,
+) -> Result<FileResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let content = Tournament::export_tournament_csv(user_id, tournament_id, table, db_pool).await?;
+
+    let table_name = match table {
+        ExportTable::Teams => "teams",
+        ExportTable::Fixtures => "fixtures",
+        ExportTable::StandingTable => "standing-tables",
+    };
+
+    Ok(FileResponse {
+        file_name: format!("tournament-{}-{}.csv", tournament_id, table_name),
+        content_type: ContentType::CSV,
+        content,
+        disposition: FileDisposition::Attachment,
+    })
+}
+
+#[get("/<tournament_id>/export/teams.csv")]
+pub async fn export_tournament_teams(
+    tournament_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    tournament_csv(tournament_id, ExportTable::Teams, token, db_pool).await
+}
+
+#[get("/<tournament_id>/export/fixtures.csv")]
+pub async fn export_tournament_fixtures(
+    tournament_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    tournament_csv(tournament_id, ExportTable::Fixtures, token, db_pool).await
+}
+
+#[get("/<tournament_id>/export/standing-tables.csv")]
+pub async fn export_tournament_standing_tables(
+    tournament_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<FileResponse, HTTPException> {
+    tournament_csv(tournament_id, ExportTable::StandingTable, token, db_pool).await
+}
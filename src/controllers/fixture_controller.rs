@@ -1,25 +1,37 @@
 use rocket::{serde::json::Json, State};
+use serde_json::json;
 use sqlx::{MySql, Pool};
 
 use crate::{
     jwt_auth_handler::UserToken,
-    models::{fixture::{EditFixtureDTO, Fixture}, league::League, user::User},
+    models::{
+        fixture::{
+            EditFixtureDTO, EditFixturePairingDTO, Fixture, FixtureConstraintsDTO,
+            FixtureGenerationQuery, FixtureListQuery, NewFixtureDTO, SubmittedScheduleDTO,
+        },
+        league::League,
+        user::User,
+    },
     responses::{CustomResponse, ErrorResponse, HTTPException, HTTPSuccessResponse},
 };
 
-#[get("/<tournament_id>/leagues/<league_id>/fixtures")]
+#[get("/<tournament_id>/leagues/<league_id>/fixtures?<list_query..>")]
 pub async fn get_league_fixtures(
     tournament_id: u64,
     league_id: u64,
+    list_query: FixtureListQuery,
     token: Result<UserToken, ErrorResponse>,
     db_pool: &State<Pool<MySql>>,
 ) -> Result<HTTPSuccessResponse, HTTPException> {
     let user_id = User::get_user_id_by_token(token).unwrap_or(0);
-    let fixtures = League::get_league_fixtures(user_id, tournament_id, league_id, db_pool).await?;
+    let (fixtures, pagination) =
+        League::get_league_fixtures_page(user_id, tournament_id, league_id, &list_query, db_pool)
+            .await?;
 
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::new(),
         data: serde_json::to_value(fixtures).unwrap(),
+        pagination: Some(pagination),
     }))
 }
 
@@ -36,22 +48,122 @@ pub async fn delete_fixtures_from_league(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("Successfully deleted fixtures"),
         data: serde_json::to_value("").unwrap(),
+        pagination: None,
     }))
 }
 
-#[post("/<tournament_id>/leagues/<league_id>/fixtures")]
+#[post("/<tournament_id>/leagues/<league_id>/fixtures?<generation_query..>")]
 pub async fn generate_fixtures(
     tournament_id: u64,
     league_id: u64,
+    generation_query: FixtureGenerationQuery,
     token: Result<UserToken, ErrorResponse>,
     db_pool: &State<Pool<MySql>>,
 ) -> Result<HTTPSuccessResponse, HTTPException> {
     let user_id = User::get_user_id_by_token(token)?;
-    Fixture::generate_league_fixtures(user_id, tournament_id, league_id, db_pool).await?;
+    let changes = Fixture::generate_league_fixtures(
+        user_id,
+        tournament_id,
+        league_id,
+        &generation_query,
+        db_pool,
+    )
+    .await?;
 
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("Successfully generated fixtures"),
-        data: serde_json::to_value("").unwrap(),
+        data: serde_json::to_value(changes).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[post(
+    "/<tournament_id>/leagues/<league_id>/fixtures/schedule",
+    format = "json",
+    data = "<constraints>"
+)]
+pub async fn schedule_fixtures(
+    tournament_id: u64,
+    league_id: u64,
+    constraints: Json<FixtureConstraintsDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let scheduled_fixtures = Fixture::schedule_league_fixtures(
+        user_id,
+        tournament_id,
+        league_id,
+        constraints.0,
+        db_pool,
+    )
+    .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::from("Successfully scheduled fixtures"),
+        data: serde_json::to_value(scheduled_fixtures).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[post("/<tournament_id>/leagues/<league_id>/fixtures/swiss")]
+pub async fn generate_swiss_round(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let swiss_round =
+        Fixture::generate_swiss_round(user_id, tournament_id, league_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Created(CustomResponse {
+        message: String::from("Successfully paired the swiss round"),
+        data: serde_json::to_value(swiss_round).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/fixtures/preview?<seed>")]
+pub async fn preview_fixtures(
+    tournament_id: u64,
+    league_id: u64,
+    seed: Option<u64>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let preview =
+        Fixture::preview_league_fixtures(user_id, tournament_id, league_id, seed, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(preview).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[put(
+    "/<tournament_id>/leagues/<league_id>/fixtures",
+    format = "json",
+    data = "<schedule>"
+)]
+pub async fn submit_fixtures(
+    tournament_id: u64,
+    league_id: u64,
+    schedule: Json<SubmittedScheduleDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let created_fixtures =
+        Fixture::submit_league_fixtures(user_id, tournament_id, league_id, schedule.0, db_pool)
+            .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::from("Successfully saved fixtures"),
+        data: serde_json::to_value(created_fixtures).unwrap(),
+        pagination: None,
     }))
 }
 
@@ -70,6 +182,7 @@ pub async fn get_fixture_by_id(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::new(),
         data: serde_json::to_value(fixture).unwrap(),
+        pagination: None,
     }))
 }
 
@@ -86,7 +199,7 @@ pub async fn edit_fixture(
     db_pool: &State<Pool<MySql>>,
 ) -> Result<HTTPSuccessResponse, HTTPException> {
     let user_id = User::get_user_id_by_token(token)?;
-    let fixture = Fixture::edit_fixture_by_id(
+    let league_completed = Fixture::edit_fixture_by_id(
         user_id,
         tournament_id,
         league_id,
@@ -98,6 +211,90 @@ pub async fn edit_fixture(
 
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("Successfully edited fixture"),
-        data: serde_json::to_value(fixture).unwrap(),
+        data: serde_json::to_value(json!({
+            "league_completed": league_completed,
+        }))
+        .unwrap(),
+        pagination: None,
+    }))
+}
+
+#[post(
+    "/<tournament_id>/leagues/<league_id>/fixtures/new",
+    format = "json",
+    data = "<fixture_data>"
+)]
+pub async fn create_fixture(
+    tournament_id: u64,
+    league_id: u64,
+    fixture_data: Json<NewFixtureDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let created_fixture_id =
+        Fixture::create_fixture(user_id, tournament_id, league_id, fixture_data.0, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Created(CustomResponse {
+        message: String::from("Fixture created successfully"),
+        data: serde_json::to_value(json!({
+            "id": created_fixture_id
+        }))
+        .unwrap(),
+        pagination: None,
+    }))
+}
+
+#[put(
+    "/<tournament_id>/leagues/<league_id>/fixtures/<fixture_id>/pairing",
+    format = "json",
+    data = "<pairing_data>"
+)]
+pub async fn edit_fixture_pairing(
+    tournament_id: u64,
+    league_id: u64,
+    fixture_id: u64,
+    pairing_data: Json<EditFixturePairingDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    Fixture::edit_fixture_pairing(
+        user_id,
+        tournament_id,
+        league_id,
+        fixture_id,
+        pairing_data.0,
+        db_pool,
+    )
+    .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::from("Successfully edited fixture"),
+        data: serde_json::to_value("").unwrap(),
+        pagination: None,
+    }))
+}
+
+#[delete("/<tournament_id>/leagues/<league_id>/fixtures/<fixture_id>")]
+pub async fn delete_fixture(
+    tournament_id: u64,
+    league_id: u64,
+    fixture_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let league_completed =
+        Fixture::delete_fixture_by_id(user_id, tournament_id, league_id, fixture_id, db_pool)
+            .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::from("Successfully deleted fixture"),
+        data: serde_json::to_value(json!({
+            "league_completed": league_completed,
+        }))
+        .unwrap(),
+        pagination: None,
     }))
 }
@@ -0,0 +1,55 @@
+use rocket::{serde::json::Json, State};
+use sqlx::{MySql, Pool};
+
+use crate::{
+    jwt_auth_handler::UserToken,
+    models::{import::LeagueImportDTO, league::League, user::User},
+    responses::{CustomResponse, ErrorResponse, HTTPException, HTTPSuccessResponse},
+};
+
+#[post(
+    "/<tournament_id>/leagues/<league_id>/import/preview",
+    format = "json",
+    data = "<import_data>"
+)]
+pub async fn preview_league_import(
+    tournament_id: u64,
+    league_id: u64,
+    import_data: Json<LeagueImportDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let report =
+        League::preview_league_import(user_id, tournament_id, league_id, &import_data, db_pool)
+            .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(report).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[post(
+    "/<tournament_id>/leagues/<league_id>/import",
+    format = "json",
+    data = "<import_data>"
+)]
+pub async fn import_league(
+    tournament_id: u64,
+    league_id: u64,
+    import_data: Json<LeagueImportDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let report =
+        League::import_league(user_id, tournament_id, league_id, import_data.0, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Created(CustomResponse {
+        message: String::from("League imported successfully"),
+        data: serde_json::to_value(report).unwrap(),
+        pagination: None,
+    }))
+}
@@ -6,7 +6,10 @@ use sqlx::{MySql, Pool};
 use crate::{
     jwt_auth_handler::UserToken,
     models::{
-        league::{League, LeagueRegisterDTO},
+        league::{
+            ArchiveListQuery, League, LeagueListQuery, LeagueRegisterDTO, NewSeasonDTO,
+            WithdrawalPolicy,
+        },
         user::User,
     },
     responses::{CustomResponse, ErrorResponse, HTTPException, HTTPSuccessResponse},
@@ -25,22 +28,97 @@ pub async fn get_league(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::new(),
         data: serde_json::to_value(league).unwrap(),
+        pagination: None,
     }))
 }
 
-#[get("/<tournament_id>/leagues/<league_id>/standing-table")]
+#[get("/<tournament_id>/leagues?<list_query..>")]
+pub async fn get_tournament_leagues(
+    tournament_id: u64,
+    list_query: LeagueListQuery,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let (leagues, pagination) =
+        League::get_tournament_leagues_page(user_id, tournament_id, &list_query, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(leagues).unwrap(),
+        pagination: Some(pagination),
+    }))
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/standing-table?<after_round>")]
 pub async fn get_league_standing_table(
     tournament_id: u64,
     league_id: u64,
+    after_round: Option<u16>,
     token: Result<UserToken, ErrorResponse>,
     db_pool: &State<Pool<MySql>>
 ) -> Result<HTTPSuccessResponse, HTTPException> {
     let user_id = User::get_user_id_by_token(token).unwrap_or(0);
-    let standing_table = League::get_league_standing_table(user_id, tournament_id, league_id, db_pool).await?;
+    let standing_table = League::get_league_standing_table(league_id, user_id, tournament_id, after_round, db_pool).await?;
 
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::new(),
         data: serde_json::to_value(standing_table).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/standing-progression")]
+pub async fn get_league_standing_progression(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let progression =
+        League::get_league_standing_progression(league_id, user_id, tournament_id, db_pool)
+            .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(progression).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/<tournament_id>/archive?<list_query..>")]
+pub async fn get_tournament_archive(
+    tournament_id: u64,
+    list_query: ArchiveListQuery,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let (leagues, pagination) =
+        League::get_tournament_archive(user_id, tournament_id, &list_query, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(leagues).unwrap(),
+        pagination: Some(pagination),
+    }))
+}
+
+#[get("/<tournament_id>/archive/<league_id>")]
+pub async fn get_archived_season(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let season = League::get_archived_season(user_id, tournament_id, league_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(season).unwrap(),
+        pagination: None,
     }))
 }
 
@@ -60,6 +138,25 @@ pub async fn create_new_league(
             "id": new_league,
         }))
         .unwrap(),
+        pagination: None,
+    }))
+}
+
+#[post("/<tournament_id>/seasons", format = "json", data = "<season_data>")]
+pub async fn create_new_season(
+    tournament_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    season_data: Json<NewSeasonDTO>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let new_leagues =
+        League::create_new_season(user_id, tournament_id, season_data.0, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Created(CustomResponse {
+        message: String::from("Season created successfully"),
+        data: serde_json::to_value(new_leagues).unwrap(),
+        pagination: None,
     }))
 }
 
@@ -77,6 +174,7 @@ pub async fn league_add_team(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("Team added successfully"),
         data: serde_json::to_value("").unwrap(),
+        pagination: None,
     }))
 }
 
@@ -98,6 +196,7 @@ pub async fn edit_league(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("League edited successfully"),
         data: serde_json::to_value("").unwrap(),
+        pagination: None,
     }))
 }
 
@@ -114,16 +213,25 @@ pub async fn delete_league(
     Ok(status::Custom(Status::NoContent, ""))
 }
 
-#[delete("/<tournament_id>/leagues/<league_id>/teams/<team_id>")]
+#[delete("/<tournament_id>/leagues/<league_id>/teams/<team_id>?<policy>")]
 pub async fn league_remove_team(
     tournament_id: u64,
     league_id: u64,
     team_id: u64,
+    policy: Option<WithdrawalPolicy>,
     token: Result<UserToken, ErrorResponse>,
     db_pool: &State<Pool<MySql>>,
 ) -> Result<status::Custom<&str>, HTTPException> {
     let user_id = User::get_user_id_by_token(token)?;
-    League::remove_team_from_league(user_id, tournament_id, league_id, team_id, db_pool).await?;
+    League::remove_team_from_league(
+        user_id,
+        tournament_id,
+        league_id,
+        team_id,
+        policy.unwrap_or_default(),
+        db_pool,
+    )
+    .await?;
 
     Ok(status::Custom(Status::NoContent, ""))
 }
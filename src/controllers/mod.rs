@@ -1,5 +1,10 @@
 pub mod authentication_controller;
+pub mod bracket_controller;
+pub mod calendar_controller;
+pub mod export_controller;
 pub mod fixture_controller;
+pub mod import_controller;
 pub mod league_controller;
 pub mod team_controller;
+pub mod tie_controller;
 pub mod tournament_controller;
@@ -6,7 +6,7 @@ use sqlx::{MySql, Pool};
 use crate::{
     jwt_auth_handler::UserToken,
     models::{
-        team::{Team, TeamRegisterDTO},
+        team::{Team, TeamListQuery, TeamRegisterDTO},
         user::User,
     },
     responses::{CustomResponse, ErrorResponse, HTTPException, HTTPSuccessResponse},
@@ -26,6 +26,61 @@ pub async fn get_team(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::new(),
         data: serde_json::to_value(team_info).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/<tournament_id>/teams/<team_id>/profile")]
+pub async fn get_team_profile(
+    tournament_id: u64,
+    team_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let team_profile = Team::get_team_profile(user_id, tournament_id, team_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(team_profile).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/<tournament_id>/teams/<team_id>/vs/<opponent_id>")]
+pub async fn get_head_to_head(
+    tournament_id: u64,
+    team_id: u64,
+    opponent_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let head_to_head =
+        Team::get_head_to_head(user_id, tournament_id, team_id, opponent_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(head_to_head).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/<tournament_id>/teams?<list_query..>")]
+pub async fn get_tournament_teams(
+    tournament_id: u64,
+    list_query: TeamListQuery,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let (teams, pagination) =
+        Team::get_tournament_teams_page(user_id, tournament_id, &list_query, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(teams).unwrap(),
+        pagination: Some(pagination),
     }))
 }
 
@@ -45,6 +100,7 @@ pub async fn create_team(
             "id": created_team_id
         }))
         .unwrap(),
+        pagination: None,
     }))
 }
 
@@ -66,6 +122,7 @@ pub async fn edit_team(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("Team edited successfully!"),
         data: serde_json::to_value("").unwrap(),
+        pagination: None,
     }))
 }
 
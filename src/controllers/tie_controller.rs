@@ -0,0 +1,104 @@
+use rocket::{serde::json::Json, State};
+use serde_json::json;
+use sqlx::{MySql, Pool};
+
+use crate::{
+    jwt_auth_handler::UserToken,
+    models::{
+        tie::{NewTieDTO, Tie, TieExtraTimeDTO},
+        user::User,
+    },
+    responses::{CustomResponse, ErrorResponse, HTTPException, HTTPSuccessResponse},
+};
+
+#[get("/<tournament_id>/leagues/<league_id>/ties")]
+pub async fn get_league_ties(
+    tournament_id: u64,
+    league_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let ties = Tie::get_league_ties(user_id, tournament_id, league_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(ties).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/<tournament_id>/leagues/<league_id>/ties/<tie_id>")]
+pub async fn get_tie_by_id(
+    tournament_id: u64,
+    league_id: u64,
+    tie_id: u64,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token).unwrap_or(0);
+    let tie = Tie::get_tie_by_id(user_id, tournament_id, league_id, tie_id, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(tie).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[post(
+    "/<tournament_id>/leagues/<league_id>/ties",
+    format = "json",
+    data = "<tie_data>"
+)]
+pub async fn create_tie(
+    tournament_id: u64,
+    league_id: u64,
+    tie_data: Json<NewTieDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let created_tie_id =
+        Tie::create_tie(user_id, tournament_id, league_id, tie_data.0, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Created(CustomResponse {
+        message: String::from("Tie created successfully"),
+        data: serde_json::to_value(json!({
+            "id": created_tie_id
+        }))
+        .unwrap(),
+        pagination: None,
+    }))
+}
+
+#[put(
+    "/<tournament_id>/leagues/<league_id>/ties/<tie_id>",
+    format = "json",
+    data = "<extra_time_data>"
+)]
+pub async fn edit_tie(
+    tournament_id: u64,
+    league_id: u64,
+    tie_id: u64,
+    extra_time_data: Json<TieExtraTimeDTO>,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    Tie::edit_tie_extra_time(
+        user_id,
+        tournament_id,
+        league_id,
+        tie_id,
+        extra_time_data.0,
+        db_pool,
+    )
+    .await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::from("Successfully edited tie"),
+        data: serde_json::to_value("").unwrap(),
+        pagination: None,
+    }))
+}
@@ -3,10 +3,11 @@ use rocket::{response::status, serde::json::Json, State};
 use serde_json::json;
 use sqlx::{MySql, Pool};
 
-use crate::models::tournament::TournamentEditDTO;
+use crate::models::tournament::{TournamentEditDTO, TournamentListQuery};
 use crate::{
     jwt_auth_handler::UserToken,
     models::{
+        pagination::Pagination,
         tournament::{Tournament, TournamentRegisterDTO},
         user::User,
     },
@@ -29,6 +30,7 @@ pub async fn create_tournament(
             "id": new_tournament_id
         }))
         .unwrap(),
+        pagination: None,
     }))
 }
 
@@ -57,21 +59,62 @@ pub async fn edit_tournament(
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("Tournament edited successfully!"),
         data: serde_json::to_value("").unwrap(),
+        pagination: None,
     }))
 }
 
-#[get("/<tournament_id>")]
+#[get("/<tournament_id>?<limit>&<offset>")]
 pub async fn get_tournament(
     tournament_id: u64,
+    limit: Option<u32>,
+    offset: Option<u32>,
     token: Result<UserToken, ErrorResponse>,
     db_pool: &State<Pool<MySql>>,
 ) -> Result<HTTPSuccessResponse, HTTPException> {
     let user_id: Option<u64> = User::get_user_id_by_token(token).ok();
-    let tournament_information =
-        Tournament::get_tournament_information_by_id(user_id, tournament_id, &db_pool).await?;
+    let tournament_information = Tournament::get_tournament_information_by_id(
+        user_id,
+        tournament_id,
+        Pagination::new(limit, offset),
+        &db_pool,
+    )
+    .await?;
 
     Ok(HTTPSuccessResponse::Ok(CustomResponse {
         message: String::from("Tournament information here"),
         data: serde_json::to_value(tournament_information).unwrap(),
+        pagination: None,
+    }))
+}
+
+#[get("/?<list_query..>")]
+pub async fn get_user_tournaments(
+    list_query: TournamentListQuery,
+    token: Result<UserToken, ErrorResponse>,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let user_id = User::get_user_id_by_token(token)?;
+    let (tournaments, pagination) =
+        Tournament::get_user_tournaments_page(user_id, &list_query, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(tournaments).unwrap(),
+        pagination: Some(pagination),
+    }))
+}
+
+#[get("/public?<list_query..>")]
+pub async fn get_public_tournaments(
+    list_query: TournamentListQuery,
+    db_pool: &State<Pool<MySql>>,
+) -> Result<HTTPSuccessResponse, HTTPException> {
+    let (tournaments, pagination) =
+        Tournament::get_public_tournaments_page(&list_query, db_pool).await?;
+
+    Ok(HTTPSuccessResponse::Ok(CustomResponse {
+        message: String::new(),
+        data: serde_json::to_value(tournaments).unwrap(),
+        pagination: Some(pagination),
     }))
 }
@@ -1,7 +1,14 @@
+use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::str::FromStr;
 
 use chrono::Utc;
-use jsonwebtoken::{errors::Error, DecodingKey, EncodingKey, Header, TokenData, Validation};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{
+    errors::{Error, ErrorKind},
+    Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation,
+};
 use rocket::http::Status;
 use rocket::request::FromRequest;
 use rocket::request::Outcome;
@@ -16,11 +23,199 @@ pub struct UserToken {
     pub iat: i64,
     // expiration
     pub exp: i64,
+    // issuer, only present when `JWT_ISSUER` is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    // audience, only present when `JWT_AUDIENCE` is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    // restricts the token to a single flow, only present on the tokens which can't be used on the API
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
     // data
     pub user_id: u64,
 }
 
 const ONE_WEEK: i64 = 60 * 60 * 24 * 7;
+/// Scope of the tokens which keep the signed in user while an identity provider account is linked
+const OIDC_LINK_SCOPE: &str = "oidc_link";
+/// The account link must be finished on the identity provider in ten minutes
+const OIDC_LINK_EXPIRATION: i64 = 60 * 10;
+
+/// JWT settings loaded once when the server starts and stored on the Rocket managed state.
+///
+/// The settings are read from the following environment variables:
+/// * `JWT_ALGORITHM` - `HS256` (default), `RS256` or `EdDSA`
+/// * `JWT_SECRET` - The shared secret, only used by `HS256`
+/// * `JWT_PRIVATE_KEY_PATH` - Path to the PEM private key used to sign tokens with `RS256`/`EdDSA`
+/// * `JWT_JWKS_PATH` - Path to a JSON Web Key Set with every public key that is still accepted
+/// * `JWT_KEY_ID` - The `kid` of the key used to sign new tokens
+/// * `JWT_ISSUER` - Optional `iss` claim
+/// * `JWT_AUDIENCE` - Optional `aud` claim
+/// * `JWT_EXPIRATION_SECONDS` - Token lifetime, defaults to one week
+///
+/// To rotate a key, add the new public key to the key set, point `JWT_PRIVATE_KEY_PATH`/`JWT_KEY_ID` to the new key
+/// and keep the old public key on the set until every token signed with it has expired
+pub struct JwtConfig {
+    pub algorithm: Algorithm,
+    pub key_id: Option<String>,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+    pub expiration: i64,
+    encoding_key: EncodingKey,
+    // Keys used to verify the tokens, indexed by their `kid`
+    decoding_keys: HashMap<String, DecodingKey>,
+    // Public keys which are published on the JWKS endpoint
+    public_keys: JwkSet,
+}
+
+impl JwtConfig {
+    /// Responsible to load the JWT settings from the environment variables
+    ///
+    /// # Errors
+    /// `String` - A message describing the missing or invalid setting
+    pub fn from_env() -> Result<JwtConfig, String> {
+        let algorithm = match env::var("JWT_ALGORITHM") {
+            Ok(value) => Algorithm::from_str(&value)
+                .map_err(|_| format!("JWT_ALGORITHM has an unsupported value: {}", value))?,
+            Err(_) => Algorithm::HS256,
+        };
+
+        let key_id = env::var("JWT_KEY_ID").ok();
+        let issuer = env::var("JWT_ISSUER").ok();
+        let audience = env::var("JWT_AUDIENCE").ok();
+        let expiration = match env::var("JWT_EXPIRATION_SECONDS") {
+            Ok(value) => value
+                .parse::<i64>()
+                .map_err(|_| String::from("JWT_EXPIRATION_SECONDS must be a number"))?,
+            Err(_) => ONE_WEEK,
+        };
+
+        let jwt_config = match algorithm {
+            Algorithm::HS256 => {
+                let jwt_secret = env::var("JWT_SECRET")
+                    .map_err(|_| String::from("JWT_SECRET missing on env variables"))?;
+
+                JwtConfig::from_secret(&jwt_secret, key_id)
+            }
+            Algorithm::RS256 | Algorithm::EdDSA => {
+                let private_key_path = env::var("JWT_PRIVATE_KEY_PATH")
+                    .map_err(|_| String::from("JWT_PRIVATE_KEY_PATH missing on env variables"))?;
+                let jwks_path = env::var("JWT_JWKS_PATH")
+                    .map_err(|_| String::from("JWT_JWKS_PATH missing on env variables"))?;
+                let signing_key_id =
+                    key_id.ok_or_else(|| String::from("JWT_KEY_ID missing on env variables"))?;
+
+                let private_key = fs::read(&private_key_path).map_err(|_| {
+                    format!("Failed to read the private key at {}", private_key_path)
+                })?;
+                let jwks = fs::read_to_string(&jwks_path)
+                    .map_err(|_| format!("Failed to read the key set at {}", jwks_path))?;
+                let public_keys: JwkSet = serde_json::from_str(&jwks)
+                    .map_err(|_| String::from("JWT_JWKS_PATH does not contain a valid key set"))?;
+
+                JwtConfig::from_key_pair(algorithm, signing_key_id, &private_key, public_keys)?
+            }
+            _ => {
+                return Err(String::from(
+                    "JWT_ALGORITHM must be one of HS256, RS256 or EdDSA",
+                ))
+            }
+        };
+
+        Ok(JwtConfig {
+            issuer,
+            audience,
+            expiration,
+            ..jwt_config
+        })
+    }
+
+    /// Responsible to build the `HS256` settings, without issuer and audience and with tokens lasting one week
+    ///
+    /// # Arguments
+    /// * `secret` - The shared secret
+    /// * `key_id` - The optional `kid` of the tokens
+    pub fn from_secret(secret: &str, key_id: Option<String>) -> JwtConfig {
+        // The secret is never published, so the key set stays empty
+        let mut decoding_keys = HashMap::new();
+        decoding_keys.insert(
+            key_id.clone().unwrap_or_default(),
+            DecodingKey::from_secret(secret.as_ref()),
+        );
+
+        JwtConfig {
+            algorithm: Algorithm::HS256,
+            key_id,
+            issuer: None,
+            audience: None,
+            expiration: ONE_WEEK,
+            encoding_key: EncodingKey::from_secret(secret.as_ref()),
+            decoding_keys,
+            public_keys: JwkSet { keys: vec![] },
+        }
+    }
+
+    /// Responsible to build the `RS256`/`EdDSA` settings, without issuer and audience and with tokens lasting one week
+    ///
+    /// # Arguments
+    /// * `algorithm` - `RS256` or `EdDSA`
+    /// * `key_id` - The `kid` of the key used to sign new tokens, it must be on the key set
+    /// * `private_key` - The PEM private key used to sign new tokens
+    /// * `public_keys` - Every public key that is still accepted
+    ///
+    /// # Errors
+    /// `String` - A message describing the invalid key
+    pub fn from_key_pair(
+        algorithm: Algorithm,
+        key_id: String,
+        private_key: &[u8],
+        public_keys: JwkSet,
+    ) -> Result<JwtConfig, String> {
+        let encoding_key = match algorithm {
+            Algorithm::RS256 => EncodingKey::from_rsa_pem(private_key),
+            Algorithm::EdDSA => EncodingKey::from_ed_pem(private_key),
+            _ => {
+                return Err(String::from(
+                    "The key pair algorithm must be RS256 or EdDSA",
+                ))
+            }
+        }
+        .map_err(|_| String::from("The private key is not a valid PEM key of the algorithm"))?;
+
+        let mut decoding_keys = HashMap::new();
+        for jwk in &public_keys.keys {
+            let kid = jwk
+                .common
+                .key_id
+                .clone()
+                .ok_or_else(|| String::from("Every key on the key set must have a kid"))?;
+            let decoding_key = DecodingKey::from_jwk(jwk)
+                .map_err(|_| format!("The key {} on the key set is invalid", kid))?;
+            decoding_keys.insert(kid, decoding_key);
+        }
+
+        if !decoding_keys.contains_key(&key_id) {
+            return Err(format!("The signing key {} is not on the key set", key_id));
+        }
+
+        Ok(JwtConfig {
+            algorithm,
+            key_id: Some(key_id),
+            issuer: None,
+            audience: None,
+            expiration: ONE_WEEK,
+            encoding_key,
+            decoding_keys,
+            public_keys,
+        })
+    }
+
+    /// Returns the public keys that other services can use to verify the tokens
+    pub fn public_keys(&self) -> &JwkSet {
+        &self.public_keys
+    }
+}
 
 /// This function handles the requests. To use it you need to insert the following parameter:
 ///
@@ -40,14 +235,17 @@ impl<'r> FromRequest<'r> for UserToken {
         // If there's a "Authorization" on the coming header request
         // There's a "Bearer" string on the value
         // Split the "Bearer " string into 2 values, one being the Bearer and the other value being the token
-        // It decodes the token
+        // It decodes the token with the settings from the managed state
         // If everything is successful and the token is valid, it returns the token claims and it can be used on the specified token parameter at the route function
-        if let Some(auth_header) = request.headers().get_one("Authorization") {
+        if let (Some(auth_header), Some(jwt_config)) = (
+            request.headers().get_one("Authorization"),
+            request.rocket().state::<JwtConfig>(),
+        ) {
             let auth_string = auth_header.to_string();
             if auth_string.contains("Bearer") {
                 let bearer_vector: Vec<_> = auth_string.split("Bearer ").collect();
                 let token = bearer_vector[1];
-                if let Ok(token_data) = decode_token(token.to_string()) {
+                if let Ok(token_data) = decode_token(token.to_string(), jwt_config) {
                     return Outcome::Success(token_data.claims);
                 }
             }
@@ -68,39 +266,109 @@ impl<'r> FromRequest<'r> for UserToken {
 ///
 /// # Arguments
 /// * `user_id` - `u64` integer which represents the user id
+/// * `jwt_config` - `&JwtConfig` reference with the signing key and claims settings
+///
+/// # Errors
+/// `Error` - If something wrongs happens when trying to encode the token
+pub fn generate_token(user_id: u64, jwt_config: &JwtConfig) -> Result<String, Error> {
+    let now = Utc::now().timestamp();
+
+    let payload = UserToken {
+        iat: now,
+        exp: now + jwt_config.expiration,
+        iss: jwt_config.issuer.clone(),
+        aud: jwt_config.audience.clone(),
+        scope: None,
+        user_id,
+    };
+
+    encode_token(&payload, jwt_config)
+}
+
+/// Responsible to generate a short lived token which only identifies the user while an identity provider account is linked.
+/// It is rejected by `decode_token`, so it can't be used on the API
+///
+/// # Arguments
+/// * `user_id` - `u64` integer which represents the user id
+/// * `jwt_config` - `&JwtConfig` reference with the signing key and claims settings
 ///
 /// # Errors
 /// `Error` - If something wrongs happens when trying to encode the token
-pub fn generate_token(user_id: u64) -> Result<String, Error> {
-    let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET missing on env variables");
+pub fn generate_link_token(user_id: u64, jwt_config: &JwtConfig) -> Result<String, Error> {
     let now = Utc::now().timestamp();
 
     let payload = UserToken {
         iat: now,
-        exp: now + ONE_WEEK,
+        exp: now + OIDC_LINK_EXPIRATION,
+        iss: jwt_config.issuer.clone(),
+        aud: jwt_config.audience.clone(),
+        scope: Some(String::from(OIDC_LINK_SCOPE)),
         user_id,
     };
 
-    jsonwebtoken::encode(
-        &Header::default(),
-        &payload,
-        &EncodingKey::from_secret(jwt_secret.as_ref()),
-    )
+    encode_token(&payload, jwt_config)
+}
+
+fn encode_token(payload: &UserToken, jwt_config: &JwtConfig) -> Result<String, Error> {
+    let mut header = Header::new(jwt_config.algorithm);
+    header.kid = jwt_config.key_id.clone();
+
+    jsonwebtoken::encode(&header, payload, &jwt_config.encoding_key)
 }
 
 /// Responsible to decode a jwt token
 ///
 /// # Arguments
 /// * `token` - `String` value that represents the jwt token
+/// * `jwt_config` - `&JwtConfig` reference with the verification keys and claims settings
 ///
 /// # Errors
-/// * `Error` -  Happens if the token is invalid or something wrong failed while decoding it
-pub fn decode_token(token: String) -> Result<TokenData<UserToken>, Error> {
-    let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET missing on env variables");
-
-    jsonwebtoken::decode(
-        &token,
-        &DecodingKey::from_secret(jwt_secret.as_ref()),
-        &Validation::default(),
-    )
+/// * `Error` -  Happens if the token is invalid, it was signed by an unknown key, it is scoped to a single flow
+/// or something wrong failed while decoding it
+pub fn decode_token(token: String, jwt_config: &JwtConfig) -> Result<TokenData<UserToken>, Error> {
+    let token_data = decode_claims(token, jwt_config)?;
+
+    match token_data.claims.scope {
+        None => Ok(token_data),
+        Some(_) => Err(Error::from(ErrorKind::InvalidToken)),
+    }
+}
+
+/// Responsible to decode a token generated by `generate_link_token`
+///
+/// # Arguments
+/// * `token` - `String` value that represents the jwt token
+/// * `jwt_config` - `&JwtConfig` reference with the verification keys and claims settings
+///
+/// # Errors
+/// * `Error` -  Happens if the token is invalid, expired, it is not a link token or something wrong failed while decoding it
+pub fn decode_link_token(
+    token: String,
+    jwt_config: &JwtConfig,
+) -> Result<TokenData<UserToken>, Error> {
+    let token_data = decode_claims(token, jwt_config)?;
+
+    match token_data.claims.scope.as_deref() {
+        Some(OIDC_LINK_SCOPE) => Ok(token_data),
+        _ => Err(Error::from(ErrorKind::InvalidToken)),
+    }
+}
+
+fn decode_claims(token: String, jwt_config: &JwtConfig) -> Result<TokenData<UserToken>, Error> {
+    let header = jsonwebtoken::decode_header(&token)?;
+    let decoding_key = jwt_config
+        .decoding_keys
+        .get(&header.kid.unwrap_or_default())
+        .ok_or_else(|| Error::from(ErrorKind::InvalidToken))?;
+
+    let mut validation = Validation::new(jwt_config.algorithm);
+    if let Some(issuer) = &jwt_config.issuer {
+        validation.set_issuer(&[issuer]);
+    }
+    match &jwt_config.audience {
+        Some(audience) => validation.set_audience(&[audience]),
+        None => validation.validate_aud = false,
+    }
+
+    jsonwebtoken::decode(&token, decoding_key, &validation)
 }
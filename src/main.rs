@@ -1,20 +1,40 @@
 use dotenvy::dotenv;
+use jwt_auth_handler::JwtConfig;
+use oidc_handler::OidcConfig;
+use rocket::{Build, Rocket};
 use sqlx::MySqlPool;
 use std::env;
 
 use controllers::{
-    authentication_controller::{login, register},
+    authentication_controller::{
+        jwks, login, oidc_callback, oidc_link_redirect, oidc_login_redirect, register,
+    },
+    bracket_controller::{generate_league_bracket, get_league_bracket},
+    calendar_controller::{get_league_calendar, get_team_calendar},
+    export_controller::{
+        export_league, export_league_fixtures, export_league_standing_table, export_league_teams,
+        export_tournament, export_tournament_fixtures, export_tournament_standing_tables,
+        export_tournament_teams,
+    },
     fixture_controller::{
-        delete_fixtures_from_league, edit_fixture, generate_fixtures, get_fixture_by_id,
-        get_league_fixtures,
+        create_fixture, delete_fixture, delete_fixtures_from_league, edit_fixture,
+        edit_fixture_pairing, generate_fixtures, generate_swiss_round, get_fixture_by_id,
+        get_league_fixtures, preview_fixtures, schedule_fixtures, submit_fixtures,
     },
+    import_controller::{import_league, preview_league_import},
     league_controller::{
-        create_new_league, delete_league, edit_league, get_league, get_league_standing_table,
-        league_add_team, league_remove_team,
+        create_new_league, create_new_season, delete_league, edit_league, get_archived_season,
+        get_league, get_league_standing_progression, get_league_standing_table,
+        get_tournament_archive, get_tournament_leagues, league_add_team, league_remove_team,
     },
-    team_controller::{create_team, delete_team, edit_team, get_team},
+    team_controller::{
+        create_team, delete_team, edit_team, get_head_to_head, get_team, get_team_profile,
+        get_tournament_teams,
+    },
+    tie_controller::{create_tie, edit_tie, get_league_ties, get_tie_by_id},
     tournament_controller::{
-        create_tournament, delete_tournament, edit_tournament, get_tournament,
+        create_tournament, delete_tournament, edit_tournament, get_public_tournaments,
+        get_tournament, get_user_tournaments,
     },
 };
 
@@ -25,6 +45,7 @@ mod constants;
 mod controllers;
 mod jwt_auth_handler;
 mod models;
+mod oidc_handler;
 mod responses;
 mod services;
 #[cfg(test)]
@@ -39,6 +60,17 @@ fn index() -> &'static str {
 async fn rocket() -> _ {
     dotenv().ok();
 
+    let jwt_config = JwtConfig::from_env().expect("Invalid JWT settings on env variables");
+    let oidc_config =
+        OidcConfig::from_env().expect("Invalid OpenID Connect settings on env variables");
+
+    build_rocket(jwt_config, oidc_config).await
+}
+
+/// Builds the server with the given JWT and OpenID Connect settings, so the tests can use other settings than the environment ones
+async fn build_rocket(jwt_config: JwtConfig, oidc_config: Option<OidcConfig>) -> Rocket<Build> {
+    dotenv().ok();
+
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL missing on env variables");
     let pool = MySqlPool::connect(&database_url)
         .await
@@ -46,7 +78,17 @@ async fn rocket() -> _ {
 
     rocket::build()
         .mount("/", routes![index])
-        .mount("/authentication", routes![register, login])
+        .mount(
+            "/authentication",
+            routes![
+                register,
+                login,
+                oidc_login_redirect,
+                oidc_link_redirect,
+                oidc_callback
+            ],
+        )
+        .mount("/.well-known", routes![jwks])
         // Tournaments
         .mount(
             "/tournament",
@@ -54,13 +96,23 @@ async fn rocket() -> _ {
                 create_tournament,
                 delete_tournament,
                 edit_tournament,
-                get_tournament
+                get_tournament,
+                get_user_tournaments,
+                get_public_tournaments
             ],
         )
         // Teams
         .mount(
             "/tournament",
-            routes![create_team, edit_team, delete_team, get_team],
+            routes![
+                create_team,
+                edit_team,
+                delete_team,
+                get_team,
+                get_team_profile,
+                get_head_to_head,
+                get_tournament_teams
+            ],
         )
         // Leagues
         .mount(
@@ -70,9 +122,14 @@ async fn rocket() -> _ {
                 edit_league,
                 delete_league,
                 get_league,
+                get_tournament_leagues,
                 league_add_team,
                 league_remove_team,
-                get_league_standing_table
+                get_league_standing_table,
+                get_league_standing_progression,
+                create_new_season,
+                get_tournament_archive,
+                get_archived_season
             ],
         )
         // Fixtures
@@ -81,10 +138,50 @@ async fn rocket() -> _ {
             routes![
                 get_league_fixtures,
                 generate_fixtures,
+                schedule_fixtures,
+                generate_swiss_round,
+                preview_fixtures,
+                submit_fixtures,
                 get_fixture_by_id,
                 delete_fixtures_from_league,
-                edit_fixture
+                edit_fixture,
+                create_fixture,
+                edit_fixture_pairing,
+                delete_fixture
             ],
         )
+        // Brackets
+        .mount(
+            "/tournament",
+            routes![get_league_bracket, generate_league_bracket],
+        )
+        // Ties
+        .mount(
+            "/tournament",
+            routes![get_league_ties, get_tie_by_id, create_tie, edit_tie],
+        )
+        // Imports
+        .mount("/tournament", routes![preview_league_import, import_league])
+        // Exports
+        .mount(
+            "/tournament",
+            routes![
+                export_league,
+                export_league_teams,
+                export_league_fixtures,
+                export_league_standing_table,
+                export_tournament,
+                export_tournament_teams,
+                export_tournament_fixtures,
+                export_tournament_standing_tables
+            ],
+        )
+        // Calendars
+        .mount(
+            "/tournament",
+            routes![get_league_calendar, get_team_calendar],
+        )
         .manage::<MySqlPool>(pool)
+        .manage::<JwtConfig>(jwt_config)
+        .manage::<Option<OidcConfig>>(oidc_config)
 }
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+use super::{fixture::FixtureDataDTO, tie::TieAggregate};
+
+/// The part of a double elimination bracket which a match is on
+/// * `GrandFinal` - The winners bracket champion against the losers bracket champion
+/// * `GrandFinalReset` - Played only when the losers bracket champion wins the grand final, so both teams lost once
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BracketSide {
+    Winners,
+    Losers,
+    GrandFinal,
+    GrandFinalReset,
+}
+
+impl BracketSide {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            BracketSide::Winners => "winners",
+            BracketSide::Losers => "losers",
+            BracketSide::GrandFinal => "grand_final",
+            BracketSide::GrandFinalReset => "grand_final_reset",
+        }
+    }
+
+    pub fn from_sql(value: &str) -> BracketSide {
+        match value {
+            "losers" => BracketSide::Losers,
+            "grand_final" => BracketSide::GrandFinal,
+            "grand_final_reset" => BracketSide::GrandFinalReset,
+            _ => BracketSide::Winners,
+        }
+    }
+}
+
+/// Where the team of a bracket match slot comes from
+/// * `Seed` - The seeded team, `None` being a bye
+/// * `Winner` - The winner of the match with the number
+/// * `Loser` - The loser of the match with the number
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "type", content = "value")]
+pub enum BracketSlot {
+    Seed(Option<u64>),
+    Winner(u16),
+    Loser(u16),
+}
+
+impl BracketSlot {
+    /// Returns the seeded team, the source match and if the slot takes its `winner` or `loser`
+    pub fn as_sql(&self) -> (Option<u64>, Option<u16>, Option<&'static str>) {
+        match self {
+            BracketSlot::Seed(team_id) => (*team_id, None, None),
+            BracketSlot::Winner(match_number) => (None, Some(*match_number), Some("winner")),
+            BracketSlot::Loser(match_number) => (None, Some(*match_number), Some("loser")),
+        }
+    }
+
+    pub fn from_sql(
+        seed_team_id: Option<u64>,
+        source_match: Option<u16>,
+        source_result: Option<&str>,
+    ) -> BracketSlot {
+        match (source_match, source_result) {
+            (Some(match_number), Some("loser")) => BracketSlot::Loser(match_number),
+            (Some(match_number), _) => BracketSlot::Winner(match_number),
+            (None, _) => BracketSlot::Seed(seed_team_id),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BracketMatch {
+    pub match_number: u16,
+    pub bracket: BracketSide,
+    pub bracket_round: u16,
+    pub position: u16,
+    pub home_slot: BracketSlot,
+    pub away_slot: BracketSlot,
+    // The first leg when the match is two legged
+    pub fixture_id: Option<u64>,
+    pub two_legged: bool,
+    pub away_goals: bool,
+}
+
+/// `team_ids` is the seeding, from the first to the last seed. When it's not sent the league teams are seeded by their id.
+/// When `two_legged` is set, every match but the grand final and its reset is played as a tie
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BracketGenerationDTO {
+    pub team_ids: Option<Vec<u64>>,
+    #[serde(default)]
+    pub grand_final_reset: bool,
+    #[serde(default)]
+    pub two_legged: bool,
+    #[serde(default)]
+    pub away_goals: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BracketMatchDTO {
+    pub match_number: u16,
+    pub bracket: BracketSide,
+    pub bracket_round: u16,
+    pub position: u16,
+    pub home_slot: BracketSlot,
+    pub away_slot: BracketSlot,
+    // The teams are null while they're not known yet, or when the slot is a bye
+    pub home_team_id: Option<u64>,
+    pub away_team_id: Option<u64>,
+    pub fixture: Option<FixtureDataDTO>,
+    // Only present when the match is two legged, the fixture being its first leg
+    pub second_leg: Option<FixtureDataDTO>,
+    pub aggregate: Option<TieAggregate>,
+    // If the match is decided, the winner is null when both slots were byes
+    pub decided: bool,
+    pub winner_team_id: Option<u64>,
+    // The matches which the winner and the loser go to, null when they leave the bracket
+    pub winner_to_match: Option<u16>,
+    pub loser_to_match: Option<u16>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BracketDTO {
+    pub matches: Vec<BracketMatchDTO>,
+    // Only present after the grand final, or its reset, is decided
+    pub champion_team_id: Option<u64>,
+}
@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A dated fixture written as an event of the iCalendar feeds
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CalendarFixture {
+    pub id: u64,
+    pub league_name: String,
+    pub round: u16,
+    pub home_team_name: String,
+    pub away_team_name: String,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub played: bool,
+    pub playing_date: DateTime<Utc>,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    fixture::FixtureDataDTO,
+    import::LeagueImportDTO,
+    league::{LeagueInformationDTO, TeamStandingTable},
+    team::TeamInfoDTO,
+};
+
+/// The tables which can be exported as a CSV file
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportTable {
+    Teams,
+    Fixtures,
+    StandingTable,
+}
+
+/// Every table of a league. `import` has the teams and fixtures as the CSV files of the league import,
+/// so they can be sent as they are to import the league again
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LeagueExportDTO {
+    pub league: LeagueInformationDTO,
+    pub teams: Vec<TeamInfoDTO>,
+    pub fixtures: Vec<FixtureDataDTO>,
+    // The overall standing table
+    pub standing_table: Vec<TeamStandingTable>,
+    pub import: LeagueImportDTO,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TournamentExportDTO {
+    pub id: u64,
+    pub name: String,
+    pub teams: Vec<TeamInfoDTO>,
+    pub leagues: Vec<LeagueExportDTO>,
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::team::TeamInfoDTO;
+use super::{pagination::SortOrder, team::TeamInfoDTO};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Fixture {
@@ -26,6 +26,39 @@ pub struct FixtureObject {
     pub round: u16,
 }
 
+/// What changed on the league fixtures after they were generated
+#[derive(Deserialize, Serialize, Debug)]
+pub struct FixtureChangesDTO {
+    // How many fixtures were left as they were, played fixtures are always kept
+    pub kept_fixtures: usize,
+    // The ids of the pending fixtures which were deleted
+    pub removed_fixtures: Vec<u64>,
+    pub created_fixtures: Vec<FixtureObject>,
+    // The seed used to shuffle the teams, generating with it again gives the same draw
+    pub seed: u64,
+}
+
+/// A draw which was not saved, the organiser can edit it and submit it with `SubmittedScheduleDTO`
+#[derive(Deserialize, Serialize, Debug)]
+pub struct FixturePreviewDTO {
+    pub fixtures: Vec<Vec<FixtureObject>>,
+    // The seed used to shuffle the teams
+    pub seed: u64,
+}
+
+/// A fixture of a submitted schedule, the round is where it's on the schedule
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SubmittedFixtureDTO {
+    pub home_team_id: u64,
+    pub away_team_id: u64,
+}
+
+/// A schedule edited by the organiser, each item of `fixtures` is a round, starting from the first one
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SubmittedScheduleDTO {
+    pub fixtures: Vec<Vec<SubmittedFixtureDTO>>,
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct FixtureDataDTO {
     pub id: u64,
@@ -43,3 +76,121 @@ pub struct EditFixtureDTO {
     pub away_score: u8,
     pub played: bool,
 }
+
+/// The fixtures of a new swiss round
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SwissRoundDTO {
+    pub round: u16,
+    pub created_fixtures: Vec<FixtureObject>,
+    // The team without an opponent on the round, when the teams quantity is odd
+    pub bye_team_id: Option<u64>,
+}
+
+/// A fixture created by hand, both teams must be on the league
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NewFixtureDTO {
+    pub home_team_id: u64,
+    pub away_team_id: u64,
+    pub round: u16,
+}
+
+/// Changes to the teams or round of a fixture, the fields which are not sent are kept
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EditFixturePairingDTO {
+    pub home_team_id: Option<u64>,
+    pub away_team_id: Option<u64>,
+    pub round: Option<u16>,
+}
+
+#[derive(FromFormField, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum FixtureSort {
+    Round,
+    Date,
+    Id,
+}
+
+/// Query string accepted by the league fixtures listing.
+/// `from` and `to` are dates on the `YYYY-MM-DD` format and both are inclusive
+#[derive(FromForm, Deserialize, Serialize, Debug, Default)]
+pub struct FixtureListQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub round: Option<u16>,
+    pub team_id: Option<u64>,
+    pub played: Option<bool>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub sort: Option<FixtureSort>,
+    pub order: Option<SortOrder>,
+}
+
+/// Query string accepted when generating the league fixtures.
+/// `regenerate` allows generating them again when the league already has fixtures, keeping the played ones.
+/// `seed` shuffles the teams before the draw, when it's not sent the league seed is reused on a regeneration or a new one is generated
+#[derive(FromForm, Deserialize, Serialize, Debug, Default)]
+pub struct FixtureGenerationQuery {
+    pub regenerate: Option<bool>,
+    pub seed: Option<u64>,
+}
+
+/// Two teams sharing a ground, they can't be both home on the same round
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SharedGroundConstraint {
+    pub first_team_id: u64,
+    pub second_team_id: u64,
+}
+
+/// A date which the team can't play, `date` is on the `YYYY-MM-DD` format
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct UnavailableDateConstraint {
+    pub team_id: u64,
+    pub date: String,
+}
+
+/// A fixture between two teams which must be played on the round, e.g. a derby
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct FixedRoundConstraint {
+    pub first_team_id: u64,
+    pub second_team_id: u64,
+    pub round: u16,
+}
+
+/// Constraints accepted by the fixtures scheduler.
+/// The rounds are dated from `start_date` every `days_between_rounds` days, which is needed for the `unavailable_dates`
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct FixtureConstraintsDTO {
+    pub start_date: Option<String>,
+    pub days_between_rounds: Option<u16>,
+    #[serde(default)]
+    pub shared_grounds: Vec<SharedGroundConstraint>,
+    #[serde(default)]
+    pub unavailable_dates: Vec<UnavailableDateConstraint>,
+    #[serde(default)]
+    pub fixed_rounds: Vec<FixedRoundConstraint>,
+    pub seed: Option<u64>,
+}
+
+/// The constraints which the scheduler could not meet
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct UnmetConstraintsDTO {
+    pub shared_grounds: Vec<SharedGroundConstraint>,
+    pub unavailable_dates: Vec<UnavailableDateConstraint>,
+    pub fixed_rounds: Vec<FixedRoundConstraint>,
+}
+
+impl UnmetConstraintsDTO {
+    pub fn len(&self) -> usize {
+        self.shared_grounds.len() + self.unavailable_dates.len() + self.fixed_rounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ScheduledFixturesDTO {
+    pub created_fixtures: Vec<FixtureObject>,
+    pub seed: u64,
+    pub unmet_constraints: UnmetConstraintsDTO,
+}
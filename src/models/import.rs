@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// The CSV files of a league import, the first line of each file is its header.
+/// * `teams` - The `name` column, a team of the tournament with the same name is added to the league instead of creating a new one
+/// * `fixtures` - The `home_team`, `away_team` and `round` columns, the optional `home_score` and `away_score` columns set the result
+#[derive(Deserialize, Serialize, Debug)]
+pub struct LeagueImportDTO {
+    pub teams: Option<String>,
+    pub fixtures: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFile {
+    Teams,
+    Fixtures,
+}
+
+/// The row is counted from the header, which is the row 1
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ImportRowErrorDTO {
+    pub file: ImportFile,
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ImportedTeamDTO {
+    pub row: usize,
+    pub name: String,
+    // null while previewing a team which is created by the import
+    pub team_id: Option<u64>,
+    pub created: bool,
+    pub added_to_league: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ImportedFixtureDTO {
+    pub row: usize,
+    pub home_team_name: String,
+    pub away_team_name: String,
+    pub round: u16,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub played: bool,
+}
+
+/// The rows of the import, nothing is saved while `errors` is not empty
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct LeagueImportReportDTO {
+    pub teams: Vec<ImportedTeamDTO>,
+    pub fixtures: Vec<ImportedFixtureDTO>,
+    pub errors: Vec<ImportRowErrorDTO>,
+    // true when the imported results complete a round robin league
+    pub league_completed: bool,
+}
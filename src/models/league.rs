@@ -1,17 +1,74 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::{pagination::SortOrder, team::TeamInfoDTO};
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct League {
     pub id: u64,
     pub tournament_id: u64,
     pub name: String,
     pub completed: bool,
+    pub fixture_seed: Option<u64>,
+    pub format: LeagueFormat,
+    pub swiss_rounds: Option<u16>,
+}
+
+/// How the league fixtures are paired
+/// * `RoundRobin` - Every team plays against each other once, the fixtures are drawn all at once
+/// * `Swiss` - One round is paired at a time, matching teams with the same points which did not play each other yet
+/// * `DoubleElimination` - The teams play on a winners and a losers bracket, a team is eliminated after losing twice
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LeagueFormat {
+    #[default]
+    RoundRobin,
+    Swiss,
+    DoubleElimination,
+}
+
+impl LeagueFormat {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            LeagueFormat::RoundRobin => "round_robin",
+            LeagueFormat::Swiss => "swiss",
+            LeagueFormat::DoubleElimination => "double_elimination",
+        }
+    }
+
+    pub fn from_sql(value: &str) -> LeagueFormat {
+        match value {
+            "swiss" => LeagueFormat::Swiss,
+            "double_elimination" => LeagueFormat::DoubleElimination,
+            _ => LeagueFormat::RoundRobin,
+        }
+    }
+}
+
+/// A range of positions highlighted on the standing table, e.g. promotion or relegation.
+/// Negative positions are counted from the bottom of the table, so `-3` to `-1` are the last 3 teams
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct LeagueZone {
+    pub label: String,
+    // Hexadecimal colour, e.g. "#1E90FF"
+    pub color: String,
+    pub start_position: i16,
+    pub end_position: i16,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct LeagueRegisterDTO {
     pub name: String,
     pub completed: bool,
+    // When it's not sent the league zones are kept as they are
+    #[serde(default)]
+    pub zones: Option<Vec<LeagueZone>>,
+    // When it's not sent a new league is a round robin and an edited one keeps its format
+    #[serde(default)]
+    pub format: Option<LeagueFormat>,
+    // Only accepted on swiss leagues. When it's not sent a new swiss league is completed by hand and an edited one keeps its rounds
+    #[serde(default)]
+    pub swiss_rounds: Option<u16>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -20,12 +77,27 @@ pub struct LeagueInformationDTO {
     pub name: String,
     pub tournament_id: u64,
     pub completed: bool,
+    // Only loaded when a single league is requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zones: Option<Vec<LeagueZone>>,
+    // The first team of the final standing table, only present on completed leagues when a single league is requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub champion: Option<TeamInfoDTO>,
+    // The seed of the latest fixtures draw, only present when a single league is requested and its fixtures were generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixture_seed: Option<u64>,
+    #[serde(default)]
+    pub format: LeagueFormat,
+    // The rounds a swiss league plays before it's completed, only present when a single swiss league is requested and they're set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swiss_rounds: Option<u16>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct TeamStandingTable {
     pub team_id: u64,
     pub team_name: String,
+    pub matches_played: u8,
     pub total_points: u8,
     pub win: u8,
     pub draw: u8,
@@ -33,9 +105,162 @@ pub struct TeamStandingTable {
     pub goals_scored: u8,
     pub goals_against: u8,
     pub goal_difference: i16,
+    // The latest results from the oldest to the newest one, e.g. "WWDLW"
+    pub form: String,
+    // The league zone which the team position is on, only present on the overall table
+    pub zone: Option<LeagueZone>,
+    // Swiss tiebreaks, only present on the overall table of swiss leagues.
+    // Buchholz is the sum of the opponents points, Sonneborn-Berger the points of the beaten opponents plus half of the drawn ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buchholz: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sonneborn_berger: Option<f32>,
+}
+
+/// Position of a team on the standings table right after a round
+#[derive(Deserialize, Serialize, Debug)]
+pub struct RoundPosition {
+    pub round: u16,
+    pub position: u32,
+    pub total_points: u8,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TeamPositionProgression {
+    pub team_id: u64,
+    pub team_name: String,
+    pub positions: Vec<RoundPosition>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct LeagueStandingsTable {
-    pub standings_table: Vec<TeamStandingTable>
-}
\ No newline at end of file
+    pub overall: Vec<TeamStandingTable>,
+    pub home: Vec<TeamStandingTable>,
+    pub away: Vec<TeamStandingTable>,
+}
+
+/// Which side of the fixtures are counted on a standing table
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum StandingVenue {
+    Overall,
+    Home,
+    Away,
+}
+
+impl StandingVenue {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            StandingVenue::Overall => "overall",
+            StandingVenue::Home => "home",
+            StandingVenue::Away => "away",
+        }
+    }
+}
+
+/// A division of the finished season and the name of the league which replaces it on the new season
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SeasonDivisionDTO {
+    pub league_id: u64,
+    pub name: String,
+}
+
+/// The divisions are ordered from the top to the bottom tier
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NewSeasonDTO {
+    pub divisions: Vec<SeasonDivisionDTO>,
+    // How many teams are promoted from and relegated to each division
+    pub exchanged_teams: u8,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NewSeasonLeagueDTO {
+    pub id: u64,
+    pub name: String,
+    pub previous_league_id: u64,
+    pub teams: Vec<u64>,
+    // Teams which came from the division below
+    pub promoted: Vec<u64>,
+    // Teams which came from the division above
+    pub relegated: Vec<u64>,
+}
+
+/// A completed league listed on the tournament archive
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ArchivedLeagueDTO {
+    pub id: u64,
+    pub name: String,
+    pub champion: Option<TeamInfoDTO>,
+}
+
+/// A row of the standing table saved when the league was completed
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ArchivedStanding {
+    pub position: u16,
+    pub team_id: u64,
+    pub team_name: String,
+    pub matches_played: u8,
+    pub total_points: u8,
+    pub win: u8,
+    pub draw: u8,
+    pub loss: u8,
+    pub goals_scored: u8,
+    pub goals_against: u8,
+    pub goal_difference: i16,
+    pub form: String,
+}
+
+/// A fixture saved when the league was completed, with the team names it had at that time
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ArchivedFixture {
+    pub fixture_id: u64,
+    pub round: u16,
+    pub home_team: TeamInfoDTO,
+    pub away_team: TeamInfoDTO,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub played: bool,
+    pub playing_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ArchivedSeasonDTO {
+    pub id: u64,
+    pub name: String,
+    pub champion: Option<TeamInfoDTO>,
+    pub standings: Vec<ArchivedStanding>,
+    pub fixtures: Vec<ArchivedFixture>,
+}
+
+#[derive(FromForm, Deserialize, Serialize, Debug, Default)]
+pub struct ArchiveListQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// What happens to the fixtures of a team which withdraws from the league.
+/// `Forfeit` is the default, so the played results are only deleted when it's asked for
+/// * `Expunge` - Every fixture of the team is deleted, as if it never played the league
+/// * `Forfeit` - The played fixtures are kept and the pending ones are awarded to the opponents
+#[derive(FromFormField, Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum WithdrawalPolicy {
+    Expunge,
+    #[default]
+    Forfeit,
+}
+
+#[derive(FromFormField, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum LeagueSort {
+    Name,
+    Id,
+}
+
+/// Query string accepted by the tournament leagues listing. `name` searches leagues which contains the value
+#[derive(FromForm, Deserialize, Serialize, Debug, Default)]
+pub struct LeagueListQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub name: Option<String>,
+    pub completed: Option<bool>,
+    pub sort: Option<LeagueSort>,
+    pub order: Option<SortOrder>,
+}
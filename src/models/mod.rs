@@ -2,4 +2,10 @@ pub mod league;
 pub mod team;
 pub mod tournament;
 pub mod user;
-pub mod fixture;
\ No newline at end of file
+pub mod fixture;
+pub mod pagination;
+pub mod bracket;
+pub mod tie;
+pub mod import;
+pub mod export;
+pub mod calendar;
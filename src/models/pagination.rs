@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PaginationData {
+    pub limit: u32,
+    pub offset: u32,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Pagination {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(FromFormField, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Pagination {
+    /// Builds the pagination from the optional query values, falling back to the first page
+    /// and making sure a client can't ask for more than `MAX_PAGE_SIZE` rows at once
+    pub fn new(limit: Option<u32>, offset: Option<u32>) -> Pagination {
+        Pagination {
+            limit: limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+            offset: offset.unwrap_or(0),
+        }
+    }
+
+    pub fn with_total(&self, total: u64) -> PaginationData {
+        PaginationData {
+            limit: self.limit,
+            offset: self.offset,
+            total,
+        }
+    }
+}
+
+/// Builds the `LIKE` pattern of a name search, escaping the `%` and `_` wildcards and the `\` escape character
+/// so the value is searched as it was typed
+pub fn contains_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+
+    format!("%{}%", escaped)
+}
+
+impl SortOrder {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
@@ -1,5 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::{league::TeamStandingTable, pagination::SortOrder};
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Team {
     pub id: u64,
@@ -17,3 +20,108 @@ pub struct TeamInfoDTO {
     pub id: u64,
     pub name: String,
 }
+
+#[derive(FromFormField, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum TeamSort {
+    Name,
+    Id,
+}
+
+/// Query string accepted by the tournament teams listing. `name` searches teams which contains the value
+#[derive(FromForm, Deserialize, Serialize, Debug, Default)]
+pub struct TeamListQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub name: Option<String>,
+    pub sort: Option<TeamSort>,
+    pub order: Option<SortOrder>,
+}
+
+/// Result of a played fixture from the point of view of a team
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum MatchResult {
+    #[serde(rename = "W")]
+    Win,
+    #[serde(rename = "D")]
+    Draw,
+    #[serde(rename = "L")]
+    Loss,
+}
+
+/// Aggregated results of a team over a set of played fixtures
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct TeamRecord {
+    pub played: u32,
+    pub win: u32,
+    pub draw: u32,
+    pub loss: u32,
+    pub goals_scored: u32,
+    pub goals_against: u32,
+    pub goal_difference: i32,
+}
+
+/// A league the team plays, with its current position on the standings table
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TeamLeagueSummary {
+    pub id: u64,
+    pub name: String,
+    pub completed: bool,
+    pub position: Option<u32>,
+    pub standing: Option<TeamStandingTable>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TeamFormEntry {
+    pub fixture_id: u64,
+    pub league_id: u64,
+    pub round: u16,
+    pub opponent: TeamInfoDTO,
+    pub home: bool,
+    pub goals_scored: u8,
+    pub goals_against: u8,
+    pub result: MatchResult,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TeamUpcomingFixture {
+    pub fixture_id: u64,
+    pub league_id: u64,
+    pub round: u16,
+    pub opponent: TeamInfoDTO,
+    pub home: bool,
+    pub playing_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TeamProfileDTO {
+    pub id: u64,
+    pub name: String,
+    pub leagues: Vec<TeamLeagueSummary>,
+    pub form: Vec<TeamFormEntry>,
+    pub next_fixtures: Vec<TeamUpcomingFixture>,
+    pub record: TeamRecord,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct HeadToHeadMeeting {
+    pub fixture_id: u64,
+    pub league_id: u64,
+    pub league_name: String,
+    pub round: u16,
+    pub home_team: TeamInfoDTO,
+    pub away_team: TeamInfoDTO,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub playing_date: Option<DateTime<Utc>>,
+}
+
+/// Record between two teams across every league of the tournament.
+/// `team_record` and `opponent_record` are the same results seen from each side
+#[derive(Deserialize, Serialize, Debug)]
+pub struct HeadToHeadDTO {
+    pub team: TeamInfoDTO,
+    pub opponent: TeamInfoDTO,
+    pub team_record: TeamRecord,
+    pub opponent_record: TeamRecord,
+    pub last_meetings: Vec<HeadToHeadMeeting>,
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use super::fixture::FixtureDataDTO;
+
+/// Two fixtures between the same teams, the second leg is played at the first leg away team ground.
+/// The extra time and penalties are played on the second leg, their scores are counted by the second leg home and away teams
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Tie {
+    pub id: u64,
+    pub league_id: u64,
+    pub first_leg_id: u64,
+    pub second_leg_id: u64,
+    // If the team which scored more goals away advances when the aggregate is level
+    pub away_goals: bool,
+    pub extra_time_home_score: Option<u8>,
+    pub extra_time_away_score: Option<u8>,
+    pub penalties_home_score: Option<u8>,
+    pub penalties_away_score: Option<u8>,
+}
+
+/// The teams and score of a fixture, used to compute the aggregate of a tie
+#[derive(Debug, Clone, Copy)]
+pub struct LegScore {
+    pub home_team_id: u64,
+    pub away_team_id: u64,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub played: bool,
+}
+
+impl From<&FixtureDataDTO> for LegScore {
+    fn from(fixture: &FixtureDataDTO) -> Self {
+        LegScore {
+            home_team_id: fixture.home_team.id,
+            away_team_id: fixture.away_team.id,
+            home_score: fixture.home_score,
+            away_score: fixture.away_score,
+            played: fixture.played,
+        }
+    }
+}
+
+/// What decided the winner of a tie
+/// * `Aggregate` - The goals of both legs
+/// * `AwayGoals` - The goals scored away, when the tie allows it
+/// * `ExtraTime` - The goals of both legs and the second leg extra time
+/// * `Penalties` - The second leg penalty shoot-out
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TieDecider {
+    Aggregate,
+    AwayGoals,
+    ExtraTime,
+    Penalties,
+}
+
+/// The aggregate score of a tie, counted for the first leg home and away teams
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+pub struct TieAggregate {
+    pub home_team_id: u64,
+    pub home_score: u16,
+    pub away_team_id: u64,
+    pub away_score: u16,
+    // Only present once both legs are played and the tie is decided
+    pub winner_team_id: Option<u64>,
+    pub decided_by: Option<TieDecider>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct NewTieDTO {
+    // The first leg home team
+    pub home_team_id: u64,
+    pub away_team_id: u64,
+    pub first_leg_round: u16,
+    pub second_leg_round: u16,
+    #[serde(default)]
+    pub away_goals: bool,
+}
+
+/// The second leg extra time and penalties, `null` removes them
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TieExtraTimeDTO {
+    pub extra_time_home_score: Option<u8>,
+    pub extra_time_away_score: Option<u8>,
+    pub penalties_home_score: Option<u8>,
+    pub penalties_away_score: Option<u8>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TieDTO {
+    pub id: u64,
+    pub first_leg: FixtureDataDTO,
+    pub second_leg: FixtureDataDTO,
+    pub away_goals: bool,
+    pub extra_time_home_score: Option<u8>,
+    pub extra_time_away_score: Option<u8>,
+    pub penalties_home_score: Option<u8>,
+    pub penalties_away_score: Option<u8>,
+    pub aggregate: TieAggregate,
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use super::pagination::SortOrder;
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Tournament {
@@ -38,6 +39,35 @@ pub struct TeamInformationData {
 pub struct TournamentInformationData {
     pub id: u64,
     pub name: String,
+    // A page of the leagues and teams, `leagues_total` and `teams_total` are the counts of every page
     pub leagues: Vec<LeagueInformationData>,
+    pub leagues_total: u64,
     pub teams: Vec<TeamInformationData>,
+    pub teams_total: u64,
+}
+
+/// A tournament on the tournament listings, without its leagues and teams
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TournamentSummaryData {
+    pub id: u64,
+    pub name: String,
+    pub public: bool,
+    pub leagues_total: u64,
+    pub teams_total: u64,
+}
+
+#[derive(FromFormField, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+pub enum TournamentSort {
+    Name,
+    Id,
+}
+
+/// Query string accepted by the tournament listings. `name` searches tournaments which contains the value
+#[derive(FromForm, Deserialize, Serialize, Debug, Default)]
+pub struct TournamentListQuery {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    pub name: Option<String>,
+    pub sort: Option<TournamentSort>,
+    pub order: Option<SortOrder>,
 }
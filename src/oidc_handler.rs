@@ -0,0 +1,330 @@
+use std::env;
+use std::str::FromStr;
+
+use jsonwebtoken::{jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::responses::HTTPException;
+
+/// Name of the cookie that keeps the `state` and `nonce` between the login redirect and the callback
+pub const OIDC_STATE_COOKIE: &str = "oidc_state";
+
+/// OpenID Connect settings loaded once when the server starts and stored on the Rocket managed state.
+/// The login flow is only enabled when `OIDC_ISSUER_URL` is set.
+///
+/// The settings are read from the following environment variables:
+/// * `OIDC_ISSUER_URL` - The identity provider issuer, used to find the discovery document
+/// * `OIDC_CLIENT_ID` - The client id registered on the identity provider
+/// * `OIDC_CLIENT_SECRET` - The client secret registered on the identity provider
+/// * `OIDC_REDIRECT_URL` - The URL of the `/authentication/oidc/callback` route
+/// * `OIDC_SCOPES` - Space separated scopes, defaults to `openid email profile`
+/// * `OIDC_ID_TOKEN_ALGORITHMS` - Space separated algorithms accepted on the `id_token`,
+/// defaults to the ones advertised on the discovery document
+pub struct OidcConfig {
+    pub issuer_url: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_url: String,
+    pub scopes: String,
+    pub id_token_algorithms: Option<Vec<Algorithm>>,
+}
+
+/// The part of the identity provider discovery document used by the login flow
+#[derive(Deserialize, Serialize, Debug)]
+pub struct OidcDiscovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    // When the identity provider does not advertise it, only `RS256` is accepted as the OpenID Connect specification requires
+    #[serde(default)]
+    pub id_token_signing_alg_values_supported: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct OidcTokenResponse {
+    pub id_token: String,
+}
+
+/// Claims from the identity provider `id_token`. `aud` and `exp` are checked while decoding, so they're not kept here
+#[derive(Deserialize, Serialize, Debug)]
+pub struct OidcClaims {
+    pub iss: String,
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+}
+
+impl OidcConfig {
+    /// Responsible to load the OpenID Connect settings from the environment variables
+    ///
+    /// # Returns
+    /// * `Ok(None)` - If `OIDC_ISSUER_URL` is not set, meaning the login flow is disabled
+    /// * `Ok(Some(OidcConfig))` - The loaded settings
+    ///
+    /// # Errors
+    /// `String` - A message describing the missing setting
+    pub fn from_env() -> Result<Option<OidcConfig>, String> {
+        let issuer_url = match env::var("OIDC_ISSUER_URL") {
+            Ok(value) => value,
+            Err(_) => return Ok(None),
+        };
+
+        let client_id = env::var("OIDC_CLIENT_ID")
+            .map_err(|_| String::from("OIDC_CLIENT_ID missing on env variables"))?;
+        let client_secret = env::var("OIDC_CLIENT_SECRET")
+            .map_err(|_| String::from("OIDC_CLIENT_SECRET missing on env variables"))?;
+        let redirect_url = env::var("OIDC_REDIRECT_URL")
+            .map_err(|_| String::from("OIDC_REDIRECT_URL missing on env variables"))?;
+        let scopes =
+            env::var("OIDC_SCOPES").unwrap_or_else(|_| String::from("openid email profile"));
+        let id_token_algorithms = match env::var("OIDC_ID_TOKEN_ALGORITHMS") {
+            Ok(value) => Some(
+                value
+                    .split_whitespace()
+                    .map(|algorithm| {
+                        Algorithm::from_str(algorithm).map_err(|_| {
+                            format!(
+                                "OIDC_ID_TOKEN_ALGORITHMS has an unsupported value: {}",
+                                algorithm
+                            )
+                        })
+                    })
+                    .collect::<Result<Vec<Algorithm>, String>>()?,
+            ),
+            Err(_) => None,
+        };
+
+        Ok(Some(OidcConfig {
+            issuer_url: issuer_url.trim_end_matches('/').to_string(),
+            client_id,
+            client_secret,
+            redirect_url,
+            scopes,
+            id_token_algorithms,
+        }))
+    }
+}
+
+/// Responsible to generate a random alphanumeric value from the operating system secure random generator,
+/// used on the `state`, the `nonce` and the passwords nobody knows
+///
+/// # Arguments
+/// * `length` - `usize` number of characters
+pub fn generate_secret(length: usize) -> String {
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+/// Responsible to fetch the identity provider discovery document
+///
+/// # Arguments
+/// * `oidc_config` - `&OidcConfig` reference with the identity provider settings
+///
+/// # Errors
+/// * `HTTPException::Internal` - If the identity provider could not be reached, the document is invalid or
+/// its `issuer` differs from the configured `OIDC_ISSUER_URL`
+pub async fn fetch_discovery(oidc_config: &OidcConfig) -> Result<OidcDiscovery, HTTPException> {
+    let discovery_url = format!(
+        "{}/.well-known/openid-configuration",
+        oidc_config.issuer_url
+    );
+
+    let response = reqwest::get(&discovery_url).await.map_err(|_| {
+        HTTPException::Internal(String::from(
+            "Failed to reach the identity provider. Please try again later",
+        ))
+    })?;
+
+    let discovery = response.json::<OidcDiscovery>().await.map_err(|_| {
+        HTTPException::Internal(String::from(
+            "The identity provider returned an invalid discovery document",
+        ))
+    })?;
+
+    // The discovery document must be issued by the configured identity provider, otherwise its endpoints can't be trusted
+    if discovery.issuer.trim_end_matches('/') != oidc_config.issuer_url {
+        return Err(HTTPException::Internal(String::from(
+            "The identity provider discovery document has an unexpected issuer",
+        )));
+    }
+
+    Ok(discovery)
+}
+
+/// Responsible to build the identity provider URL which the user is redirected to when signing in
+///
+/// # Arguments
+/// * `oidc_config` - `&OidcConfig` reference with the identity provider settings
+/// * `discovery` - `&OidcDiscovery` reference with the identity provider endpoints
+/// * `state` - Random value which is checked on the callback to prevent CSRF
+/// * `nonce` - Random value which must be present on the `id_token` to prevent replay attacks
+///
+/// # Errors
+/// * `HTTPException::Internal` - If the authorization endpoint is not a valid URL
+pub fn authorization_url(
+    oidc_config: &OidcConfig,
+    discovery: &OidcDiscovery,
+    state: &str,
+    nonce: &str,
+) -> Result<String, HTTPException> {
+    let mut url = Url::parse(&discovery.authorization_endpoint).map_err(|_| {
+        HTTPException::Internal(String::from(
+            "The identity provider returned an invalid authorization endpoint",
+        ))
+    })?;
+
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &oidc_config.client_id)
+        .append_pair("redirect_uri", &oidc_config.redirect_url)
+        .append_pair("scope", &oidc_config.scopes)
+        .append_pair("state", state)
+        .append_pair("nonce", nonce);
+
+    Ok(url.to_string())
+}
+
+/// Responsible to exchange the authorization code for the identity provider tokens and validate the `id_token`
+///
+/// # Arguments
+/// * `oidc_config` - `&OidcConfig` reference with the identity provider settings
+/// * `discovery` - `&OidcDiscovery` reference with the identity provider endpoints
+/// * `code` - The authorization code received on the callback
+/// * `nonce` - The nonce sent on the authorization request
+///
+/// # Returns
+/// * `Ok(OidcClaims)` - The validated `id_token` claims
+///
+/// # Errors
+/// * `HTTPException::Unauthorized` - If the code was rejected or the `id_token` is invalid
+/// * `HTTPException::Internal` - If the identity provider could not be reached
+pub async fn exchange_code(
+    oidc_config: &OidcConfig,
+    discovery: &OidcDiscovery,
+    code: &str,
+    nonce: &str,
+) -> Result<OidcClaims, HTTPException> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&discovery.token_endpoint)
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", oidc_config.redirect_url.as_str()),
+            ("client_id", oidc_config.client_id.as_str()),
+            ("client_secret", oidc_config.client_secret.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|_| {
+            HTTPException::Internal(String::from(
+                "Failed to reach the identity provider. Please try again later",
+            ))
+        })?;
+
+    if !response.status().is_success() {
+        return Err(HTTPException::Unauthorized(String::from(
+            "The identity provider rejected the authorization code",
+        )));
+    }
+
+    let token_response = response.json::<OidcTokenResponse>().await.map_err(|_| {
+        HTTPException::Internal(String::from(
+            "The identity provider returned an invalid token response",
+        ))
+    })?;
+
+    let claims = validate_id_token(oidc_config, discovery, &token_response.id_token).await?;
+
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(HTTPException::Unauthorized(String::from(
+            "The identity provider token does not match the login request",
+        )));
+    }
+
+    Ok(claims)
+}
+
+/// Responsible to get the algorithms accepted on the `id_token`. The configured ones are used first,
+/// then the ones advertised on the discovery document and `RS256` when neither is available
+fn id_token_algorithms(oidc_config: &OidcConfig, discovery: &OidcDiscovery) -> Vec<Algorithm> {
+    if let Some(algorithms) = &oidc_config.id_token_algorithms {
+        return algorithms.clone();
+    }
+
+    let advertised: Vec<Algorithm> = discovery
+        .id_token_signing_alg_values_supported
+        .iter()
+        .filter_map(|algorithm| Algorithm::from_str(algorithm).ok())
+        .collect();
+
+    if advertised.is_empty() {
+        vec![Algorithm::RS256]
+    } else {
+        advertised
+    }
+}
+
+/// Responsible to verify the `id_token` signature, issuer, audience and expiration.
+/// The token algorithm must be one of the accepted ones, as the header can be forged.
+/// `HS256` tokens are verified with the client secret, other algorithms with the identity provider key set
+///
+/// # Errors
+/// * `HTTPException::Unauthorized` - If the `id_token` is invalid
+/// * `HTTPException::Internal` - If the identity provider key set could not be fetched
+async fn validate_id_token(
+    oidc_config: &OidcConfig,
+    discovery: &OidcDiscovery,
+    id_token: &str,
+) -> Result<OidcClaims, HTTPException> {
+    let invalid_token = || {
+        HTTPException::Unauthorized(String::from(
+            "The identity provider returned an invalid token",
+        ))
+    };
+
+    let header = jsonwebtoken::decode_header(id_token).map_err(|_| invalid_token())?;
+    if !id_token_algorithms(oidc_config, discovery).contains(&header.alg) {
+        return Err(invalid_token());
+    }
+
+    let decoding_key = if header.alg == Algorithm::HS256 {
+        DecodingKey::from_secret(oidc_config.client_secret.as_ref())
+    } else {
+        let response = reqwest::get(&discovery.jwks_uri).await.map_err(|_| {
+            HTTPException::Internal(String::from(
+                "Failed to reach the identity provider. Please try again later",
+            ))
+        })?;
+        let key_set = response.json::<JwkSet>().await.map_err(|_| {
+            HTTPException::Internal(String::from(
+                "The identity provider returned an invalid key set",
+            ))
+        })?;
+
+        let jwk = match header.kid {
+            Some(kid) => key_set.find(&kid),
+            None => key_set.keys.first(),
+        }
+        .ok_or_else(invalid_token)?;
+
+        DecodingKey::from_jwk(jwk).map_err(|_| invalid_token())?
+    };
+
+    // The header algorithm is one of the accepted ones at this point
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[&discovery.issuer]);
+    validation.set_audience(&[&oidc_config.client_id]);
+
+    let token_data = jsonwebtoken::decode::<OidcClaims>(id_token, &decoding_key, &validation)
+        .map_err(|_| invalid_token())?;
+
+    Ok(token_data.claims)
+}
@@ -1,12 +1,14 @@
 use std::io::Cursor;
 
 use rocket::{
-    http::{ContentType, Status},
+    http::{ContentType, Header, Status},
     response::{self, Responder},
     Response,
 };
 use serde::{Deserialize, Serialize};
 
+use crate::models::pagination::PaginationData;
+
 #[derive(Serialize, Debug)]
 pub struct ErrorResponse {
     pub message: String,
@@ -16,6 +18,9 @@ pub struct ErrorResponse {
 pub struct CustomResponse {
     pub message: String,
     pub data: serde_json::Value,
+    // Only present on listings, describes which slice of the results is on `data`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<PaginationData>,
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +28,8 @@ pub enum HTTPException {
     Internal(String),
     NotFound(String),
     BadRequest(String),
+    // A bad request which sends back data to fix it, e.g. the invalid rows of an import
+    BadRequestWithData(String, serde_json::Value),
     Conflict(String),
     Unauthorized(String),
 }
@@ -33,12 +40,31 @@ pub enum HTTPSuccessResponse {
     Created(CustomResponse),
 }
 
+/// How a `FileResponse` is shown by the client
+/// * `Attachment` - The file is downloaded, e.g. a CSV export
+/// * `Inline` - The file is shown or read by the client, e.g. a calendar feed which the calendar apps subscribe to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileDisposition {
+    Attachment,
+    Inline,
+}
+
+/// A file sent by a route, e.g. a CSV export. `file_name` is the name which the file is saved with
+#[derive(Debug, Clone)]
+pub struct FileResponse {
+    pub file_name: String,
+    pub content_type: ContentType,
+    pub content: String,
+    pub disposition: FileDisposition,
+}
+
 impl HTTPException {
     fn get_http_status(&self) -> Status {
         match self {
             HTTPException::Internal(_) => Status::InternalServerError,
             HTTPException::NotFound(_) => Status::NotFound,
             HTTPException::BadRequest(_) => Status::BadRequest,
+            HTTPException::BadRequestWithData(_, _) => Status::BadRequest,
             HTTPException::Conflict(_) => Status::Conflict,
             HTTPException::Unauthorized(_) => Status::Unauthorized,
         }
@@ -65,6 +91,7 @@ impl std::fmt::Display for HTTPException {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         let error_message = match self {
             Self::BadRequest(message) => message,
+            Self::BadRequestWithData(message, _) => message,
             Self::Conflict(message) => message,
             Self::Internal(message) => message,
             Self::NotFound(message) => message,
@@ -77,11 +104,20 @@ impl std::fmt::Display for HTTPException {
 
 impl<'r> Responder<'r, 'static> for HTTPException {
     fn respond_to(self, _: &'r rocket::Request<'_>) -> response::Result<'static> {
-        let error_response = serde_json::to_string(
-            &(ErrorResponse {
-                message: self.to_string(),
-            }),
-        )
+        let error_response = match &self {
+            HTTPException::BadRequestWithData(message, data) => serde_json::to_string(
+                &(CustomResponse {
+                    message: message.clone(),
+                    data: data.clone(),
+                    pagination: None,
+                }),
+            ),
+            _ => serde_json::to_string(
+                &(ErrorResponse {
+                    message: self.to_string(),
+                }),
+            ),
+        }
         .unwrap();
 
         Response::build()
@@ -100,6 +136,7 @@ impl<'r> Responder<'r, 'static> for HTTPSuccessResponse {
             &(CustomResponse {
                 message: custom_response.message,
                 data: custom_response.data,
+                pagination: custom_response.pagination,
             }),
         )
         .unwrap();
@@ -111,3 +148,22 @@ impl<'r> Responder<'r, 'static> for HTTPSuccessResponse {
             .ok()
     }
 }
+
+impl<'r> Responder<'r, 'static> for FileResponse {
+    fn respond_to(self, _: &'r rocket::Request<'_>) -> response::Result<'static> {
+        let disposition = match self.disposition {
+            FileDisposition::Attachment => "attachment",
+            FileDisposition::Inline => "inline",
+        };
+
+        Response::build()
+            .status(Status::Ok)
+            .header(self.content_type)
+            .header(Header::new(
+                "Content-Disposition",
+                format!("{}; filename=\"{}\"", disposition, self.file_name),
+            ))
+            .sized_body(self.content.len(), Cursor::new(self.content))
+            .ok()
+    }
+}
@@ -0,0 +1,1336 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx::{Executor, MySql, Pool, Transaction};
+
+use crate::{
+    constants::MYSQL_CUSTOM_ERROR,
+    models::{
+        bracket::{
+            BracketDTO, BracketGenerationDTO, BracketMatch, BracketMatchDTO, BracketSide,
+            BracketSlot,
+        },
+        fixture::{EditFixtureDTO, FixtureDataDTO},
+        league::{League, LeagueFormat},
+        tie::{LegScore, Tie, TieAggregate, TieDTO},
+    },
+    responses::HTTPException,
+};
+
+use super::league_service::get_teams_from_league;
+
+/// A `bracket_matches` row, the slots are read with `BracketSlot::from_sql`
+struct BracketMatchRow {
+    match_number: u16,
+    bracket: String,
+    bracket_round: u16,
+    position: u16,
+    home_seed_team_id: Option<u64>,
+    home_source_match: Option<u16>,
+    home_source_result: Option<String>,
+    away_seed_team_id: Option<u64>,
+    away_source_match: Option<u16>,
+    away_source_result: Option<String>,
+    fixture_id: Option<u64>,
+    two_legged: i8,
+    away_goals: i8,
+}
+
+impl From<BracketMatchRow> for BracketMatch {
+    fn from(row: BracketMatchRow) -> Self {
+        BracketMatch {
+            match_number: row.match_number,
+            bracket: BracketSide::from_sql(&row.bracket),
+            bracket_round: row.bracket_round,
+            position: row.position,
+            home_slot: BracketSlot::from_sql(
+                row.home_seed_team_id,
+                row.home_source_match,
+                row.home_source_result.as_deref(),
+            ),
+            away_slot: BracketSlot::from_sql(
+                row.away_seed_team_id,
+                row.away_source_match,
+                row.away_source_result.as_deref(),
+            ),
+            fixture_id: row.fixture_id,
+            two_legged: row.two_legged == 1,
+            away_goals: row.away_goals == 1,
+        }
+    }
+}
+
+/// The result of a bracket match, read from its fixture or from its tie when the match is two legged
+struct BracketResult {
+    home_team_id: u64,
+    away_team_id: u64,
+    // If a fixture of the match was played, so its teams can't change anymore
+    started: bool,
+    winner_team_id: Option<u64>,
+    second_leg_id: Option<u64>,
+}
+
+/// The team of a bracket slot
+/// * `Pending` - The match which feeds the slot is not decided yet
+/// * `Empty` - A bye, nobody comes to the slot, or the team withdrew from the league before playing the match
+#[derive(Clone, Copy, PartialEq)]
+enum SlotState {
+    Pending,
+    Team(u64),
+    Empty,
+}
+
+/// A bracket match after reading the results of the matches which feed it
+#[derive(Clone, Copy)]
+struct ResolvedMatch {
+    home: SlotState,
+    away: SlotState,
+    // The winner and the loser once the match is decided, they're `None` when the slot was a bye
+    result: Option<(Option<u64>, Option<u64>)>,
+}
+
+/// Responsible to order the seeds on the first winners round, so the best seeds only meet on the last rounds, e.g. 1, 8, 4, 5, 2, 7, 3, 6
+///
+/// # Arguments
+/// * `size` - The bracket size, a power of two
+///
+/// # Returns
+/// * `Vec<usize>` - The seeds from 1 to `size`, every two seeds play each other
+fn bracket_seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let round_size = order.len() * 2;
+        order = order
+            .into_iter()
+            .flat_map(|seed| [seed, round_size + 1 - seed])
+            .collect();
+    }
+
+    order
+}
+
+/// Responsible to build the matches of a double elimination bracket.
+/// The bracket size is the next power of two, the best seeds get a bye when the teams quantity is smaller than it.
+/// The losers of a winners round drop to the losers bracket in the reverse order, so the teams don't meet again right away
+///
+/// # Arguments
+/// * `seeds` - The teams ids from the first to the last seed
+/// * `grand_final_reset` - If the grand final is played again when the losers bracket champion wins it
+///
+/// # Returns
+/// * `Vec<BracketMatch>` - The matches ordered by their number, without fixtures
+fn build_double_elimination_bracket(seeds: &[u64], grand_final_reset: bool) -> Vec<BracketMatch> {
+    let size = seeds.len().next_power_of_two().max(2);
+    let winners_rounds = size.trailing_zeros() as u16;
+    let mut matches: Vec<BracketMatch> = vec![];
+    let mut add_match = |bracket: BracketSide,
+                         bracket_round: u16,
+                         home_slot: BracketSlot,
+                         away_slot: BracketSlot|
+     -> u16 {
+        let position = matches
+            .iter()
+            .filter(|bracket_match| {
+                bracket_match.bracket == bracket && bracket_match.bracket_round == bracket_round
+            })
+            .count() as u16
+            + 1;
+        let match_number = matches.len() as u16 + 1;
+        matches.push(BracketMatch {
+            match_number,
+            bracket,
+            bracket_round,
+            position,
+            home_slot,
+            away_slot,
+            fixture_id: None,
+            two_legged: false,
+            away_goals: false,
+        });
+
+        match_number
+    };
+
+    let seed_order = bracket_seed_order(size);
+    let mut winners: Vec<Vec<u16>> = vec![seed_order
+        .chunks(2)
+        .map(|pair| {
+            add_match(
+                BracketSide::Winners,
+                1,
+                BracketSlot::Seed(seeds.get(pair[0] - 1).copied()),
+                BracketSlot::Seed(seeds.get(pair[1] - 1).copied()),
+            )
+        })
+        .collect()];
+    for round in 2..=winners_rounds {
+        let previous_round = winners.last().cloned().unwrap_or_default();
+        winners.push(
+            previous_round
+                .chunks(2)
+                .map(|pair| {
+                    add_match(
+                        BracketSide::Winners,
+                        round,
+                        BracketSlot::Winner(pair[0]),
+                        BracketSlot::Winner(pair[1]),
+                    )
+                })
+                .collect(),
+        );
+    }
+
+    // With two teams there's no losers round, the loser of the only winners match goes to the grand final
+    let mut losers_champion = BracketSlot::Loser(winners[0][0]);
+    if winners_rounds > 1 {
+        let mut losers_round: Vec<u16> = winners[0]
+            .chunks(2)
+            .map(|pair| {
+                add_match(
+                    BracketSide::Losers,
+                    1,
+                    BracketSlot::Loser(pair[0]),
+                    BracketSlot::Loser(pair[1]),
+                )
+            })
+            .collect();
+
+        for round in 1..winners_rounds as usize {
+            // The losers of the next winners round join the bracket
+            let dropped_teams: Vec<u16> = winners[round].iter().rev().copied().collect();
+            losers_round = losers_round
+                .iter()
+                .zip(dropped_teams)
+                .map(|(&losers_match, winners_match)| {
+                    add_match(
+                        BracketSide::Losers,
+                        round as u16 * 2,
+                        BracketSlot::Winner(losers_match),
+                        BracketSlot::Loser(winners_match),
+                    )
+                })
+                .collect();
+
+            if round + 1 < winners_rounds as usize {
+                losers_round = losers_round
+                    .chunks(2)
+                    .map(|pair| {
+                        add_match(
+                            BracketSide::Losers,
+                            round as u16 * 2 + 1,
+                            BracketSlot::Winner(pair[0]),
+                            BracketSlot::Winner(pair[1]),
+                        )
+                    })
+                    .collect();
+            }
+        }
+
+        losers_champion = BracketSlot::Winner(losers_round[0]);
+    }
+
+    let winners_champion = BracketSlot::Winner(winners[winners_rounds as usize - 1][0]);
+    let grand_final = add_match(
+        BracketSide::GrandFinal,
+        1,
+        winners_champion,
+        losers_champion,
+    );
+    if grand_final_reset {
+        // When the reset is played the loser of the grand final is the winners bracket champion, so it stays at home
+        add_match(
+            BracketSide::GrandFinalReset,
+            1,
+            BracketSlot::Loser(grand_final),
+            BracketSlot::Winner(grand_final),
+        );
+    }
+
+    matches
+}
+
+/// Responsible to get the fixture round of a bracket match, so the teams play at most once a round.
+/// A losers round is played with the winners round which sends its losers to the next losers round.
+/// A two legged bracket takes two fixture rounds for each of them, the single fixtures are played on the first one
+///
+/// # Arguments
+/// * `bracket_match` - The bracket match
+/// * `winners_rounds` - How many rounds the winners bracket has
+/// * `legs` - How many legs the bracket matches are played over
+///
+/// # Returns
+/// * `u16` - The fixture round, the round of the first leg on a two legged match
+fn bracket_fixture_round(bracket_match: &BracketMatch, winners_rounds: u16, legs: u16) -> u16 {
+    let bracket_round = match bracket_match.bracket {
+        BracketSide::Winners if bracket_match.bracket_round == 1 => 1,
+        BracketSide::Winners => bracket_match.bracket_round * 2 - 2,
+        BracketSide::Losers => bracket_match.bracket_round + 1,
+        BracketSide::GrandFinal => winners_rounds * 2,
+        BracketSide::GrandFinalReset => winners_rounds * 2 + 1,
+    };
+
+    (bracket_round - 1) * legs + 1
+}
+
+/// Responsible to read the results of the bracket fixtures, a two legged match is decided by the aggregate of its tie
+///
+/// # Arguments
+/// * `fixtures` - The league fixtures, indexed by their id
+/// * `ties` - The first leg id, the second leg id and the aggregate of the league ties
+///
+/// # Returns
+/// * `HashMap<u64, BracketResult>` - The results indexed by the fixture id, the first leg id for the ties
+fn bracket_results(
+    fixtures: &HashMap<u64, LegScore>,
+    ties: &[(u64, u64, TieAggregate)],
+) -> HashMap<u64, BracketResult> {
+    let mut results: HashMap<u64, BracketResult> = fixtures
+        .iter()
+        .map(|(&fixture_id, fixture)| {
+            let winner_team_id =
+                (fixture.played && fixture.home_score != fixture.away_score).then(|| {
+                    if fixture.home_score > fixture.away_score {
+                        fixture.home_team_id
+                    } else {
+                        fixture.away_team_id
+                    }
+                });
+
+            (
+                fixture_id,
+                BracketResult {
+                    home_team_id: fixture.home_team_id,
+                    away_team_id: fixture.away_team_id,
+                    started: fixture.played,
+                    winner_team_id,
+                    second_leg_id: None,
+                },
+            )
+        })
+        .collect();
+
+    for &(first_leg_id, second_leg_id, aggregate) in ties {
+        let second_leg_played = fixtures
+            .get(&second_leg_id)
+            .is_some_and(|second_leg| second_leg.played);
+        if let Some(result) = results.get_mut(&first_leg_id) {
+            result.started |= second_leg_played;
+            result.winner_team_id = aggregate.winner_team_id;
+            result.second_leg_id = Some(second_leg_id);
+        }
+    }
+
+    results
+}
+
+/// Responsible to read the bracket teams and winners from the fixture results.
+/// A team with a bye goes through without a fixture, and a match is decided when its fixture, or its tie, is won by a team of its slots.
+/// A team which withdrew from the league leaves its slot empty on the matches it didn't start, so its opponent gets a walkover
+///
+/// # Arguments
+/// * `matches` - The bracket matches ordered by their number
+/// * `results` - The results of the bracket fixtures, indexed by their id
+/// * `league_team_ids` - The teams which are still on the league
+///
+/// # Returns
+/// * `HashMap<u16, ResolvedMatch>` - The matches indexed by their number
+fn resolve_bracket(
+    matches: &[BracketMatch],
+    results: &HashMap<u64, BracketResult>,
+    league_team_ids: &HashSet<u64>,
+) -> HashMap<u16, ResolvedMatch> {
+    let mut resolved: HashMap<u16, ResolvedMatch> = HashMap::new();
+    for bracket_match in matches {
+        // A match always comes after the matches which feed it, so they're already resolved
+        let slot_state = |slot: BracketSlot| {
+            let (source_match, winner) = match slot {
+                BracketSlot::Seed(Some(team_id)) => return SlotState::Team(team_id),
+                BracketSlot::Seed(None) => return SlotState::Empty,
+                BracketSlot::Winner(source_match) => (source_match, true),
+                BracketSlot::Loser(source_match) => (source_match, false),
+            };
+
+            match resolved.get(&source_match).and_then(|source| source.result) {
+                None => SlotState::Pending,
+                Some((winner_team, loser_team)) => {
+                    let team = if winner { winner_team } else { loser_team };
+                    team.map_or(SlotState::Empty, SlotState::Team)
+                }
+            }
+        };
+        let mut home = slot_state(bracket_match.home_slot);
+        let mut away = slot_state(bracket_match.away_slot);
+
+        // The reset is not played when the winners bracket champion wins the grand final
+        if let (BracketSide::GrandFinalReset, BracketSlot::Loser(grand_final)) =
+            (bracket_match.bracket, bracket_match.home_slot)
+        {
+            if let Some(ResolvedMatch {
+                home: SlotState::Team(winners_champion),
+                result: Some((winner_team, _)),
+                ..
+            }) = resolved.get(&grand_final)
+            {
+                if *winner_team == Some(*winners_champion) {
+                    home = SlotState::Empty;
+                    away = SlotState::Empty;
+                }
+            }
+        }
+
+        let started = bracket_match
+            .fixture_id
+            .and_then(|fixture_id| results.get(&fixture_id))
+            .is_some_and(|result| result.started);
+        if !started {
+            for slot in [&mut home, &mut away] {
+                if let SlotState::Team(team_id) = *slot {
+                    if !league_team_ids.contains(&team_id) {
+                        *slot = SlotState::Empty;
+                    }
+                }
+            }
+        }
+
+        let result = match (home, away) {
+            (SlotState::Pending, _) | (_, SlotState::Pending) => None,
+            (SlotState::Team(home_team), SlotState::Team(away_team)) => bracket_match
+                .fixture_id
+                .and_then(|fixture_id| results.get(&fixture_id))
+                .filter(|result| {
+                    result.home_team_id == home_team && result.away_team_id == away_team
+                })
+                .and_then(|result| result.winner_team_id)
+                .map(|winner_team| {
+                    if winner_team == home_team {
+                        (Some(home_team), Some(away_team))
+                    } else {
+                        (Some(away_team), Some(home_team))
+                    }
+                }),
+            (SlotState::Team(team_id), SlotState::Empty)
+            | (SlotState::Empty, SlotState::Team(team_id)) => Some((Some(team_id), None)),
+            (SlotState::Empty, SlotState::Empty) => Some((None, None)),
+        };
+
+        resolved.insert(
+            bracket_match.match_number,
+            ResolvedMatch { home, away, result },
+        );
+    }
+
+    resolved
+}
+
+/// Responsible to get the teams which are still on a league, the bracket teams missing from it withdrew
+///
+/// # Arguments
+/// * `league_id` - The league id
+/// * `executor` - The `&Pool<MySql>` database pool, or the connection of a running transaction
+///
+/// # Errors
+/// * `HTTPException::Internal` - If the database query fails
+async fn get_league_team_ids<'c, E>(
+    league_id: u64,
+    executor: E,
+) -> Result<HashSet<u64>, HTTPException>
+where
+    E: Executor<'c, Database = MySql>,
+{
+    let league_teams = sqlx::query!(
+        "SELECT team_id FROM `teams_leagues` WHERE league_id = (?)",
+        league_id
+    )
+    .fetch_all(executor)
+    .await
+    .or_else(|_error| {
+        Err(HTTPException::Internal(String::from(
+            "Failed to get the league bracket. Please try again later",
+        )))
+    })?;
+
+    Ok(league_teams
+        .into_iter()
+        .map(|league_team| league_team.team_id)
+        .collect())
+}
+
+/// Responsible to get the champion of a resolved bracket, the winner of the reset when it's played or the winner of the grand final
+fn bracket_champion(
+    matches: &[BracketMatch],
+    resolved: &HashMap<u16, ResolvedMatch>,
+) -> Option<u64> {
+    let match_result = |bracket: BracketSide| {
+        matches
+            .iter()
+            .find(|bracket_match| bracket_match.bracket == bracket)
+            .map(|bracket_match| {
+                resolved
+                    .get(&bracket_match.match_number)
+                    .and_then(|resolved_match| resolved_match.result)
+            })
+    };
+
+    let grand_final_winner = match_result(BracketSide::GrandFinal)
+        .flatten()
+        .and_then(|(winner_team, _)| winner_team);
+    match match_result(BracketSide::GrandFinalReset) {
+        None | Some(Some((None, _))) => grand_final_winner,
+        Some(Some((Some(winner_team), _))) => Some(winner_team),
+        Some(None) => None,
+    }
+}
+
+impl BracketMatch {
+    /// Responsible to generate the double elimination bracket of a league and create the fixtures of the first round
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `generation_data` - A `BracketGenerationDTO` struct with the seeding, if the grand final can be reset and if the matches are two legged
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(BracketDTO)` - The generated bracket
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's not enough teams, the user doesn't have permission to generate the bracket or the league is completed
+    /// * * The league is not a double elimination league
+    /// * * The league already has fixtures
+    /// * * The seeding doesn't have every team of the league exactly once
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn generate_bracket(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        generation_data: BracketGenerationDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<BracketDTO, HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+
+        let teams = get_teams_from_league(league_id, tournament_id, user_id, db_pool).await?;
+        if teams.len() < 2 {
+            return Err(HTTPException::BadRequest(String::from(
+                "Not enough teams or permission to generate the bracket",
+            )));
+        }
+
+        if League::get_league_format(league_id, db_pool).await?
+            != Some(LeagueFormat::DoubleElimination)
+        {
+            return Err(HTTPException::BadRequest(String::from(
+                "Only the double elimination leagues are played on a bracket",
+            )));
+        }
+
+        let existing_fixtures =
+            League::get_league_fixtures(user_id, tournament_id, league_id, db_pool).await?;
+        if !existing_fixtures.is_empty() {
+            return Err(HTTPException::BadRequest(String::from(
+                "The league already has fixtures. Delete them before generating the bracket",
+            )));
+        }
+
+        let seeds: Vec<u64> = match generation_data.team_ids {
+            None => teams.iter().map(|team| team.id).collect(),
+            Some(team_ids) => {
+                let league_team_ids: HashSet<u64> = teams.iter().map(|team| team.id).collect();
+                let seeded_team_ids: HashSet<u64> = team_ids.iter().copied().collect();
+                if team_ids.len() != seeded_team_ids.len() || seeded_team_ids != league_team_ids {
+                    return Err(HTTPException::BadRequest(String::from(
+                        "The seeding must have every team of the league exactly once",
+                    )));
+                }
+
+                team_ids
+            }
+        };
+
+        let mut bracket =
+            build_double_elimination_bracket(&seeds, generation_data.grand_final_reset);
+        // The grand final and its reset are always played on a single fixture
+        for bracket_match in bracket.iter_mut().filter(|bracket_match| {
+            matches!(
+                bracket_match.bracket,
+                BracketSide::Winners | BracketSide::Losers
+            )
+        }) {
+            bracket_match.two_legged = generation_data.two_legged;
+            bracket_match.away_goals = generation_data.away_goals;
+        }
+
+        // A bracket left without fixtures can't be played anymore, so it's replaced
+        sqlx::query!(
+            "DELETE FROM `bracket_matches` WHERE league_id = (?)",
+            league_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while saving the bracket. Please try again later",
+            )))
+        })?;
+
+        for bracket_match in &bracket {
+            let (home_seed_team_id, home_source_match, home_source_result) =
+                bracket_match.home_slot.as_sql();
+            let (away_seed_team_id, away_source_match, away_source_result) =
+                bracket_match.away_slot.as_sql();
+
+            sqlx::query!(
+                "
+            INSERT INTO `bracket_matches` (
+                `league_id`, `match_number`, `bracket`, `bracket_round`, `position`,
+                `home_seed_team_id`, `home_source_match`, `home_source_result`,
+                `away_seed_team_id`, `away_source_match`, `away_source_result`,
+                `two_legged`, `away_goals`
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+                league_id,
+                bracket_match.match_number,
+                bracket_match.bracket.as_sql(),
+                bracket_match.bracket_round,
+                bracket_match.position,
+                home_seed_team_id,
+                home_source_match,
+                home_source_result,
+                away_seed_team_id,
+                away_source_match,
+                away_source_result,
+                bracket_match.two_legged,
+                bracket_match.away_goals
+            )
+            .execute(&mut *transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed while saving the bracket. Please try again later",
+                )))
+            })?;
+        }
+
+        BracketMatch::advance_bracket_matches(user_id, league_id, &mut transaction).await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while saving the bracket. Please try again later",
+            )))
+        })?;
+
+        BracketMatch::get_bracket(user_id, tournament_id, league_id, db_pool).await
+    }
+
+    /// Responsible to get the double elimination bracket of a league, with the teams which reached each match so far
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(BracketDTO)` - The bracket matches ordered by their number and the champion
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the league does not have a bracket, or the tournament is private and the user does not own it
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_bracket(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<BracketDTO, HTTPException> {
+        let matches: Vec<BracketMatch> = sqlx::query_as!(
+            BracketMatchRow,
+            "
+        SELECT BracketRow.match_number, BracketRow.bracket, BracketRow.bracket_round, BracketRow.position,
+            BracketRow.home_seed_team_id, BracketRow.home_source_match, BracketRow.home_source_result,
+            BracketRow.away_seed_team_id, BracketRow.away_source_match, BracketRow.away_source_result,
+            BracketRow.fixture_id, BracketRow.two_legged, BracketRow.away_goals
+        FROM `bracket_matches` as BracketRow
+        INNER JOIN `leagues` as LeagueRow
+            ON LeagueRow.id = BracketRow.league_id
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        WHERE BracketRow.league_id = (?) AND TournamentRow.id = (?) AND
+        (
+            (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
+            OR
+            (TournamentRow.public = TRUE)
+        )
+        ORDER BY BracketRow.match_number
+        ",
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league bracket. Please try again later",
+            )))
+        })?
+        .into_iter()
+        .map(BracketMatch::from)
+        .collect();
+
+        if matches.is_empty() {
+            return Err(HTTPException::BadRequest(String::from(
+                "The league does not have a bracket. Please make sure the tournament/league exists and you have permission to access it",
+            )));
+        }
+
+        let mut league_fixtures: HashMap<u64, FixtureDataDTO> =
+            League::get_league_fixtures(user_id, tournament_id, league_id, db_pool)
+                .await?
+                .into_iter()
+                .map(|fixture| (fixture.id, fixture))
+                .collect();
+        let fixtures: HashMap<u64, LegScore> = league_fixtures
+            .values()
+            .map(|fixture| (fixture.id, LegScore::from(fixture)))
+            .collect();
+        // The two legged matches are linked to the first leg of their tie
+        let mut ties: HashMap<u64, TieDTO> =
+            Tie::get_league_ties(user_id, tournament_id, league_id, db_pool)
+                .await?
+                .into_iter()
+                .map(|tie| (tie.first_leg.id, tie))
+                .collect();
+        let tie_aggregates: Vec<(u64, u64, TieAggregate)> = ties
+            .values()
+            .map(|tie| (tie.first_leg.id, tie.second_leg.id, tie.aggregate))
+            .collect();
+
+        let results = bracket_results(&fixtures, &tie_aggregates);
+        let league_team_ids = get_league_team_ids(league_id, db_pool).await?;
+        let resolved = resolve_bracket(&matches, &results, &league_team_ids);
+        let champion_team_id = bracket_champion(&matches, &resolved);
+
+        let mut winner_to_match: HashMap<u16, u16> = HashMap::new();
+        let mut loser_to_match: HashMap<u16, u16> = HashMap::new();
+        for bracket_match in &matches {
+            for slot in [bracket_match.home_slot, bracket_match.away_slot] {
+                match slot {
+                    BracketSlot::Winner(source_match) => {
+                        winner_to_match.insert(source_match, bracket_match.match_number);
+                    }
+                    BracketSlot::Loser(source_match) => {
+                        loser_to_match.insert(source_match, bracket_match.match_number);
+                    }
+                    BracketSlot::Seed(_) => {}
+                }
+            }
+        }
+
+        let slot_team = |slot: SlotState| match slot {
+            SlotState::Team(team_id) => Some(team_id),
+            _ => None,
+        };
+        let matches = matches
+            .into_iter()
+            .map(|bracket_match| {
+                let resolved_match = resolved.get(&bracket_match.match_number);
+                let result = resolved_match.and_then(|resolved_match| resolved_match.result);
+                let (second_leg, aggregate) = match bracket_match
+                    .fixture_id
+                    .and_then(|fixture_id| ties.remove(&fixture_id))
+                {
+                    Some(tie) => (Some(tie.second_leg), Some(tie.aggregate)),
+                    None => (None, None),
+                };
+
+                BracketMatchDTO {
+                    match_number: bracket_match.match_number,
+                    bracket: bracket_match.bracket,
+                    bracket_round: bracket_match.bracket_round,
+                    position: bracket_match.position,
+                    home_slot: bracket_match.home_slot,
+                    away_slot: bracket_match.away_slot,
+                    home_team_id: resolved_match
+                        .and_then(|resolved_match| slot_team(resolved_match.home)),
+                    away_team_id: resolved_match
+                        .and_then(|resolved_match| slot_team(resolved_match.away)),
+                    fixture: bracket_match
+                        .fixture_id
+                        .and_then(|fixture_id| league_fixtures.remove(&fixture_id)),
+                    second_leg,
+                    aggregate,
+                    decided: result.is_some(),
+                    winner_team_id: result.and_then(|(winner_team, _)| winner_team),
+                    winner_to_match: winner_to_match.get(&bracket_match.match_number).copied(),
+                    loser_to_match: loser_to_match.get(&bracket_match.match_number).copied(),
+                }
+            })
+            .collect();
+
+        Ok(BracketDTO {
+            matches,
+            champion_team_id,
+        })
+    }
+
+    /// Responsible to get the champion of the league bracket on a transaction, so the results saved on it are counted
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `transaction` - The transaction which the bracket is read on
+    ///
+    /// # Returns
+    /// * `Ok(Option<u64>)` - The champion team id, `None` while the bracket is not decided or when the league does not have a bracket
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_bracket_champion(
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<Option<u64>, HTTPException> {
+        let (matches, _, resolved) =
+            BracketMatch::get_bracket_results(league_id, transaction).await?;
+        if matches.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(bracket_champion(&matches, &resolved))
+    }
+
+    /// Responsible to sync the bracket fixtures with the results: the fixture of a match is created once both of its teams are known,
+    /// a pending fixture gets the teams which reached the match and it's deleted when they're not known anymore.
+    /// It should run on the transaction which changes a fixture result, it does nothing when the league does not have a bracket
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `league_id` - The league id
+    /// * `transaction` - The transaction which the changes are saved on
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the user doesn't have permission to create the fixtures
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn advance_bracket_matches(
+        user_id: u64,
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), HTTPException> {
+        let (matches, results, resolved) =
+            BracketMatch::get_bracket_results(league_id, transaction).await?;
+        if matches.is_empty() {
+            return Ok(());
+        }
+
+        let winners_rounds = matches
+            .iter()
+            .filter(|bracket_match| bracket_match.bracket == BracketSide::Winners)
+            .map(|bracket_match| bracket_match.bracket_round)
+            .max()
+            .unwrap_or(1);
+        let legs = if matches.iter().any(|bracket_match| bracket_match.two_legged) {
+            2
+        } else {
+            1
+        };
+
+        for bracket_match in &matches {
+            let Some(resolved_match) = resolved.get(&bracket_match.match_number) else {
+                continue;
+            };
+            let result = bracket_match
+                .fixture_id
+                .and_then(|fixture_id| results.get(&fixture_id).map(|result| (fixture_id, result)));
+
+            match (resolved_match.home, resolved_match.away, result) {
+                (SlotState::Team(home_team_id), SlotState::Team(away_team_id), None) => {
+                    BracketMatch::create_bracket_fixture(
+                        user_id,
+                        league_id,
+                        bracket_match,
+                        home_team_id,
+                        away_team_id,
+                        bracket_fixture_round(bracket_match, winners_rounds, legs),
+                        transaction,
+                    )
+                    .await?;
+                }
+                (
+                    SlotState::Team(home_team_id),
+                    SlotState::Team(away_team_id),
+                    Some((fixture_id, result)),
+                ) if !result.started
+                    && (result.home_team_id != home_team_id
+                        || result.away_team_id != away_team_id) =>
+                {
+                    // The second leg is played at the first leg away team ground
+                    let leg_teams = [(fixture_id, home_team_id, away_team_id)]
+                        .into_iter()
+                        .chain(
+                            result
+                                .second_leg_id
+                                .map(|second_leg_id| (second_leg_id, away_team_id, home_team_id)),
+                        );
+                    for (leg_id, leg_home_team_id, leg_away_team_id) in leg_teams {
+                        sqlx::query!(
+                            "
+                        UPDATE `fixtures` SET home_team_id = (?), away_team_id = (?)
+                        WHERE id = (?)
+                        ",
+                            leg_home_team_id,
+                            leg_away_team_id,
+                            leg_id
+                        )
+                        .execute(&mut **transaction)
+                        .await
+                        .or_else(|_error| {
+                            Err(HTTPException::Internal(String::from(
+                                "Failed while updating the bracket fixtures. Please try again later",
+                            )))
+                        })?;
+                    }
+                }
+                (SlotState::Team(_), SlotState::Team(_), Some(_)) => {}
+                // A previous result changed, so the teams of the pending fixtures are not known anymore
+                (_, _, Some((fixture_id, result))) if !result.started => {
+                    for leg_id in [Some(fixture_id), result.second_leg_id]
+                        .into_iter()
+                        .flatten()
+                    {
+                        sqlx::query!("DELETE FROM `fixtures` WHERE id = (?)", leg_id)
+                            .execute(&mut **transaction)
+                            .await
+                            .or_else(|_error| {
+                                Err(HTTPException::Internal(String::from(
+                                    "Failed while updating the bracket fixtures. Please try again later",
+                                )))
+                            })?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Responsible to read the bracket matches and the results of their fixtures on a transaction, locking the matches until it ends
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `transaction` - The transaction which the bracket is read on
+    ///
+    /// # Returns
+    /// * `Ok((Vec<BracketMatch>, HashMap<u64, BracketResult>, HashMap<u16, ResolvedMatch>))` - The matches ordered by their number,
+    /// empty when the league does not have a bracket, the results indexed by the fixture id and the resolved matches indexed by their number
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn get_bracket_results(
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<
+        (
+            Vec<BracketMatch>,
+            HashMap<u64, BracketResult>,
+            HashMap<u16, ResolvedMatch>,
+        ),
+        HTTPException,
+    > {
+        let matches: Vec<BracketMatch> = sqlx::query_as!(
+            BracketMatchRow,
+            "
+        SELECT BracketRow.match_number, BracketRow.bracket, BracketRow.bracket_round, BracketRow.position,
+            BracketRow.home_seed_team_id, BracketRow.home_source_match, BracketRow.home_source_result,
+            BracketRow.away_seed_team_id, BracketRow.away_source_match, BracketRow.away_source_result,
+            BracketRow.fixture_id, BracketRow.two_legged, BracketRow.away_goals
+        FROM `bracket_matches` as BracketRow
+        WHERE BracketRow.league_id = (?)
+        ORDER BY BracketRow.match_number
+        FOR UPDATE
+        ",
+            league_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league bracket. Please try again later",
+            )))
+        })?
+        .into_iter()
+        .map(BracketMatch::from)
+        .collect();
+
+        if matches.is_empty() {
+            return Ok((matches, HashMap::new(), HashMap::new()));
+        }
+
+        let fixtures = Tie::get_leg_scores_from_league(league_id, transaction).await?;
+
+        let tie_aggregates: Vec<(u64, u64, TieAggregate)> =
+            Tie::get_ties_from_league(league_id, transaction)
+                .await?
+                .into_iter()
+                .filter_map(|tie| {
+                    let first_leg = fixtures.get(&tie.first_leg_id)?;
+                    let second_leg = fixtures.get(&tie.second_leg_id)?;
+
+                    Some((
+                        tie.first_leg_id,
+                        tie.second_leg_id,
+                        tie.aggregate(first_leg, second_leg),
+                    ))
+                })
+                .collect();
+
+        let results = bracket_results(&fixtures, &tie_aggregates);
+        let league_team_ids = get_league_team_ids(league_id, &mut **transaction).await?;
+        let resolved = resolve_bracket(&matches, &results, &league_team_ids);
+
+        Ok((matches, results, resolved))
+    }
+
+    /// Responsible to create the fixture of a bracket match and link it to the match.
+    /// A two legged match gets a tie, the second leg is played on the next round
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If a team is not on the league or the user doesn't have permission to create the fixture
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn create_bracket_fixture(
+        user_id: u64,
+        league_id: u64,
+        bracket_match: &BracketMatch,
+        home_team_id: u64,
+        away_team_id: u64,
+        round: u16,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), HTTPException> {
+        let fixture_id = if bracket_match.two_legged {
+            Tie::insert_tie(
+                user_id,
+                league_id,
+                home_team_id,
+                away_team_id,
+                (round, round + 1),
+                bracket_match.away_goals,
+                transaction,
+            )
+            .await?
+            .first_leg_id
+        } else {
+            sqlx::query!(
+                "
+            CALL generate_fixture(?, ?, ?, ?, ?)
+            ",
+                home_team_id,
+                away_team_id,
+                league_id,
+                round,
+                user_id
+            )
+            .execute(&mut **transaction)
+            .await
+            .or_else(|exception| {
+                if let Some(database_error) = exception.as_database_error() {
+                    if let Some(code) = database_error.code() {
+                        if code == MYSQL_CUSTOM_ERROR {
+                            let message = database_error.message();
+                            return Err(HTTPException::BadRequest(message.to_string()));
+                        }
+                    }
+                }
+
+                Err(HTTPException::Internal(String::from(
+                    "Failed while creating the bracket fixtures. Please try again later",
+                )))
+            })?;
+
+            sqlx::query!("SELECT LAST_INSERT_ID() as id")
+                .fetch_one(&mut **transaction)
+                .await
+                .or_else(|_error| {
+                    Err(HTTPException::Internal(String::from(
+                        "Failed while creating the bracket fixtures. Please try again later",
+                    )))
+                })?
+                .id
+        };
+
+        sqlx::query!(
+            "
+        UPDATE `bracket_matches` SET fixture_id = (?)
+        WHERE league_id = (?) AND match_number = (?)
+        ",
+            fixture_id,
+            league_id,
+            bracket_match.match_number
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while creating the bracket fixtures. Please try again later",
+            )))
+        })?;
+
+        Ok(())
+    }
+
+    /// Responsible to check a new result of a bracket fixture. A single bracket fixture needs a winner,
+    /// and its winner can't change after the matches which it feeds are played. The legs of a two legged match can end on a draw,
+    /// but their results can't change after the next matches are played
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `fixture_id` - The fixture id
+    /// * `edit_data` - A `&EditFixtureDTO` reference with the new result
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the result is a draw or the next matches were already played
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn check_bracket_result(
+        league_id: u64,
+        fixture_id: u64,
+        edit_data: &EditFixtureDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let bracket_fixture = sqlx::query!(
+            "
+        SELECT BracketRow.match_number, BracketRow.two_legged,
+            FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played
+        FROM `bracket_matches` as BracketRow
+        LEFT JOIN `ties` as TieRow
+            ON TieRow.first_leg_id = BracketRow.fixture_id
+        INNER JOIN `fixtures` as FixtureRow
+            ON FixtureRow.id = BracketRow.fixture_id OR FixtureRow.id = TieRow.second_leg_id
+        WHERE BracketRow.league_id = (?) AND FixtureRow.id = (?)
+        ",
+            league_id,
+            fixture_id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league bracket. Please try again later",
+            )))
+        })?;
+
+        let Some(bracket_fixture) = bracket_fixture else {
+            return Ok(());
+        };
+
+        if bracket_fixture.two_legged == 1 {
+            // The tie is decided by the aggregate, so any leg result can change its winner
+            let current_result = (bracket_fixture.played == 1)
+                .then_some((bracket_fixture.home_score, bracket_fixture.away_score));
+            let new_result = edit_data
+                .played
+                .then_some((edit_data.home_score, edit_data.away_score));
+            if current_result == new_result {
+                return Ok(());
+            }
+        } else {
+            if edit_data.played && edit_data.home_score == edit_data.away_score {
+                return Err(HTTPException::BadRequest(String::from(
+                    "A bracket fixture can't end on a draw",
+                )));
+            }
+
+            let current_winner = (bracket_fixture.played == 1)
+                .then_some(bracket_fixture.home_score > bracket_fixture.away_score);
+            let new_winner = edit_data
+                .played
+                .then_some(edit_data.home_score > edit_data.away_score);
+            if current_winner == new_winner {
+                return Ok(());
+            }
+        }
+
+        BracketMatch::check_next_matches_not_played(
+            league_id,
+            bracket_fixture.match_number,
+            db_pool,
+        )
+        .await
+    }
+
+    /// Responsible to check the winner of a two legged bracket match can change, when its tie extra time or penalties change
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `first_leg_id` - The first leg id of the tie
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the next matches were already played
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn check_bracket_tie(
+        league_id: u64,
+        first_leg_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let bracket_match = sqlx::query!(
+            "
+        SELECT BracketRow.match_number
+        FROM `bracket_matches` as BracketRow
+        WHERE BracketRow.league_id = (?) AND BracketRow.fixture_id = (?)
+        ",
+            league_id,
+            first_leg_id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league bracket. Please try again later",
+            )))
+        })?;
+
+        match bracket_match {
+            None => Ok(()),
+            Some(bracket_match) => {
+                BracketMatch::check_next_matches_not_played(
+                    league_id,
+                    bracket_match.match_number,
+                    db_pool,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Responsible to refuse changing the winner of a bracket match after the matches which it feeds are played
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the next matches were already played
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn check_next_matches_not_played(
+        league_id: u64,
+        match_number: u16,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let next_matches = sqlx::query!(
+            "
+        SELECT COUNT(FixtureRow.id) as played_quantity
+        FROM `bracket_matches` as BracketRow
+        LEFT JOIN `ties` as TieRow
+            ON TieRow.first_leg_id = BracketRow.fixture_id
+        INNER JOIN `fixtures` as FixtureRow
+            ON FixtureRow.id = BracketRow.fixture_id OR FixtureRow.id = TieRow.second_leg_id
+        WHERE BracketRow.league_id = (?) AND FixtureRow.played = TRUE
+            AND (BracketRow.home_source_match = (?) OR BracketRow.away_source_match = (?))
+        ",
+            league_id,
+            match_number,
+            match_number
+        )
+        .fetch_one(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league bracket. Please try again later",
+            )))
+        })?;
+
+        if next_matches.played_quantity > 0 {
+            return Err(HTTPException::BadRequest(String::from(
+                "The winner can't change after the next bracket matches are played",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Responsible to refuse changing the teams of a bracket fixture by hand, they're moved by the bracket results.
+    /// The second legs of the two legged matches are bracket fixtures too
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `fixture_id` - The fixture id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If it's a bracket fixture
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn check_not_bracket_fixture(
+        league_id: u64,
+        fixture_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let bracket_match = sqlx::query!(
+            "
+        SELECT BracketRow.match_number
+        FROM `bracket_matches` as BracketRow
+        LEFT JOIN `ties` as TieRow
+            ON TieRow.first_leg_id = BracketRow.fixture_id
+        WHERE BracketRow.league_id = (?) AND (BracketRow.fixture_id = (?) OR TieRow.second_leg_id = (?))
+        ",
+            league_id,
+            fixture_id,
+            fixture_id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league bracket. Please try again later",
+            )))
+        })?;
+
+        if bracket_match.is_some() {
+            return Err(HTTPException::BadRequest(String::from(
+                "The bracket fixtures are created from the previous results, so they can't be changed by hand",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Responsible to delete the bracket of a league, so it can be generated again
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `transaction` - The transaction which deletes the league fixtures
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn delete_bracket(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), HTTPException> {
+        sqlx::query!(
+            "
+        DELETE BracketRow.* FROM `bracket_matches` as BracketRow
+        INNER JOIN `leagues` as LeagueRow
+            ON LeagueRow.id = BracketRow.league_id
+        INNER JOIN `tournaments` as TournamentRow
+            ON LeagueRow.tournament_id = TournamentRow.id
+        WHERE TournamentRow.id = (?) AND LeagueRow.id = (?) AND TournamentRow.user_id = (?)
+        ",
+            tournament_id,
+            league_id,
+            user_id
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to delete the league bracket. Please try again later",
+            )))
+        })?;
+
+        Ok(())
+    }
+}
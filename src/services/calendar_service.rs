@@ -0,0 +1,273 @@
+use chrono::{Duration, Timelike, Utc};
+use sqlx::{MySql, Pool};
+
+use crate::{
+    models::{calendar::CalendarFixture, league::League, team::Team},
+    responses::HTTPException,
+};
+
+// The fixtures dated with a time are shown as events of this length, the ones dated at midnight as all day events
+const FIXTURE_DURATION_HOURS: i64 = 2;
+// The longest line of an iCalendar file, in bytes and without the line break
+const CALENDAR_LINE_LENGTH: usize = 75;
+
+/// Responsible to escape a text value of an iCalendar property. The `CRLF` and `CR` line breaks are written as `\n` too,
+/// a bare `CR` would end the property line
+fn escape_text(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Responsible to fold an iCalendar line, the lines longer than 75 bytes continue on the next lines after a space
+///
+/// # Arguments
+/// * `line` - The property line
+///
+/// # Returns
+/// * `String` - The folded line, with a `CRLF` line break after each part
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut part_length = 0;
+
+    for character in line.chars() {
+        if part_length + character.len_utf8() > CALENDAR_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            part_length = 1;
+        }
+
+        folded.push(character);
+        part_length += character.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Responsible to write an iCalendar file with an event for each fixture.
+/// The description of a fixture has the league, round, teams and score. There's no location, since the teams grounds are not known
+///
+/// # Arguments
+/// * `name` - The calendar name shown by the calendar apps
+/// * `fixtures` - The dated fixtures
+///
+/// # Returns
+/// * `String` - The iCalendar file
+fn write_calendar(name: &str, fixtures: &[CalendarFixture]) -> String {
+    let timestamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//tournament//fixtures//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+        String::from("METHOD:PUBLISH"),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for fixture in fixtures {
+        let (summary, score) = if fixture.played {
+            (
+                format!(
+                    "{} {} - {} {}",
+                    fixture.home_team_name,
+                    fixture.home_score,
+                    fixture.away_score,
+                    fixture.away_team_name
+                ),
+                format!("{} - {}", fixture.home_score, fixture.away_score),
+            )
+        } else {
+            (
+                format!("{} vs {}", fixture.home_team_name, fixture.away_team_name),
+                String::from("Not played yet"),
+            )
+        };
+        let description = format!(
+            "{}, round {}\nHome: {}\nAway: {}\nScore: {}",
+            fixture.league_name,
+            fixture.round,
+            fixture.home_team_name,
+            fixture.away_team_name,
+            score
+        );
+
+        let (start, end) = if fixture.playing_date.num_seconds_from_midnight() == 0 {
+            (
+                format!(
+                    "DTSTART;VALUE=DATE:{}",
+                    fixture.playing_date.format("%Y%m%d")
+                ),
+                format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (fixture.playing_date + Duration::days(1)).format("%Y%m%d")
+                ),
+            )
+        } else {
+            (
+                format!("DTSTART:{}", fixture.playing_date.format("%Y%m%dT%H%M%SZ")),
+                format!(
+                    "DTEND:{}",
+                    (fixture.playing_date + Duration::hours(FIXTURE_DURATION_HOURS))
+                        .format("%Y%m%dT%H%M%SZ")
+                ),
+            )
+        };
+
+        lines.extend([
+            String::from("BEGIN:VEVENT"),
+            format!("UID:fixture-{}@tournament", fixture.id),
+            format!("DTSTAMP:{}", timestamp),
+            start,
+            end,
+            format!("SUMMARY:{}", escape_text(&summary)),
+            format!("DESCRIPTION:{}", escape_text(&description)),
+            String::from("END:VEVENT"),
+        ]);
+    }
+
+    lines.push(String::from("END:VCALENDAR"));
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+impl League {
+    /// Responsible to get the iCalendar feed of the league fixtures, the fixtures without a date are left out
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id, `0` when the feed is requested without authentication
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The iCalendar file
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the league does not exist, or the tournament is private and the user does not own it
+    pub async fn get_league_calendar(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<String, HTTPException> {
+        let league = League::get_league(user_id, tournament_id, league_id, db_pool).await?;
+
+        let fixtures = sqlx::query!(
+            "
+            SELECT FixtureRow.id, FixtureRow.round, FixtureRow.playing_date, FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played,
+            HomeTeamRow.name as home_team_name, AwayTeamRow.name as away_team_name
+            FROM `fixtures` as FixtureRow
+            INNER JOIN `teams` as HomeTeamRow
+                ON HomeTeamRow.id = FixtureRow.home_team_id
+            INNER JOIN `teams` as AwayTeamRow
+                ON AwayTeamRow.id = FixtureRow.away_team_id
+            WHERE FixtureRow.league_id = (?) AND FixtureRow.playing_date IS NOT NULL
+            ORDER BY FixtureRow.playing_date ASC, FixtureRow.round ASC, FixtureRow.id ASC
+        ",
+            league_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league fixtures. Please try again later",
+            )))
+        })?;
+
+        let fixtures: Vec<CalendarFixture> = fixtures
+            .into_iter()
+            .filter_map(|fixture| {
+                Some(CalendarFixture {
+                    id: fixture.id,
+                    league_name: league.name.clone(),
+                    round: fixture.round,
+                    home_team_name: fixture.home_team_name,
+                    away_team_name: fixture.away_team_name,
+                    home_score: fixture.home_score,
+                    away_score: fixture.away_score,
+                    played: fixture.played == 1,
+                    playing_date: fixture.playing_date?,
+                })
+            })
+            .collect();
+
+        Ok(write_calendar(&league.name, &fixtures))
+    }
+}
+
+impl Team {
+    /// Responsible to get the iCalendar feed of the team fixtures on every league of the tournament, the fixtures without a date are left out
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id, `0` when the feed is requested without authentication
+    /// * `tournament_id` - The tournament id
+    /// * `team_id` - The team id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The iCalendar file
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the team does not exist, or the tournament is private and the user does not own it
+    pub async fn get_team_calendar(
+        user_id: u64,
+        tournament_id: u64,
+        team_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<String, HTTPException> {
+        let team = Team::get_team_by_id_and_tournament_id(user_id, tournament_id, team_id, db_pool)
+            .await?;
+
+        let fixtures = sqlx::query!(
+            "
+            SELECT FixtureRow.id, FixtureRow.round, FixtureRow.playing_date, FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played,
+            HomeTeamRow.name as home_team_name, AwayTeamRow.name as away_team_name, LeagueRow.name as league_name
+            FROM `fixtures` as FixtureRow
+            INNER JOIN `leagues` as LeagueRow
+                ON LeagueRow.id = FixtureRow.league_id
+            INNER JOIN `teams` as HomeTeamRow
+                ON HomeTeamRow.id = FixtureRow.home_team_id
+            INNER JOIN `teams` as AwayTeamRow
+                ON AwayTeamRow.id = FixtureRow.away_team_id
+            WHERE LeagueRow.tournament_id = (?) AND FixtureRow.playing_date IS NOT NULL
+            AND (FixtureRow.home_team_id = (?) OR FixtureRow.away_team_id = (?))
+            ORDER BY FixtureRow.playing_date ASC, FixtureRow.round ASC, FixtureRow.id ASC
+        ",
+            tournament_id,
+            team_id,
+            team_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the team fixtures. Please try again later",
+            )))
+        })?;
+
+        let fixtures: Vec<CalendarFixture> = fixtures
+            .into_iter()
+            .filter_map(|fixture| {
+                Some(CalendarFixture {
+                    id: fixture.id,
+                    league_name: fixture.league_name,
+                    round: fixture.round,
+                    home_team_name: fixture.home_team_name,
+                    away_team_name: fixture.away_team_name,
+                    home_score: fixture.home_score,
+                    away_score: fixture.away_score,
+                    played: fixture.played == 1,
+                    playing_date: fixture.playing_date?,
+                })
+            })
+            .collect();
+
+        Ok(write_calendar(&team.name, &fixtures))
+    }
+}
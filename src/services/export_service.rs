@@ -0,0 +1,403 @@
+use sqlx::{MySql, Pool};
+
+use crate::{
+    constants::CSV_FORMULA_PREFIXES,
+    models::{
+        export::{ExportTable, LeagueExportDTO, TournamentExportDTO},
+        fixture::FixtureDataDTO,
+        import::LeagueImportDTO,
+        league::{League, LeagueFormat, TeamStandingTable},
+        team::TeamInfoDTO,
+        tournament::Tournament,
+    },
+    responses::HTTPException,
+};
+
+/// Responsible to write a CSV file. A field with commas, quotes or line breaks is quoted, and its quotes are written as `""`.
+/// A field starting with `=`, `+`, `-`, `@`, a tab or a carriage return which is not a number, e.g. a team name, gets a leading `'` so the spreadsheet apps don't run it as a formula
+///
+/// # Arguments
+/// * `header` - The columns of the file
+/// * `rows` - The fields of each row, in the same order as the header
+///
+/// # Returns
+/// * `String` - The CSV file, with a line break after each row
+fn write_csv(header: &[&str], rows: Vec<Vec<String>>) -> String {
+    let write_row = |fields: Vec<String>| {
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|field| {
+                let is_formula =
+                    field.starts_with(CSV_FORMULA_PREFIXES) && field.parse::<f64>().is_err();
+                let field = if is_formula {
+                    format!("'{}", field)
+                } else {
+                    field
+                };
+
+                if field.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", field.replace('"', "\"\""))
+                } else {
+                    field
+                }
+            })
+            .collect();
+
+        format!("{}\n", fields.join(","))
+    };
+
+    let mut file = write_row(header.iter().map(|column| column.to_string()).collect());
+    for row in rows {
+        file.push_str(&write_row(row));
+    }
+
+    file
+}
+
+/// The teams file, its `name` column is the one read by the league import
+fn teams_csv(teams: &[TeamInfoDTO]) -> String {
+    let rows = teams
+        .iter()
+        .map(|team| vec![team.id.to_string(), team.name.clone()])
+        .collect();
+
+    write_csv(&["id", "name"], rows)
+}
+
+/// The fields of a fixture row, the scores are empty while the fixture is not played so the league import keeps it pending
+fn fixture_fields(fixture: &FixtureDataDTO) -> Vec<String> {
+    let score = |score: u8| {
+        if fixture.played {
+            score.to_string()
+        } else {
+            String::new()
+        }
+    };
+
+    vec![
+        fixture.id.to_string(),
+        fixture.round.to_string(),
+        fixture.home_team.id.to_string(),
+        fixture.home_team.name.clone(),
+        fixture.away_team.id.to_string(),
+        fixture.away_team.name.clone(),
+        score(fixture.home_score),
+        score(fixture.away_score),
+        fixture.played.to_string(),
+    ]
+}
+
+const FIXTURE_COLUMNS: [&str; 9] = [
+    "id",
+    "round",
+    "home_team_id",
+    "home_team",
+    "away_team_id",
+    "away_team",
+    "home_score",
+    "away_score",
+    "played",
+];
+
+/// The fixtures file, with the `home_team`, `away_team`, `round`, `home_score` and `away_score` columns of the league import
+fn fixtures_csv(fixtures: &[FixtureDataDTO]) -> String {
+    write_csv(
+        &FIXTURE_COLUMNS,
+        fixtures.iter().map(fixture_fields).collect(),
+    )
+}
+
+/// The fields of a standing table row, the swiss tiebreaks are empty on the other formats
+fn standing_fields(position: usize, team: &TeamStandingTable) -> Vec<String> {
+    vec![
+        position.to_string(),
+        team.team_id.to_string(),
+        team.team_name.clone(),
+        team.matches_played.to_string(),
+        team.total_points.to_string(),
+        team.win.to_string(),
+        team.draw.to_string(),
+        team.loss.to_string(),
+        team.goals_scored.to_string(),
+        team.goals_against.to_string(),
+        team.goal_difference.to_string(),
+        team.form.clone(),
+        team.zone
+            .as_ref()
+            .map_or(String::new(), |zone| zone.label.clone()),
+        team.buchholz
+            .map_or(String::new(), |buchholz| buchholz.to_string()),
+        team.sonneborn_berger
+            .map_or(String::new(), |sonneborn_berger| {
+                sonneborn_berger.to_string()
+            }),
+    ]
+}
+
+const STANDING_COLUMNS: [&str; 15] = [
+    "position",
+    "team_id",
+    "team",
+    "matches_played",
+    "total_points",
+    "win",
+    "draw",
+    "loss",
+    "goals_scored",
+    "goals_against",
+    "goal_difference",
+    "form",
+    "zone",
+    "buchholz",
+    "sonneborn_berger",
+];
+
+/// Responsible to put the league columns of the tournament files before the fields of a row
+fn with_league(league: &LeagueExportDTO, fields: Vec<String>) -> Vec<String> {
+    [league.league.id.to_string(), league.league.name.clone()]
+        .into_iter()
+        .chain(fields)
+        .collect()
+}
+
+fn standing_table_csv(standing_table: &[TeamStandingTable]) -> String {
+    let rows = standing_table
+        .iter()
+        .enumerate()
+        .map(|(index, team)| standing_fields(index + 1, team))
+        .collect();
+
+    write_csv(&STANDING_COLUMNS, rows)
+}
+
+impl League {
+    /// Responsible to export every table of a league
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(LeagueExportDTO)` - The league, its teams, fixtures and overall standing table.
+    /// The fixtures of a double elimination league are left out of the import files, since its fixtures are created by its bracket
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the league does not exist, or the tournament is private and the user does not own it
+    pub async fn export_league(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<LeagueExportDTO, HTTPException> {
+        let league = League::get_league(user_id, tournament_id, league_id, db_pool).await?;
+
+        let teams = sqlx::query!(
+            "
+        SELECT TeamRow.id, TeamRow.name
+        FROM `teams` as TeamRow
+        INNER JOIN `teams_leagues` as TeamLeagueRow
+            ON TeamRow.id = TeamLeagueRow.team_id
+        WHERE TeamLeagueRow.league_id = (?)
+        ORDER BY TeamRow.id
+        ",
+            league_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the teams from the league. Please try again later",
+            )))
+        })?
+        .into_iter()
+        .map(|team| TeamInfoDTO {
+            id: team.id,
+            name: team.name,
+        })
+        .collect::<Vec<TeamInfoDTO>>();
+
+        let fixtures =
+            League::get_league_fixtures(user_id, tournament_id, league_id, db_pool).await?;
+        let standing_table =
+            League::get_league_standing_table(league_id, user_id, tournament_id, None, db_pool)
+                .await?
+                .overall;
+
+        let import = LeagueImportDTO {
+            teams: Some(teams_csv(&teams)),
+            fixtures: if league.format == LeagueFormat::DoubleElimination {
+                None
+            } else {
+                Some(fixtures_csv(&fixtures))
+            },
+        };
+
+        Ok(LeagueExportDTO {
+            league,
+            teams,
+            fixtures,
+            standing_table,
+            import,
+        })
+    }
+
+    /// Responsible to export a table of a league as a CSV file
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `table` - The exported table
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The CSV file
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the league does not exist, or the tournament is private and the user does not own it
+    pub async fn export_league_csv(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        table: ExportTable,
+        db_pool: &Pool<MySql>,
+    ) -> Result<String, HTTPException> {
+        let export = League::export_league(user_id, tournament_id, league_id, db_pool).await?;
+
+        Ok(match table {
+            ExportTable::Teams => teams_csv(&export.teams),
+            ExportTable::Fixtures => fixtures_csv(&export.fixtures),
+            ExportTable::StandingTable => standing_table_csv(&export.standing_table),
+        })
+    }
+}
+
+impl Tournament {
+    /// Responsible to export the teams of a tournament and every table of its leagues
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(TournamentExportDTO)` - The tournament teams and the export of each league
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the tournament does not exist, or it's private and the user does not own it
+    pub async fn export_tournament(
+        user_id: u64,
+        tournament_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<TournamentExportDTO, HTTPException> {
+        let name =
+            Tournament::check_tournament_access(Some(user_id), tournament_id, db_pool).await?;
+
+        let leagues_rows = sqlx::query!(
+            "SELECT id FROM `leagues` WHERE tournament_id = (?) ORDER BY id ASC",
+            tournament_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to search the leagues on the tournament. Please try again later",
+            )))
+        })?;
+
+        let teams = sqlx::query_as!(
+            TeamInfoDTO,
+            "SELECT id, name FROM `teams` WHERE tournament_id = (?) ORDER BY id ASC",
+            tournament_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to search the teams on the tournament. Please try again later",
+            )))
+        })?;
+
+        let mut leagues = vec![];
+        for league in leagues_rows {
+            leagues.push(League::export_league(user_id, tournament_id, league.id, db_pool).await?);
+        }
+
+        Ok(TournamentExportDTO {
+            id: tournament_id,
+            name,
+            teams,
+            leagues,
+        })
+    }
+
+    /// Responsible to export a table of a tournament as a CSV file. The fixtures and standing tables of every league are on the same file,
+    /// with the `league_id` and `league` columns first
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `table` - The exported table
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The CSV file
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the tournament does not exist, or it's private and the user does not own it
+    pub async fn export_tournament_csv(
+        user_id: u64,
+        tournament_id: u64,
+        table: ExportTable,
+        db_pool: &Pool<MySql>,
+    ) -> Result<String, HTTPException> {
+        let export = Tournament::export_tournament(user_id, tournament_id, db_pool).await?;
+
+        let (columns, rows): (&[&str], Vec<Vec<String>>) = match table {
+            ExportTable::Teams => return Ok(teams_csv(&export.teams)),
+            ExportTable::Fixtures => (
+                &FIXTURE_COLUMNS,
+                export
+                    .leagues
+                    .iter()
+                    .flat_map(|league| {
+                        league
+                            .fixtures
+                            .iter()
+                            .map(move |fixture| with_league(league, fixture_fields(fixture)))
+                    })
+                    .collect(),
+            ),
+            ExportTable::StandingTable => (
+                &STANDING_COLUMNS,
+                export
+                    .leagues
+                    .iter()
+                    .flat_map(|league| {
+                        league
+                            .standing_table
+                            .iter()
+                            .enumerate()
+                            .map(move |(index, team)| {
+                                with_league(league, standing_fields(index + 1, team))
+                            })
+                    })
+                    .collect(),
+            ),
+        };
+
+        let header: Vec<&str> = ["league_id", "league"]
+            .iter()
+            .chain(columns)
+            .copied()
+            .collect();
+
+        Ok(write_csv(&header, rows))
+    }
+}
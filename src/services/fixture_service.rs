@@ -1,12 +1,25 @@
 
-use sqlx::{MySql, Pool};
+use std::{
+    collections::{hash_map::RandomState, HashMap, HashSet},
+    hash::{BuildHasher, Hasher},
+};
+
+use chrono::{Duration, NaiveDate};
+use sqlx::{MySql, Pool, QueryBuilder, Transaction};
 
 use crate::{
     constants::MYSQL_CUSTOM_ERROR,
     models::{
-        fixture::{EditFixtureDTO, Fixture, FixtureDataDTO, FixtureObject},
-        league::League,
+        bracket::BracketMatch,
+        fixture::{
+            EditFixtureDTO, EditFixturePairingDTO, Fixture, FixtureChangesDTO,
+            FixtureConstraintsDTO, FixtureDataDTO, FixtureGenerationQuery, FixtureObject,
+            FixturePreviewDTO, NewFixtureDTO, ScheduledFixturesDTO, SubmittedScheduleDTO,
+            SwissRoundDTO, UnmetConstraintsDTO,
+        },
+        league::{League, LeagueFormat},
         team::{Team, TeamInfoDTO},
+        tie::Tie,
     },
     responses::HTTPException,
 };
@@ -15,7 +28,27 @@ use super::league_service::get_teams_from_league;
 
 pub type FixturesList = Vec<Vec<FixtureObject>>;
 
-/// Responsible to generate the league fixtures
+/// The biggest fixtures seed, it's the biggest integer a JavaScript number holds exactly so clients can send the seed back
+const MAX_FIXTURE_SEED: u64 = (1 << 53) - 1;
+
+/// How many team swaps the scheduler tries before giving up on the unmet constraints
+const SCHEDULER_ITERATIONS: usize = 5000;
+
+/// How many swaps without finding a better table the scheduler tries before shuffling the teams again
+const SCHEDULER_RESTART_AFTER: usize = 200;
+
+/// Days between the rounds when the scheduler constraints don't tell it
+const DEFAULT_DAYS_BETWEEN_ROUNDS: u16 = 7;
+
+/// How many pairs the swiss pairing tries before giving up on the round
+const SWISS_PAIRING_STEPS: usize = 100_000;
+
+/// How many steps the search of the rounds for the missing fixtures tries before allowing one more round
+const RESCHEDULE_STEPS: usize = 100_000;
+
+/// Responsible to generate the league fixtures with the Berger tables.
+/// The last team is fixed and the others rotate around it, the fixed team switches between home and away every round
+/// and the other pairings are flipped by their distance, so no team plays more than two consecutive games at home or away
 ///
 /// # Arguments
 /// * `&mut Vec<Team>` - A mutable reference of a vector of teams
@@ -36,20 +69,33 @@ fn berger_table(teams: &mut Vec<Team>) -> FixturesList {
     let teams_quantity = teams.len();
     let number_of_rounds = teams_quantity - 1;
     let games_per_round = teams_quantity / 2;
+    let fixed_team = &teams[number_of_rounds];
 
-    let mut column_a = (&teams[0..games_per_round]).to_vec();
-    let mut column_b = (&teams[games_per_round..]).to_vec();
-
-    let mut current_game_week = 1;
     let mut fixtures: Vec<Vec<FixtureObject>> = vec![];
 
-    for _ in 0..number_of_rounds {
+    for round in 0..number_of_rounds {
         let mut game_week: Vec<FixtureObject> = vec![];
 
-        for index in 0..games_per_round {
-            let home_team = &column_a[index];
-            let away_team = &column_b[index];
+        // The fixed team plays the team on the rotation position, being home on every other round
+        let mut pairings = vec![if round % 2 == 0 {
+            (fixed_team, &teams[round])
+        } else {
+            (&teams[round], fixed_team)
+        }];
+
+        // The other teams are paired by their distance to the rotation position, the team ahead is home when the distance is odd
+        for distance in 1..games_per_round {
+            let ahead_team = &teams[(round + distance) % number_of_rounds];
+            let behind_team = &teams[(round + number_of_rounds - distance) % number_of_rounds];
+
+            pairings.push(if distance % 2 == 1 {
+                (ahead_team, behind_team)
+            } else {
+                (behind_team, ahead_team)
+            });
+        }
 
+        for (home_team, away_team) in pairings {
             // Making sure both of these teams aren't "dummy"
             if home_team.id != 0 && away_team.id != 0 {
                 game_week.push(FixtureObject {
@@ -58,101 +104,1479 @@ fn berger_table(teams: &mut Vec<Team>) -> FixturesList {
                     home_score: 0,
                     away_score: 0,
                     played: false,
-                    round: current_game_week,
+                    round: round as u16 + 1,
                 });
             }
         }
-        fixtures.push(game_week);
-        current_game_week += 1;
 
-        // Rotating the teams
-        let first_from_column_b = column_b.remove(0);
-        column_a.insert(1, first_from_column_b);
-        let last_element_from_column_a = column_a.pop().unwrap();
-        column_b.push(last_element_from_column_a);
+        fixtures.push(game_week);
     }
 
     return fixtures;
 }
 
+/// The venue of the latest games of a team
+#[derive(Clone, Copy)]
+struct VenueStreak {
+    home: bool,
+    // How many games in a row the team played at this venue
+    games: u8,
+}
+
+/// Responsible to add a game to the venue streak of a team
+fn next_venue_streak(streak: Option<VenueStreak>, home: bool) -> VenueStreak {
+    match streak {
+        Some(streak) if streak.home == home => VenueStreak {
+            home,
+            games: streak.games.saturating_add(1),
+        },
+        _ => VenueStreak { home, games: 1 },
+    }
+}
+
+/// The state of the search of the rounds for the missing fixtures
+struct MissingFixturesSearch<'a> {
+    // The missing fixtures on the Berger table order, with their Berger table round
+    fixtures: &'a [FixtureObject],
+    // The teams on the order they're placed on each round
+    team_ids: &'a [u64],
+    // The indexes of the missing fixtures of each team
+    team_fixtures: HashMap<u64, Vec<usize>>,
+    last_round: u16,
+    // If a team can't play more than two games in a row at the same venue
+    check_venues: bool,
+    steps_left: usize,
+    // The round of each placed fixture and if its home and away teams are switched
+    placed_fixtures: Vec<Option<(u16, bool)>>,
+    // The teams which play, or rest, on the round being placed
+    round_teams: HashSet<u64>,
+    games_left: HashMap<u64, u16>,
+    venue_streaks: HashMap<u64, VenueStreak>,
+}
+
+/// Responsible to place the missing fixtures on the rounds until `last_round`, one team at a time on each round.
+/// A team plays its fixture of the same Berger table round first, then it rests if it still has enough rounds for its games,
+/// then it plays its other fixtures. It backtracks when a team can't play its games anymore and gives up after `RESCHEDULE_STEPS` steps
+///
+/// # Arguments
+/// * `search` - The state of the search
+/// * `round` - The round being placed
+/// * `team_position` - The position of the first team on `team_ids` which is not checked on this round yet
+///
+/// # Returns
+/// * `bool` - `true` if every fixture was placed
+fn search_missing_rounds(
+    search: &mut MissingFixturesSearch,
+    round: u16,
+    team_position: usize,
+) -> bool {
+    let next_position = (team_position..search.team_ids.len()).find(|&position| {
+        let team_id = search.team_ids[position];
+        !search.round_teams.contains(&team_id) && search.games_left[&team_id] > 0
+    });
+    let Some(position) = next_position else {
+        if search.games_left.values().all(|&games| games == 0) {
+            return true;
+        }
+        if round >= search.last_round {
+            return false;
+        }
+
+        let round_teams = std::mem::take(&mut search.round_teams);
+        if search_missing_rounds(search, round + 1, 0) {
+            return true;
+        }
+        search.round_teams = round_teams;
+
+        return false;
+    };
+
+    if search.steps_left == 0 {
+        return false;
+    }
+    search.steps_left -= 1;
+
+    let team_id = search.team_ids[position];
+    let mut options: Vec<(u16, Option<usize>)> = search.team_fixtures[&team_id]
+        .iter()
+        .filter(|&&index| {
+            let fixture = &search.fixtures[index];
+            search.placed_fixtures[index].is_none()
+                && !search.round_teams.contains(&fixture.home_team_id)
+                && !search.round_teams.contains(&fixture.away_team_id)
+        })
+        .map(|&index| {
+            let berger_round = search.fixtures[index].round;
+            if berger_round == round {
+                (0, Some(index))
+            } else {
+                (berger_round.saturating_add(1), Some(index))
+            }
+        })
+        .collect();
+    if search.games_left[&team_id] <= search.last_round - round {
+        options.push((1, None));
+    }
+    options.sort_by_key(|&(order, _)| order);
+
+    for (_, option) in options {
+        let Some(index) = option else {
+            search.round_teams.insert(team_id);
+            if search_missing_rounds(search, round, position + 1) {
+                return true;
+            }
+            search.round_teams.remove(&team_id);
+            continue;
+        };
+
+        let fixture = &search.fixtures[index];
+        let (berger_home_team_id, berger_away_team_id) =
+            (fixture.home_team_id, fixture.away_team_id);
+        let orientation = |switched: bool| {
+            let (home_team_id, away_team_id) = if switched {
+                (berger_away_team_id, berger_home_team_id)
+            } else {
+                (berger_home_team_id, berger_away_team_id)
+            };
+            let home_streak =
+                next_venue_streak(search.venue_streaks.get(&home_team_id).copied(), true);
+            let away_streak =
+                next_venue_streak(search.venue_streaks.get(&away_team_id).copied(), false);
+
+            (
+                switched,
+                home_team_id,
+                away_team_id,
+                home_streak,
+                away_streak,
+            )
+        };
+
+        // The Berger table venues are kept unless a team would play a third game in a row at the same venue
+        let mut orientations: Vec<(bool, u64, u64, VenueStreak, VenueStreak)> = [false, true]
+            .into_iter()
+            .map(orientation)
+            .filter(|(_, _, _, home_streak, away_streak)| {
+                home_streak.games <= 2 && away_streak.games <= 2
+            })
+            .collect();
+        if orientations.is_empty() && !search.check_venues {
+            orientations.push(orientation(false));
+        }
+
+        for (switched, home_team_id, away_team_id, home_streak, away_streak) in orientations {
+            let previous_home_streak = search.venue_streaks.insert(home_team_id, home_streak);
+            let previous_away_streak = search.venue_streaks.insert(away_team_id, away_streak);
+            search.placed_fixtures[index] = Some((round, switched));
+            for team_id in [home_team_id, away_team_id] {
+                search.round_teams.insert(team_id);
+                *search.games_left.entry(team_id).or_default() -= 1;
+            }
+
+            if search_missing_rounds(search, round, position + 1) {
+                return true;
+            }
+
+            for (team_id, previous_streak) in [
+                (home_team_id, previous_home_streak),
+                (away_team_id, previous_away_streak),
+            ] {
+                search.round_teams.remove(&team_id);
+                *search.games_left.entry(team_id).or_default() += 1;
+                match previous_streak {
+                    Some(streak) => search.venue_streaks.insert(team_id, streak),
+                    None => search.venue_streaks.remove(&team_id),
+                };
+            }
+            search.placed_fixtures[index] = None;
+        }
+    }
+
+    false
+}
+
+/// Responsible to schedule the league pairings which don't have a fixture yet, from `first_round`.
+/// The missing pairings are placed on the fewest rounds they fit, the Berger table round count when the kept fixtures allow it,
+/// and on their Berger table rounds when they're free. The Berger table venues are kept, unless a team would play
+/// more than two games in a row at the same venue counting the kept fixtures. When the search can't place them, each pairing
+/// is placed on the earliest round where none of its teams plays yet. Without any kept fixture the result is the same as the Berger table
+///
+/// # Arguments
+/// * `teams` - A mutable reference of a vector of teams
+/// * `kept_fixtures` - The fixtures which are kept, they're all before `first_round`
+/// * `first_round` - The first round the new fixtures can be placed on
+///
+/// # Returns
+/// * `Vec<FixtureObject>` - The new fixtures, ordered by their round
+fn schedule_missing_fixtures(
+    teams: &mut Vec<Team>,
+    kept_fixtures: &[FixtureDataDTO],
+    first_round: u16,
+) -> Vec<FixtureObject> {
+    let berger_fixtures = berger_table(teams);
+    let berger_rounds = berger_fixtures.len() as u16;
+    let kept_pairings: HashSet<(u64, u64)> = kept_fixtures
+        .iter()
+        .map(|fixture| pairing(fixture.home_team.id, fixture.away_team.id))
+        .collect();
+    let missing_fixtures: Vec<FixtureObject> = berger_fixtures
+        .into_iter()
+        .flatten()
+        .filter(|fixture| {
+            !kept_pairings.contains(&pairing(fixture.home_team_id, fixture.away_team_id))
+        })
+        .collect();
+
+    // The dummy team of the Berger table is not scheduled
+    let team_ids: Vec<u64> = teams
+        .iter()
+        .map(|team| team.id)
+        .filter(|&team_id| team_id != 0)
+        .collect();
+    let mut team_fixtures: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (index, fixture) in missing_fixtures.iter().enumerate() {
+        for team_id in [fixture.home_team_id, fixture.away_team_id] {
+            team_fixtures.entry(team_id).or_default().push(index);
+        }
+    }
+    let games_left: HashMap<u64, u16> = team_ids
+        .iter()
+        .map(|&team_id| {
+            let games = team_fixtures.entry(team_id).or_default().len() as u16;
+            (team_id, games)
+        })
+        .collect();
+
+    let mut ordered_kept_fixtures: Vec<&FixtureDataDTO> = kept_fixtures.iter().collect();
+    ordered_kept_fixtures.sort_by_key(|fixture| fixture.round);
+    let mut venue_streaks: HashMap<u64, VenueStreak> = HashMap::new();
+    for fixture in ordered_kept_fixtures {
+        for (team_id, home) in [(fixture.home_team.id, true), (fixture.away_team.id, false)] {
+            let streak = next_venue_streak(venue_streaks.get(&team_id).copied(), home);
+            venue_streaks.insert(team_id, streak);
+        }
+    }
+
+    // A team with more games left than the Berger table rounds left, e.g. a late entrant, needs more rounds
+    let most_games_left = games_left.values().copied().max().unwrap_or(0);
+    let fewest_last_round = berger_rounds.max(first_round - 1 + most_games_left);
+    for (last_round, check_venues) in [
+        (fewest_last_round, true),
+        (fewest_last_round, false),
+        (fewest_last_round + 1, true),
+        (fewest_last_round + 1, false),
+    ] {
+        let mut search = MissingFixturesSearch {
+            fixtures: &missing_fixtures,
+            team_ids: &team_ids,
+            team_fixtures: team_fixtures.clone(),
+            last_round,
+            check_venues,
+            steps_left: RESCHEDULE_STEPS,
+            placed_fixtures: vec![None; missing_fixtures.len()],
+            round_teams: HashSet::new(),
+            games_left: games_left.clone(),
+            venue_streaks: venue_streaks.clone(),
+        };
+        if !search_missing_rounds(&mut search, first_round, 0) {
+            continue;
+        }
+
+        let mut fixtures: Vec<FixtureObject> = missing_fixtures
+            .iter()
+            .zip(search.placed_fixtures)
+            .filter_map(|(fixture, placed_fixture)| {
+                let (round, switched) = placed_fixture?;
+                let (home_team_id, away_team_id) = if switched {
+                    (fixture.away_team_id, fixture.home_team_id)
+                } else {
+                    (fixture.home_team_id, fixture.away_team_id)
+                };
+
+                Some(FixtureObject {
+                    home_team_id,
+                    away_team_id,
+                    home_score: 0,
+                    away_score: 0,
+                    played: false,
+                    round,
+                })
+            })
+            .collect();
+        fixtures.sort_by_key(|fixture| fixture.round);
+
+        return fixtures;
+    }
+
+    let mut busy_teams: HashMap<u16, HashSet<u64>> = HashMap::new();
+    let mut fixtures = vec![];
+    for mut fixture in missing_fixtures {
+        let mut round = first_round;
+        loop {
+            let round_teams = busy_teams.entry(round).or_default();
+            if !round_teams.contains(&fixture.home_team_id)
+                && !round_teams.contains(&fixture.away_team_id)
+            {
+                round_teams.insert(fixture.home_team_id);
+                round_teams.insert(fixture.away_team_id);
+                break;
+            }
+
+            round += 1;
+        }
+
+        fixture.round = round;
+        fixtures.push(fixture);
+    }
+    fixtures.sort_by_key(|fixture| fixture.round);
+
+    fixtures
+}
+
+/// Seeded pseudo random generator (SplitMix64). The same seed always gives the same numbers, so a draw can be reproduced and audited
+struct SeededRandom {
+    state: u64,
+}
+
+impl SeededRandom {
+    fn new(seed: u64) -> SeededRandom {
+        SeededRandom { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut random = self.state;
+        random = (random ^ (random >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        random = (random ^ (random >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        random ^ (random >> 31)
+    }
+
+    /// Returns a number from 0 to `bound` - 1
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Responsible to shuffle the teams with a Fisher-Yates shuffle
+///
+/// # Arguments
+/// * `teams` - A mutable slice of teams, it should be on a stable order, e.g. by id
+/// * `random` - The seeded generator of the draw
+fn shuffle_teams(teams: &mut [Team], random: &mut SeededRandom) {
+    for index in (1..teams.len()).rev() {
+        teams.swap(index, random.below(index + 1));
+    }
+}
+
+/// Responsible to search a Berger table which meets the constraints. The teams are swapped between the table positions,
+/// keeping a swap when it doesn't increase the unmet constraints, and they're shuffled again when the search is stuck for `SCHEDULER_RESTART_AFTER` swaps.
+/// It stops when every constraint is met or `SCHEDULER_ITERATIONS` swaps are tried.
+/// Only the positions change, so the home and away games still alternate
+///
+/// # Arguments
+/// * `teams` - The league teams, on the order they were shuffled
+/// * `constraints` - The scheduler constraints
+/// * `unavailable_rounds` - The round of each unavailable date, `None` when there's no round on the date
+/// * `random` - The seeded generator of the draw
+///
+/// # Returns
+/// * `(FixturesList, UnmetConstraintsDTO)` - The best table found and the constraints it does not meet
+fn search_constrained_table(
+    mut teams: Vec<Team>,
+    constraints: &FixtureConstraintsDTO,
+    unavailable_rounds: &[Option<u16>],
+    random: &mut SeededRandom,
+) -> (FixturesList, UnmetConstraintsDTO) {
+    let mut best_fixtures = berger_table(&mut teams.clone());
+    let mut best_unmet = find_unmet_constraints(&best_fixtures, constraints, unavailable_rounds);
+    let mut current_unmet = best_unmet.len();
+    let mut swaps_without_improvement = 0;
+
+    for _ in 0..SCHEDULER_ITERATIONS {
+        if best_unmet.is_empty() {
+            break;
+        }
+
+        // Starting again from another order when the swaps can't improve the table anymore
+        if swaps_without_improvement >= SCHEDULER_RESTART_AFTER {
+            shuffle_teams(&mut teams, random);
+            let fixtures = berger_table(&mut teams.clone());
+            let unmet = find_unmet_constraints(&fixtures, constraints, unavailable_rounds);
+            current_unmet = unmet.len();
+            swaps_without_improvement = 0;
+
+            if unmet.len() < best_unmet.len() {
+                best_fixtures = fixtures;
+                best_unmet = unmet;
+            }
+        }
+
+        let first_position = random.below(teams.len());
+        let second_position = random.below(teams.len());
+        if first_position == second_position {
+            continue;
+        }
+
+        teams.swap(first_position, second_position);
+        let fixtures = berger_table(&mut teams.clone());
+        let unmet = find_unmet_constraints(&fixtures, constraints, unavailable_rounds);
+        swaps_without_improvement += 1;
+
+        // Accepting the swaps which don't make it worse allows the search to move away from a plateau
+        if unmet.len() > current_unmet {
+            teams.swap(first_position, second_position);
+            continue;
+        }
+
+        current_unmet = unmet.len();
+        if unmet.len() < best_unmet.len() {
+            best_fixtures = fixtures;
+            best_unmet = unmet;
+            swaps_without_improvement = 0;
+        }
+    }
+
+    (best_fixtures, best_unmet)
+}
+
+/// Responsible to check which constraints the fixtures don't meet
+///
+/// # Arguments
+/// * `fixtures` - The fixtures rounds
+/// * `constraints` - The scheduler constraints
+/// * `unavailable_rounds` - The round of each unavailable date, `None` when there's no round on the date
+///
+/// # Returns
+/// * `UnmetConstraintsDTO` - The constraints which are not met
+fn find_unmet_constraints(
+    fixtures: &FixturesList,
+    constraints: &FixtureConstraintsDTO,
+    unavailable_rounds: &[Option<u16>],
+) -> UnmetConstraintsDTO {
+    let plays_home = |team_id: u64, game_week: &Vec<FixtureObject>| {
+        game_week
+            .iter()
+            .any(|fixture| fixture.home_team_id == team_id)
+    };
+    let plays_on_round = |team_id: u64, round: u16| {
+        fixtures.iter().flatten().any(|fixture| {
+            fixture.round == round
+                && (fixture.home_team_id == team_id || fixture.away_team_id == team_id)
+        })
+    };
+
+    let shared_grounds = constraints
+        .shared_grounds
+        .iter()
+        .filter(|constraint| {
+            fixtures.iter().any(|game_week| {
+                plays_home(constraint.first_team_id, game_week)
+                    && plays_home(constraint.second_team_id, game_week)
+            })
+        })
+        .cloned()
+        .collect();
+
+    let unavailable_dates = constraints
+        .unavailable_dates
+        .iter()
+        .zip(unavailable_rounds)
+        .filter(|(constraint, round)| match round {
+            Some(round) => plays_on_round(constraint.team_id, *round),
+            None => false,
+        })
+        .map(|(constraint, _round)| constraint.clone())
+        .collect();
+
+    let fixed_rounds = constraints
+        .fixed_rounds
+        .iter()
+        .filter(|constraint| {
+            !fixtures.iter().flatten().any(|fixture| {
+                fixture.round == constraint.round
+                    && pairing(fixture.home_team_id, fixture.away_team_id)
+                        == pairing(constraint.first_team_id, constraint.second_team_id)
+            })
+        })
+        .cloned()
+        .collect();
+
+    UnmetConstraintsDTO {
+        shared_grounds,
+        unavailable_dates,
+        fixed_rounds,
+    }
+}
+
+/// Returns a random seed for a fixtures draw, no bigger than `MAX_FIXTURE_SEED`
+fn generate_fixture_seed() -> u64 {
+    RandomState::new().build_hasher().finish() & MAX_FIXTURE_SEED
+}
+
+/// The pairing history of a team on a swiss league
+struct SwissTeam {
+    team_id: u64,
+    opponents: HashSet<u64>,
+    // Home games minus away games
+    home_balance: i16,
+    // If the team played its latest fixture at home
+    last_home: Option<bool>,
+    byes: u16,
+}
+
+/// The state of a swiss round pairing, shared by every bye candidate
+struct SwissPairing {
+    // The teams, ordered by their ranking, which were already found impossible to pair without a rematch
+    unpairable_teams: HashSet<Vec<u64>>,
+    steps_left: usize,
+}
+
+/// Responsible to pair the teams of a swiss round, each team against the next one on the ranking it did not play yet.
+/// It backtracks when the teams left can't be paired without a rematch, remembering the teams which can't be paired
+/// so they're not tried again, and gives up after `SWISS_PAIRING_STEPS` pairs
+///
+/// # Arguments
+/// * `teams` - The teams ordered by their ranking
+/// * `pairing` - The teams found impossible to pair and the steps left
+///
+/// # Returns
+/// * `Option<Vec<(&SwissTeam, &SwissTeam)>>` - The pairs, `None` if the teams can't be paired without a rematch or the steps are over
+fn pair_swiss_teams<'a>(
+    teams: &[&'a SwissTeam],
+    pairing: &mut SwissPairing,
+) -> Option<Vec<(&'a SwissTeam, &'a SwissTeam)>> {
+    let Some((first_team, other_teams)) = teams.split_first() else {
+        return Some(vec![]);
+    };
+
+    let team_ids: Vec<u64> = teams.iter().map(|swiss_team| swiss_team.team_id).collect();
+    if pairing.unpairable_teams.contains(&team_ids) {
+        return None;
+    }
+
+    for (index, opponent) in other_teams.iter().enumerate() {
+        if first_team.opponents.contains(&opponent.team_id) {
+            continue;
+        }
+        if pairing.steps_left == 0 {
+            return None;
+        }
+        pairing.steps_left -= 1;
+
+        let mut remaining_teams = other_teams.to_vec();
+        remaining_teams.remove(index);
+        if let Some(mut pairs) = pair_swiss_teams(&remaining_teams, pairing) {
+            pairs.insert(0, (*first_team, *opponent));
+            return Some(pairs);
+        }
+    }
+
+    if pairing.steps_left > 0 {
+        pairing.unpairable_teams.insert(team_ids);
+    }
+
+    None
+}
+
+/// Responsible to choose which team of a swiss pair plays at home: the one with less home games,
+/// then the one which played away on its latest fixture and finally the best ranked one
+///
+/// # Returns
+/// * `bool` - `true` if the first team plays at home
+fn swiss_first_team_home(first_team: &SwissTeam, second_team: &SwissTeam) -> bool {
+    if first_team.home_balance != second_team.home_balance {
+        return first_team.home_balance < second_team.home_balance;
+    }
+
+    match (first_team.last_home, second_team.last_home) {
+        (Some(true), Some(false)) => false,
+        (Some(false), Some(true)) => true,
+        _ => true,
+    }
+}
+
+/// Responsible to parse a constraint date on the `YYYY-MM-DD` format
+///
+/// # Errors
+/// * `HTTPException::BadRequest` - If the date is invalid
+fn parse_constraint_date(date: &str) -> Result<NaiveDate, HTTPException> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").or_else(|_error| {
+        Err(HTTPException::BadRequest(format!(
+            "Invalid date {}, the dates must be on the YYYY-MM-DD format",
+            date
+        )))
+    })
+}
+
+/// Returns the two teams of a fixture on the same order no matter which one plays home
+fn pairing(first_team_id: u64, second_team_id: u64) -> (u64, u64) {
+    (
+        first_team_id.min(second_team_id),
+        first_team_id.max(second_team_id),
+    )
+}
+
 impl Fixture {
-    /// Responsible to generate the league fixtures
+    /// Responsible to generate the league fixtures.
+    /// When `regenerate` is `true` a league which already has fixtures can be generated again, e.g. after a late entrant joins it.
+    /// The played fixtures and the rounds which have any played fixture are kept, and the following rounds are recomputed
+    /// so every pairing of the league teams still happens exactly once.
+    /// The teams are shuffled with the `seed` before the draw and the seed is saved on the league, so the draw can be reproduced
     ///
     /// # Arguments
     /// * `user_id` - The user id
     /// * `tournament_id` - The tournament id
     /// * `league_id` - The league id
+    /// * `generation_query` - `&FixtureGenerationQuery` reference, if the existing fixtures should be regenerated and the draw seed
     /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
     ///
     /// # Returns
-    /// * `Ok(())` - If the fixtures were generated successfully
+    /// * `Ok(FixtureChangesDTO)` - The fixtures which were kept, removed and created
     ///
     /// # Errors
-    /// * `HTTPException::BadRequest` - If there's not enough teams or the user doesn't have permission to generate the fixtures
+    /// * `HTTPException::BadRequest`
+    /// * * There's not enough teams, the user doesn't have permission to generate the fixtures or the league is completed
+    /// * * The league already has fixtures and `regenerate` is `false`
+    /// * * The seed is bigger than `MAX_FIXTURE_SEED`
+    /// * * It's a swiss or double elimination league
     /// * `HTTPException::Internal` - If the database query fails
     pub async fn generate_league_fixtures(
         user_id: u64,
         tournament_id: u64,
         league_id: u64,
+        generation_query: &FixtureGenerationQuery,
         db_pool: &Pool<MySql>,
-    ) -> Result<(), HTTPException> {
+    ) -> Result<FixtureChangesDTO, HTTPException> {
+        let regenerate = generation_query.regenerate.unwrap_or(false);
+        if generation_query.seed.unwrap_or(0) > MAX_FIXTURE_SEED {
+            return Err(HTTPException::BadRequest(format!(
+                "The seed can't be bigger than {}",
+                MAX_FIXTURE_SEED
+            )));
+        }
+
+        // Initializing the transaction
+        // We'll use this because if one of the query fails we can cancel the whole operation
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+
         let mut teams = get_teams_from_league(league_id, tournament_id, user_id, db_pool).await?;
         if teams.len() < 2 {
             return Err(HTTPException::BadRequest(String::from(
                 "Not enough teams or permission to generate fixtures",
             )));
         }
+        Fixture::check_round_robin_league(league_id, db_pool).await?;
 
         let existing_fixtures =
-            League::get_league_fixtures(user_id, league_id, tournament_id, db_pool).await?;
-        if existing_fixtures.len() > 0 {
+            League::get_league_fixtures(user_id, tournament_id, league_id, db_pool).await?;
+        if existing_fixtures.len() > 0 && !regenerate {
             return Err(HTTPException::BadRequest(String::from(
-                "Cannot generate a fixture as it may conflict with existing ones",
+                "Cannot generate a fixture as it may conflict with existing ones. Regenerate it to keep the played fixtures",
             )));
         }
 
-        let generated_fixtures = berger_table(&mut teams);
+        let stored_seed = sqlx::query!(
+            "
+        SELECT LeagueRow.fixture_seed
+        FROM `leagues` as LeagueRow
+        WHERE LeagueRow.id = (?)
+        ",
+            league_id
+        )
+        .fetch_one(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league. Please try again later",
+            )))
+        })?
+        .fixture_seed;
+
+        let seed = match (generation_query.seed, stored_seed) {
+            (Some(seed), _) => seed,
+            (None, Some(seed)) if regenerate => seed,
+            _ => generate_fixture_seed(),
+        };
+        shuffle_teams(&mut teams, &mut SeededRandom::new(seed));
+
+        let team_ids: HashSet<u64> = teams.iter().map(|team| team.id).collect();
+        let last_played_round = existing_fixtures
+            .iter()
+            .filter(|fixture| fixture.played)
+            .map(|fixture| fixture.round)
+            .max()
+            .unwrap_or(0);
+
+        // The pending fixtures from the rounds which were already played are kept, unless one of the teams left the league
+        let (kept_fixtures, outdated_fixtures): (Vec<FixtureDataDTO>, Vec<FixtureDataDTO>) =
+            existing_fixtures.into_iter().partition(|fixture| {
+                fixture.played
+                    || (fixture.round <= last_played_round
+                        && team_ids.contains(&fixture.home_team.id)
+                        && team_ids.contains(&fixture.away_team.id))
+            });
+        let mut created_fixtures =
+            schedule_missing_fixtures(&mut teams, &kept_fixtures, last_played_round + 1);
+
+        // A recomputed fixture which is the same as a pending one doesn't need to be saved again
+        let mut removed_fixtures = vec![];
+        let mut unchanged_fixtures = 0;
+        for fixture in outdated_fixtures {
+            let same_fixture = created_fixtures.iter().position(|created| {
+                created.home_team_id == fixture.home_team.id
+                    && created.away_team_id == fixture.away_team.id
+                    && created.round == fixture.round
+            });
+
+            match same_fixture {
+                Some(index) => {
+                    created_fixtures.remove(index);
+                    unchanged_fixtures += 1;
+                }
+                None => removed_fixtures.push(fixture.id),
+            }
+        }
+
+        if !removed_fixtures.is_empty() {
+            let mut query_builder: QueryBuilder<MySql> =
+                QueryBuilder::new("DELETE FROM `fixtures` WHERE league_id = ");
+            query_builder.push_bind(league_id);
+            query_builder.push(" AND played = FALSE AND id IN (");
+            let mut separated = query_builder.separated(", ");
+            for fixture_id in &removed_fixtures {
+                separated.push_bind(fixture_id);
+            }
+            separated.push_unseparated(")");
+
+            query_builder
+                .build()
+                .execute(&mut *transaction)
+                .await
+                .or_else(|_error| {
+                    Err(HTTPException::Internal(String::from(
+                        "Failed while removing the outdated fixtures. The operation have been cancelled and will not be saved",
+                    )))
+                })?;
+        }
+
+        Fixture::insert_fixtures(
+            user_id,
+            league_id,
+            &created_fixtures,
+            Some(seed),
+            &mut transaction,
+        )
+        .await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something failed while saving all fixtures. Please try again later",
+            )))
+        })?;
+
+        Ok(FixtureChangesDTO {
+            kept_fixtures: kept_fixtures.len() + unchanged_fixtures,
+            removed_fixtures,
+            created_fixtures,
+            seed,
+        })
+    }
+
+    /// Responsible to generate the league fixtures meeting the constraints of the league, e.g. teams sharing a ground,
+    /// dates which a team can't play or derbies which must be played on a round.
+    /// The constraints which can't be met are reported, and the fixtures are saved with the table which meets the most of them
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `constraints` - `FixtureConstraintsDTO` struct with the constraints and the draw seed
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(ScheduledFixturesDTO)` - The created fixtures and the unmet constraints
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's not enough teams, the user doesn't have permission to generate the fixtures or the league is completed
+    /// * * The league already has fixtures
+    /// * * A constraint has a team which is not on the league, an invalid date or a round the league does not have
+    /// * * There's unavailable dates but no `start_date`
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn schedule_league_fixtures(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        constraints: FixtureConstraintsDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<ScheduledFixturesDTO, HTTPException> {
+        let seed = constraints.seed.unwrap_or_else(generate_fixture_seed);
+        if seed > MAX_FIXTURE_SEED {
+            return Err(HTTPException::BadRequest(format!(
+                "The seed can't be bigger than {}",
+                MAX_FIXTURE_SEED
+            )));
+        }
 
-        // Initializing the transaction
-        // We'll use this because if one of the query fails we can cancel the whole operation
         let mut transaction = db_pool.begin().await.or_else(|_error| {
             Err(HTTPException::Internal(String::from(
                 "Failed initializing the database transaction",
             )))
         })?;
 
-        // Saving all the game rounds from each fixture/game week
-        for game_week in generated_fixtures {
-            for fixture in game_week {
-                let _a = sqlx::query!(
+        let mut teams = Fixture::get_teams_to_schedule(
+            user_id,
+            tournament_id,
+            league_id,
+            &mut transaction,
+            db_pool,
+        )
+        .await?;
+
+        // Validating the constraints
+        let team_ids: HashSet<u64> = teams.iter().map(|team| team.id).collect();
+        let rounds_quantity = (teams.len() + teams.len() % 2 - 1) as u16;
+        let constraint_teams = constraints
+            .shared_grounds
+            .iter()
+            .flat_map(|constraint| [constraint.first_team_id, constraint.second_team_id])
+            .chain(
+                constraints
+                    .unavailable_dates
+                    .iter()
+                    .map(|constraint| constraint.team_id),
+            )
+            .chain(
+                constraints
+                    .fixed_rounds
+                    .iter()
+                    .flat_map(|constraint| [constraint.first_team_id, constraint.second_team_id]),
+            )
+            .collect::<Vec<u64>>();
+        if constraint_teams
+            .iter()
+            .any(|team_id| !team_ids.contains(team_id))
+        {
+            return Err(HTTPException::BadRequest(String::from(
+                "Every team on the constraints must be on the league",
+            )));
+        }
+        if constraints.fixed_rounds.iter().any(|constraint| {
+            constraint.round < 1
+                || constraint.round > rounds_quantity
+                || constraint.first_team_id == constraint.second_team_id
+        }) {
+            return Err(HTTPException::BadRequest(format!(
+                "The fixed rounds must be between 1 and {} and have two different teams",
+                rounds_quantity
+            )));
+        }
+
+        let start_date = match &constraints.start_date {
+            Some(date) => Some(parse_constraint_date(date)?),
+            None => None,
+        };
+        let days_between_rounds = constraints
+            .days_between_rounds
+            .unwrap_or(DEFAULT_DAYS_BETWEEN_ROUNDS)
+            .max(1) as i64;
+
+        // The unavailable dates are converted to the round played on them
+        let mut unavailable_rounds = vec![];
+        for constraint in &constraints.unavailable_dates {
+            let start_date = start_date.ok_or_else(|| {
+                HTTPException::BadRequest(String::from(
+                    "The start date is needed to know the rounds of the unavailable dates",
+                ))
+            })?;
+            let days = (parse_constraint_date(&constraint.date)? - start_date).num_days();
+
+            unavailable_rounds.push(if days >= 0 && days % days_between_rounds == 0 {
+                Some((days / days_between_rounds + 1) as u16)
+            } else {
+                None
+            });
+        }
+
+        let mut random = SeededRandom::new(seed);
+        shuffle_teams(&mut teams, &mut random);
+        let (fixtures, unmet_constraints) =
+            search_constrained_table(teams, &constraints, &unavailable_rounds, &mut random);
+        let created_fixtures: Vec<FixtureObject> = fixtures.into_iter().flatten().collect();
+
+        Fixture::insert_fixtures(
+            user_id,
+            league_id,
+            &created_fixtures,
+            Some(seed),
+            &mut transaction,
+        )
+        .await?;
+
+        // Dating the rounds
+        if let Some(start_date) = start_date {
+            for round in 1..=rounds_quantity {
+                let playing_date = (start_date
+                    + Duration::days((round as i64 - 1) * days_between_rounds))
+                .and_hms_opt(0, 0, 0)
+                .unwrap();
+
+                sqlx::query!(
                     "
-                CALL generate_fixture(?, ?, ?, ?, ?)
+                UPDATE `fixtures` SET playing_date = (?) WHERE league_id = (?) AND round = (?)
                 ",
-                    fixture.home_team_id,
-                    fixture.away_team_id,
+                    playing_date,
                     league_id,
-                    fixture.round,
-                    user_id
+                    round
                 )
                 .execute(&mut *transaction)
-                .await.or_else(|exception| {
-                    if let Some(database_error) = exception.as_database_error() {
-                        if let Some(code) = database_error.code() {
-                            if code == MYSQL_CUSTOM_ERROR {
-                                let message=database_error.message();
-                                return Err(HTTPException::BadRequest(message.to_string()));
-                            }
-                        }
-                    }
-
-                    Err(HTTPException::Internal(String::from("Failed while creating a new fixture. The operation have been cancelled and will not be saved")))
+                .await
+                .or_else(|_error| {
+                    Err(HTTPException::Internal(String::from(
+                        "Failed while dating the fixtures. The operation have been cancelled and will not be saved",
+                    )))
                 })?;
             }
         }
 
         transaction.commit().await.or_else(|_error| {
             Err(HTTPException::Internal(String::from(
-                "Something failed while saving all fixtures. Please try again later",
+                "Something failed while saving all fixtures. Please try again later",
+            )))
+        })?;
+
+        Ok(ScheduledFixturesDTO {
+            created_fixtures,
+            seed,
+            unmet_constraints,
+        })
+    }
+
+    /// Responsible to draw the league fixtures without saving them, so they can be edited before being submitted
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `seed` - Optional draw seed, a new one is generated when it's `None`
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(FixturePreviewDTO)` - The fixtures rounds and the seed of the draw
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's not enough teams, the user doesn't have permission to generate the fixtures or the league is completed
+    /// * * The league already has fixtures
+    /// * * The seed is bigger than `MAX_FIXTURE_SEED`
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn preview_league_fixtures(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        seed: Option<u64>,
+        db_pool: &Pool<MySql>,
+    ) -> Result<FixturePreviewDTO, HTTPException> {
+        let seed = seed.unwrap_or_else(generate_fixture_seed);
+        if seed > MAX_FIXTURE_SEED {
+            return Err(HTTPException::BadRequest(format!(
+                "The seed can't be bigger than {}",
+                MAX_FIXTURE_SEED
+            )));
+        }
+
+        // Nothing is saved, the transaction only locks the league while its teams are read
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        let mut teams = Fixture::get_teams_to_schedule(
+            user_id,
+            tournament_id,
+            league_id,
+            &mut transaction,
+            db_pool,
+        )
+        .await?;
+        shuffle_teams(&mut teams, &mut SeededRandom::new(seed));
+
+        Ok(FixturePreviewDTO {
+            fixtures: berger_table(&mut teams),
+            seed,
+        })
+    }
+
+    /// Responsible to validate and save a schedule edited by the organiser, e.g. a preview with swapped rounds or flipped home and away teams.
+    /// Every pair of teams must play exactly once and a team can't play twice on the same round
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `schedule` - `SubmittedScheduleDTO` struct with the fixtures of each round
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(Vec<FixtureObject>)` - The saved fixtures
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's not enough teams, the user doesn't have permission to generate the fixtures or the league is completed
+    /// * * The league already has fixtures
+    /// * * The schedule has an empty round, a team which is not on the league, a team playing twice on a round or a pair of teams which doesn't play exactly once
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn submit_league_fixtures(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        schedule: SubmittedScheduleDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<Vec<FixtureObject>, HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        let teams = Fixture::get_teams_to_schedule(
+            user_id,
+            tournament_id,
+            league_id,
+            &mut transaction,
+            db_pool,
+        )
+        .await?;
+        let team_ids: HashSet<u64> = teams.iter().map(|team| team.id).collect();
+
+        let mut fixtures = vec![];
+        let mut pairings = HashSet::new();
+        for (index, game_week) in schedule.fixtures.iter().enumerate() {
+            let round = (index + 1) as u16;
+            if game_week.is_empty() {
+                return Err(HTTPException::BadRequest(format!(
+                    "The round {} does not have any fixtures",
+                    round
+                )));
+            }
+
+            let mut round_teams = HashSet::new();
+            for fixture in game_week {
+                if !team_ids.contains(&fixture.home_team_id)
+                    || !team_ids.contains(&fixture.away_team_id)
+                {
+                    return Err(HTTPException::BadRequest(format!(
+                        "Every team on the round {} must be on the league",
+                        round
+                    )));
+                }
+
+                if fixture.home_team_id == fixture.away_team_id
+                    || !round_teams.insert(fixture.home_team_id)
+                    || !round_teams.insert(fixture.away_team_id)
+                {
+                    return Err(HTTPException::BadRequest(format!(
+                        "A team can't play more than once on the round {}",
+                        round
+                    )));
+                }
+
+                if !pairings.insert(pairing(fixture.home_team_id, fixture.away_team_id)) {
+                    return Err(HTTPException::BadRequest(String::from(
+                        "Every pair of teams must play exactly once",
+                    )));
+                }
+
+                fixtures.push(FixtureObject {
+                    home_team_id: fixture.home_team_id,
+                    away_team_id: fixture.away_team_id,
+                    home_score: 0,
+                    away_score: 0,
+                    played: false,
+                    round,
+                });
+            }
+        }
+
+        if pairings.len() != teams.len() * (teams.len() - 1) / 2 {
+            return Err(HTTPException::BadRequest(String::from(
+                "Every pair of teams must play exactly once",
+            )));
+        }
+
+        // The edited schedule can't be drawn again, so the league seed is cleared
+        Fixture::insert_fixtures(user_id, league_id, &fixtures, None, &mut transaction).await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something failed while saving all fixtures. Please try again later",
+            )))
+        })?;
+
+        Ok(fixtures)
+    }
+
+    /// Responsible to get the teams of a league which is ready to have its fixtures drawn: it's open, the user owns it,
+    /// it has at least two teams and no fixtures yet
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `transaction` - A `&mut Transaction<'_, MySql>` reference, the league stays locked until it ends, so the fixtures can't be created twice
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Team>)` - The league teams ordered by id
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's not enough teams, the user doesn't have permission to generate the fixtures or the league is completed
+    /// * * The league already has fixtures
+    /// * * It's a swiss or double elimination league
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn get_teams_to_schedule(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+        db_pool: &Pool<MySql>,
+    ) -> Result<Vec<Team>, HTTPException> {
+        League::check_league_is_open(user_id, tournament_id, league_id, transaction).await?;
+
+        let teams = get_teams_from_league(league_id, tournament_id, user_id, db_pool).await?;
+        if teams.len() < 2 {
+            return Err(HTTPException::BadRequest(String::from(
+                "Not enough teams or permission to generate fixtures",
+            )));
+        }
+        Fixture::check_round_robin_league(league_id, db_pool).await?;
+
+        let existing_fixtures =
+            League::get_league_fixtures(user_id, tournament_id, league_id, db_pool).await?;
+        if !existing_fixtures.is_empty() {
+            return Err(HTTPException::BadRequest(String::from(
+                "Cannot generate a fixture as it may conflict with existing ones",
+            )));
+        }
+
+        Ok(teams)
+    }
+
+    /// Responsible to check the league fixtures are drawn all at once. The swiss leagues are paired one round at a time
+    /// and the double elimination leagues are played on a bracket instead
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If it's a swiss or double elimination league
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn check_round_robin_league(
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        match League::get_league_format(league_id, db_pool).await? {
+            Some(LeagueFormat::Swiss) => Err(HTTPException::BadRequest(String::from(
+                "The swiss leagues are paired one round at a time",
+            ))),
+            Some(LeagueFormat::DoubleElimination) => Err(HTTPException::BadRequest(String::from(
+                "The double elimination leagues are played on a bracket",
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Responsible to check a single fixture can be put on the round. The double elimination fixtures are created by the bracket,
+    /// and on a swiss league the fixture must be on a round which was already paired, since the next rounds are paired by the standing table
+    /// and its teams which don't play on a paired round are counted as byes
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If it's a double elimination league, or a swiss league round which was not paired yet
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn check_single_fixture_format(
+        league_id: u64,
+        round: u16,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        match League::get_league_format(league_id, db_pool).await? {
+            Some(LeagueFormat::DoubleElimination) => Err(HTTPException::BadRequest(String::from(
+                "The double elimination fixtures are created by the bracket",
+            ))),
+            Some(LeagueFormat::Swiss) => {
+                let latest_round = sqlx::query_scalar::<_, Option<u16>>(
+                    "SELECT MAX(round) FROM `fixtures` WHERE league_id = (?)",
+                )
+                .bind(league_id)
+                .fetch_one(db_pool)
+                .await
+                .or_else(|_error| {
+                    Err(HTTPException::Internal(String::from(
+                        "Failed to get the league rounds. Please try again later",
+                    )))
+                })?
+                .unwrap_or(0);
+
+                if round > latest_round {
+                    return Err(HTTPException::BadRequest(String::from(
+                        "The fixtures of a swiss league must be on a round which was already paired",
+                    )));
+                }
+
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Responsible to pair the next round of a swiss league. The teams are ordered by the standing table, with the Buchholz
+    /// and Sonneborn-Berger tiebreaks, and each one plays the next team on the table which it did not play yet, so the teams on equal points meet.
+    /// When the teams quantity is odd, the worst ranked team with less byes doesn't play the round
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(SwissRoundDTO)` - The round, its fixtures and the team with the bye
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's not enough teams, the user doesn't have permission to generate the fixtures or the league is completed
+    /// * * The league is not a swiss league
+    /// * * The latest round has fixtures which were not played yet
+    /// * * Every round set on `swiss_rounds` was already paired
+    /// * * Every possible pairing has a rematch, or no pairing without a rematch was found after `SWISS_PAIRING_STEPS` pairs
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn generate_swiss_round(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<SwissRoundDTO, HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+
+        let teams = get_teams_from_league(league_id, tournament_id, user_id, db_pool).await?;
+        if teams.len() < 2 {
+            return Err(HTTPException::BadRequest(String::from(
+                "Not enough teams or permission to generate fixtures",
+            )));
+        }
+
+        let league = League::get_league(user_id, tournament_id, league_id, db_pool).await?;
+        if league.format != LeagueFormat::Swiss {
+            return Err(HTTPException::BadRequest(String::from(
+                "Only the swiss leagues are paired one round at a time",
+            )));
+        }
+
+        let mut fixtures =
+            League::get_league_fixtures(user_id, tournament_id, league_id, db_pool).await?;
+        if fixtures.iter().any(|fixture| !fixture.played) {
+            return Err(HTTPException::BadRequest(String::from(
+                "Every fixture must be played before pairing the next round",
+            )));
+        }
+        fixtures.sort_by_key(|fixture| fixture.round);
+        let played_rounds = fixtures.last().map(|fixture| fixture.round).unwrap_or(0);
+        if league
+            .swiss_rounds
+            .is_some_and(|swiss_rounds| played_rounds >= swiss_rounds)
+        {
+            return Err(HTTPException::BadRequest(String::from(
+                "Every round of the swiss league was already paired",
+            )));
+        }
+
+        let mut swiss_teams: HashMap<u64, SwissTeam> = teams
+            .iter()
+            .map(|team| {
+                (
+                    team.id,
+                    SwissTeam {
+                        team_id: team.id,
+                        opponents: HashSet::new(),
+                        home_balance: 0,
+                        last_home: None,
+                        byes: played_rounds,
+                    },
+                )
+            })
+            .collect();
+        for fixture in &fixtures {
+            for (team_id, opponent_id, home) in [
+                (fixture.home_team.id, fixture.away_team.id, true),
+                (fixture.away_team.id, fixture.home_team.id, false),
+            ] {
+                if let Some(swiss_team) = swiss_teams.get_mut(&team_id) {
+                    swiss_team.opponents.insert(opponent_id);
+                    swiss_team.home_balance += if home { 1 } else { -1 };
+                    swiss_team.last_home = Some(home);
+                    // Every round the team didn't play was a bye
+                    swiss_team.byes = swiss_team.byes.saturating_sub(1);
+                }
+            }
+        }
+
+        // The standing table is already ordered by the points and the swiss tiebreaks
+        let standing_table =
+            League::get_league_standing_table(league_id, user_id, tournament_id, None, db_pool)
+                .await?;
+        let ranking: Vec<&SwissTeam> = standing_table
+            .overall
+            .iter()
+            .filter_map(|standing| swiss_teams.get(&standing.team_id))
+            .collect();
+
+        // The bye goes to the team with less byes, starting from the bottom of the table
+        let mut bye_candidates: Vec<Option<&SwissTeam>> = vec![None];
+        if ranking.len() % 2 == 1 {
+            let mut candidates: Vec<&SwissTeam> = ranking.iter().rev().copied().collect();
+            candidates.sort_by_key(|swiss_team| swiss_team.byes);
+            bye_candidates = candidates.into_iter().map(Some).collect();
+        }
+
+        let mut pairing = SwissPairing {
+            unpairable_teams: HashSet::new(),
+            steps_left: SWISS_PAIRING_STEPS,
+        };
+        let paired_round = bye_candidates.into_iter().find_map(|bye_team| {
+            let paired_teams: Vec<&SwissTeam> = ranking
+                .iter()
+                .filter(|swiss_team| {
+                    bye_team.map(|bye_team| bye_team.team_id) != Some(swiss_team.team_id)
+                })
+                .copied()
+                .collect();
+
+            pair_swiss_teams(&paired_teams, &mut pairing).map(|pairs| (pairs, bye_team))
+        });
+        let Some((pairs, bye_team)) = paired_round else {
+            if pairing.steps_left == 0 {
+                return Err(HTTPException::BadRequest(String::from(
+                    "No pairing without a rematch was found for the next round",
+                )));
+            }
+
+            return Err(HTTPException::BadRequest(String::from(
+                "The next round can't be paired without a rematch",
+            )));
+        };
+
+        let round = played_rounds + 1;
+        let created_fixtures: Vec<FixtureObject> = pairs
+            .into_iter()
+            .map(|(first_team, second_team)| {
+                let (home_team, away_team) = if swiss_first_team_home(first_team, second_team) {
+                    (first_team, second_team)
+                } else {
+                    (second_team, first_team)
+                };
+
+                FixtureObject {
+                    home_team_id: home_team.team_id,
+                    away_team_id: away_team.team_id,
+                    home_score: 0,
+                    away_score: 0,
+                    played: false,
+                    round,
+                }
+            })
+            .collect();
+        let bye_team_id = bye_team.map(|bye_team| bye_team.team_id);
+
+        // The swiss rounds depend on the results, so they can't be drawn again from a seed
+        Fixture::insert_fixtures(
+            user_id,
+            league_id,
+            &created_fixtures,
+            None,
+            &mut transaction,
+        )
+        .await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something failed while saving all fixtures. Please try again later",
+            )))
+        })?;
+
+        Ok(SwissRoundDTO {
+            round,
+            created_fixtures,
+            bye_team_id,
+        })
+    }
+
+    /// Responsible to save new fixtures with the `generate_fixture` procedure, and the seed of the draw which created them on the league
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `league_id` - The league id
+    /// * `fixtures` - The fixtures which will be saved
+    /// * `seed` - The draw seed, `None` when the fixtures can't be drawn again from a seed, e.g. they were edited
+    /// * `transaction` - A `&mut Transaction<'_, MySql>` reference, the fixtures are only saved when it's committed
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the procedure refused a fixture, e.g. a team is not on the tournament
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn insert_fixtures(
+        user_id: u64,
+        league_id: u64,
+        fixtures: &[FixtureObject],
+        seed: Option<u64>,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), HTTPException> {
+        for fixture in fixtures {
+            let _a = sqlx::query!(
+                "
+            CALL generate_fixture(?, ?, ?, ?, ?)
+            ",
+                fixture.home_team_id,
+                fixture.away_team_id,
+                league_id,
+                fixture.round,
+                user_id
+            )
+            .execute(&mut **transaction)
+            .await.or_else(|exception| {
+                if let Some(database_error) = exception.as_database_error() {
+                    if let Some(code) = database_error.code() {
+                        if code == MYSQL_CUSTOM_ERROR {
+                            let message=database_error.message();
+                            return Err(HTTPException::BadRequest(message.to_string()));
+                        }
+                    }
+                }
+
+                Err(HTTPException::Internal(String::from("Failed while creating a new fixture. The operation have been cancelled and will not be saved")))
+            })?;
+        }
+
+        sqlx::query!(
+            "
+        UPDATE `leagues` SET fixture_seed = (?) WHERE id = (?)
+        ",
+            seed,
+            league_id
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while saving the fixtures seed. The operation have been cancelled and will not be saved",
             )))
         })?;
 
@@ -236,7 +1660,7 @@ impl Fixture {
         })
     }
 
-    /// Responsible to delete all fixtures from a league
+    /// Responsible to delete all fixtures from a league, and its bracket so it can be generated again
     ///
     /// # Arguments
     /// * `user_id` - The user id
@@ -248,7 +1672,7 @@ impl Fixture {
     /// * `Ok(())` - If the fixtures were deleted successfully
     ///
     /// # Errors
-    /// * `HTTPException::BadRequest` - If the fixtures couldn't be deleted or the user doesn't have permission to delete them
+    /// * `HTTPException::BadRequest` - If the fixtures couldn't be deleted, the user doesn't have permission to delete them or the league is completed
     /// * `HTTPException::Internal` - If the database query fails
     pub async fn delete_all_fixtures_from_league(
         user_id: u64,
@@ -256,6 +1680,15 @@ impl Fixture {
         league_id: u64,
         db_pool: &Pool<MySql>,
     ) -> Result<(), HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+        BracketMatch::delete_bracket(user_id, tournament_id, league_id, &mut transaction).await?;
+
         let query = sqlx::query!(
             "
         DELETE FixtureRow.* FROM `fixtures` as FixtureRow
@@ -269,7 +1702,7 @@ impl Fixture {
             league_id,
             user_id
         )
-        .execute(db_pool)
+        .execute(&mut *transaction)
         .await
         .or_else(|_exception| {
             Err(HTTPException::Internal(String::from(
@@ -283,6 +1716,384 @@ impl Fixture {
             )));
         }
 
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to reset the fixtures",
+            )))
+        })?;
+
+        Ok(())
+    }
+
+    /// Responsible to create a single fixture on the league, e.g. a replayed game or a fixture which the draw can't create
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `fixture_data` - A `NewFixtureDTO` struct with the teams and round of the fixture
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The id of the created fixture
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * The user doesn't have permission to create the fixture or the league is completed
+    /// * * The teams are not on the league, they're the same team or one of them already plays on the round
+    /// * * It's a double elimination league, or a swiss league round which was not paired yet
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn create_fixture(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        fixture_data: NewFixtureDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<u64, HTTPException> {
+        // The procedure and the id query must use the same connection
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+        Fixture::check_single_fixture_format(league_id, fixture_data.round, db_pool).await?;
+        Fixture::check_fixture_pairing(
+            user_id,
+            tournament_id,
+            league_id,
+            None,
+            &fixture_data,
+            db_pool,
+        )
+        .await?;
+
+        sqlx::query!(
+            "
+        CALL generate_fixture(?, ?, ?, ?, ?)
+        ",
+            fixture_data.home_team_id,
+            fixture_data.away_team_id,
+            league_id,
+            fixture_data.round,
+            user_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .or_else(|exception| {
+            if let Some(database_error) = exception.as_database_error() {
+                if let Some(code) = database_error.code() {
+                    if code == MYSQL_CUSTOM_ERROR {
+                        let message = database_error.message();
+                        return Err(HTTPException::BadRequest(message.to_string()));
+                    }
+                }
+            }
+
+            Err(HTTPException::Internal(String::from(
+                "Failed while creating a new fixture. Please try again later",
+            )))
+        })?;
+
+        let fixture_id = sqlx::query!("SELECT LAST_INSERT_ID() as id")
+            .fetch_one(&mut *transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed while creating a new fixture. Please try again later",
+                )))
+            })?
+            .id;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while creating a new fixture. Please try again later",
+            )))
+        })?;
+
+        Ok(fixture_id)
+    }
+
+    /// Responsible to change the teams or the round of a fixture which was not played yet
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `fixture_id` - The fixture id
+    /// * `pairing_data` - A `EditFixturePairingDTO` struct with the new teams or round
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * The fixture does not exist, the user doesn't have permission to edit it or the league is completed
+    /// * * The fixture was played, its result must be reset first
+    /// * * The teams are not on the league, they're the same team or one of them already plays on the round
+    /// * * It's a bracket fixture or a tie leg, or the round of a swiss league was not paired yet
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn edit_fixture_pairing(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        fixture_id: u64,
+        pairing_data: EditFixturePairingDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+        BracketMatch::check_not_bracket_fixture(league_id, fixture_id, db_pool).await?;
+        Tie::check_not_tie_leg(league_id, fixture_id, db_pool).await?;
+
+        let fixture =
+            Fixture::get_league_fixture(user_id, tournament_id, league_id, fixture_id, db_pool)
+                .await?;
+        if fixture.played {
+            return Err(HTTPException::BadRequest(String::from(
+                "The fixture was already played. Set it as not played before changing its teams or round",
+            )));
+        }
+
+        let fixture_data = NewFixtureDTO {
+            home_team_id: pairing_data.home_team_id.unwrap_or(fixture.home_team.id),
+            away_team_id: pairing_data.away_team_id.unwrap_or(fixture.away_team.id),
+            round: pairing_data.round.unwrap_or(fixture.round),
+        };
+        Fixture::check_single_fixture_format(league_id, fixture_data.round, db_pool).await?;
+        Fixture::check_fixture_pairing(
+            user_id,
+            tournament_id,
+            league_id,
+            Some(fixture_id),
+            &fixture_data,
+            db_pool,
+        )
+        .await?;
+
+        let query = sqlx::query!(
+            "
+        UPDATE `fixtures` as FixtureRow
+        INNER JOIN `leagues` as LeagueRow
+            ON LeagueRow.id = FixtureRow.league_id
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        SET FixtureRow.home_team_id = (?), FixtureRow.away_team_id = (?), FixtureRow.round = (?)
+        WHERE FixtureRow.id = (?) AND LeagueRow.id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?)
+        ",
+            fixture_data.home_team_id,
+            fixture_data.away_team_id,
+            fixture_data.round,
+            fixture_id,
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to edit the fixture. Please try again later",
+            )))
+        })?;
+
+        if query.rows_affected() < 1 {
+            return Err(HTTPException::BadRequest(String::from(
+                "Failed to edit the fixture. Please make sure the tournament/league exists and you have permission to access it",
+            )));
+        }
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to edit the fixture. Please try again later",
+            )))
+        })?;
+
+        Ok(())
+    }
+
+    /// Responsible to delete a single fixture from the league, deleting a leg of a tie deletes both legs and the tie
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `fixture_id` - The fixture id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - If the fixture was deleted successfully. It's `true` when it was the last pending fixture and the league was completed
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * The fixture does not exist, the user doesn't have permission to delete it or the league is completed
+    /// * * It's a bracket fixture
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn delete_fixture_by_id(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        fixture_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<bool, HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+        BracketMatch::check_not_bracket_fixture(league_id, fixture_id, db_pool).await?;
+
+        // A tie is not played without one of its legs, so both legs are deleted and the tie with them
+        let other_leg_id = sqlx::query!(
+            "
+        SELECT TieRow.first_leg_id, TieRow.second_leg_id
+        FROM `ties` as TieRow
+        WHERE TieRow.league_id = (?) AND (TieRow.first_leg_id = (?) OR TieRow.second_leg_id = (?))
+        ",
+            league_id,
+            fixture_id,
+            fixture_id
+        )
+        .fetch_optional(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league ties. Please try again later",
+            )))
+        })?
+        .map_or(fixture_id, |tie| {
+            if tie.first_leg_id == fixture_id {
+                tie.second_leg_id
+            } else {
+                tie.first_leg_id
+            }
+        });
+
+        let query = sqlx::query!(
+            "
+        DELETE FixtureRow.* FROM `fixtures` as FixtureRow
+        INNER JOIN `leagues` as LeagueRow
+            ON LeagueRow.id = FixtureRow.league_id
+        INNER JOIN `tournaments` as TournamentRow
+            ON LeagueRow.tournament_id = TournamentRow.id
+        WHERE FixtureRow.id IN (?, ?) AND LeagueRow.id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?)
+        ",
+            fixture_id,
+            other_leg_id,
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to delete the fixture. Please try again later",
+            )))
+        })?;
+
+        if query.rows_affected() < 1 {
+            return Err(HTTPException::BadRequest(String::from(
+                "Failed to delete the fixture. Please make sure it exists and you have permission to access it",
+            )));
+        }
+
+        let completed = League::complete_league_if_finished(
+            user_id,
+            tournament_id,
+            league_id,
+            &mut transaction,
+        )
+        .await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to delete the fixture. Please try again later",
+            )))
+        })?;
+
+        Ok(completed)
+    }
+
+    /// Responsible to check the teams of a fixture can play it on the round.
+    /// They must be two different teams of the league, not only of the tournament, and none of them can have another fixture on the round
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `fixture_id` - The id of the fixture being edited, which is not counted as another fixture on the round
+    /// * `fixture_data` - A `&NewFixtureDTO` reference with the teams and round
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the fixture can't be played, or the user doesn't have permission to access the league
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn check_fixture_pairing(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        fixture_id: Option<u64>,
+        fixture_data: &NewFixtureDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        if fixture_data.round < 1 {
+            return Err(HTTPException::BadRequest(String::from(
+                "The round must be bigger than 0",
+            )));
+        }
+
+        if fixture_data.home_team_id == fixture_data.away_team_id {
+            return Err(HTTPException::BadRequest(String::from(
+                "A team can't play against itself",
+            )));
+        }
+
+        let teams = get_teams_from_league(league_id, tournament_id, user_id, db_pool).await?;
+        let on_league = |team_id: u64| teams.iter().any(|team| team.id == team_id);
+        if !on_league(fixture_data.home_team_id) || !on_league(fixture_data.away_team_id) {
+            return Err(HTTPException::BadRequest(String::from(
+                "Both teams must be on the league. Please make sure the tournament/league exists and you have permission to access it",
+            )));
+        }
+
+        let round_fixtures = sqlx::query!(
+            "
+        SELECT COUNT(FixtureRow.id) as fixtures_quantity
+        FROM `fixtures` as FixtureRow
+        WHERE FixtureRow.league_id = (?) AND FixtureRow.round = (?) AND FixtureRow.id != (?) AND (
+            FixtureRow.home_team_id IN (?, ?) OR FixtureRow.away_team_id IN (?, ?)
+        )
+        ",
+            league_id,
+            fixture_data.round,
+            fixture_id.unwrap_or(0),
+            fixture_data.home_team_id,
+            fixture_data.away_team_id,
+            fixture_data.home_team_id,
+            fixture_data.away_team_id
+        )
+        .fetch_one(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the round fixtures. Please try again later",
+            )))
+        })?;
+
+        if round_fixtures.fixtures_quantity > 0 {
+            return Err(HTTPException::BadRequest(format!(
+                "One of the teams already plays on the round {}",
+                fixture_data.round
+            )));
+        }
+
         Ok(())
     }
 
@@ -297,10 +2108,12 @@ impl Fixture {
     /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
     /// 
     /// # Returns
-    /// * `Ok(())` - If the fixture was edited successfully
+    /// * `Ok(bool)` - If the fixture was edited successfully. It's `true` when it was the last fixture to be played and the league was completed
     /// 
     /// # Errors
-    /// * `HTTPException::BadRequest` - If the fixture couldn't be edited or the user doesn't have permission to edit it
+    /// * `HTTPException::BadRequest`
+    /// * * The fixture couldn't be edited, the user doesn't have permission to edit it or the league is completed
+    /// * * It's a bracket fixture ending on a draw, or its winner changes after the next bracket matches are played
     /// * `HTTPException::Internal` - If the database query fails
     pub async fn edit_fixture_by_id(
         user_id: u64,
@@ -309,7 +2122,16 @@ impl Fixture {
         fixture_id: u64,
         edit_data: EditFixtureDTO,
         db_pool: &Pool<MySql>,
-    ) -> Result<(), HTTPException> {
+    ) -> Result<bool, HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+        BracketMatch::check_bracket_result(league_id, fixture_id, &edit_data, db_pool).await?;
+
         let query= sqlx::query!("
         UPDATE `fixtures` as FixtureRow
 
@@ -321,7 +2143,7 @@ impl Fixture {
         SET FixtureRow.home_score = (?), FixtureRow.away_score = (?), FixtureRow.played = (?)
         WHERE FixtureRow.id = (?) AND LeagueRow.id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?)
         ", edit_data.home_score, edit_data.away_score, edit_data.played, fixture_id, league_id, tournament_id, user_id)
-        .execute(db_pool)
+        .execute(&mut *transaction)
         .await
         .or_else(|_exception| {
             Err(HTTPException::Internal(String::from(
@@ -335,6 +2157,22 @@ impl Fixture {
             )));
         }
 
-        Ok(())
+        // The winner and the loser of a bracket fixture move to their next matches
+        BracketMatch::advance_bracket_matches(user_id, league_id, &mut transaction).await?;
+        let completed = League::complete_league_if_finished(
+            user_id,
+            tournament_id,
+            league_id,
+            &mut transaction,
+        )
+        .await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to edit the fixture. Please try again later",
+            )))
+        })?;
+
+        Ok(completed)
     }
 }
@@ -0,0 +1,676 @@
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
+
+use sqlx::{MySql, Pool, Transaction};
+
+use crate::{
+    constants::{CSV_FORMULA_PREFIXES, MYSQL_CUSTOM_ERROR},
+    models::{
+        import::{
+            ImportFile, ImportRowErrorDTO, ImportedFixtureDTO, ImportedTeamDTO, LeagueImportDTO,
+            LeagueImportReportDTO,
+        },
+        league::{League, LeagueFormat},
+    },
+    responses::HTTPException,
+};
+
+/// A team of the league once the import is saved, the created teams are only known by their name until then
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum ImportTeam {
+    Existing(u64),
+    New(String),
+}
+
+/// The position of the fixtures file columns on its header
+struct FixtureColumns {
+    home_team: usize,
+    away_team: usize,
+    round: usize,
+    home_score: Option<usize>,
+    away_score: Option<usize>,
+}
+
+/// The checked rows of an import, the fixture teams are in the same order as the report fixtures
+struct LeagueImportPlan {
+    report: LeagueImportReportDTO,
+    fixture_teams: Vec<(ImportTeam, ImportTeam)>,
+}
+
+/// Responsible to split a CSV file into its records. A field can be quoted to have commas, line breaks or quotes, which are written as `""`
+///
+/// # Arguments
+/// * `content` - The CSV file
+///
+/// # Returns
+/// * `Vec<(usize, Vec<String>)>` - The records which are not empty with their row number, starting from 1
+fn parse_csv(content: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut row = 1;
+
+    let mut characters = content.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '"' if quoted && characters.peek() == Some(&'"') => {
+                field.push('"');
+                characters.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(mem::take(&mut field));
+                records.push((row, mem::take(&mut record)));
+                row += 1;
+            }
+            _ => field.push(character),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((row, record));
+    }
+
+    records
+        .into_iter()
+        .filter(|(_, record)| record.iter().any(|field| !field.trim().is_empty()))
+        .collect()
+}
+
+/// Responsible to find a column on a CSV header, ignoring the case and the spaces around it
+fn header_column(header: &[String], name: &str) -> Option<usize> {
+    header
+        .iter()
+        .position(|column| column.trim().eq_ignore_ascii_case(name))
+}
+
+/// Responsible to get a trimmed field of a CSV record, a missing field is empty.
+/// The leading `'` which the exports put before a formula character is removed, so an exported file is imported as it was
+fn record_field(record: &[String], column: Option<usize>) -> &str {
+    let field = column
+        .and_then(|column| record.get(column))
+        .map_or("", |field| field.trim());
+
+    match field.strip_prefix('\'') {
+        Some(unescaped) if unescaped.starts_with(CSV_FORMULA_PREFIXES) => unescaped,
+        _ => field,
+    }
+}
+
+/// Responsible to read a row of the fixtures file
+///
+/// # Arguments
+/// * `row` - The row number
+/// * `record` - The row fields
+/// * `columns` - The position of the columns on the header
+/// * `league_teams` - The names of the league teams once the import is saved
+///
+/// # Returns
+/// * `Ok((ImportTeam, ImportTeam, ImportedFixtureDTO))` - The home team, the away team and the fixture
+///
+/// # Errors
+/// * `String` - The reason why the row is invalid
+fn parse_fixture_row(
+    row: usize,
+    record: &[String],
+    columns: &FixtureColumns,
+    league_teams: &[(String, ImportTeam)],
+) -> Result<(ImportTeam, ImportTeam, ImportedFixtureDTO), String> {
+    let find_team = |name: &str| {
+        let found: Vec<&ImportTeam> = league_teams
+            .iter()
+            .filter(|(team_name, _)| team_name == name)
+            .map(|(_, team)| team)
+            .collect();
+
+        match found.as_slice() {
+            [] => Err(format!("The team {} is not on the league", name)),
+            [team] => Ok((*team).clone()),
+            _ => Err(format!(
+                "More than one team of the league is named {}",
+                name
+            )),
+        }
+    };
+
+    let home_team_name = record_field(record, Some(columns.home_team));
+    let away_team_name = record_field(record, Some(columns.away_team));
+    if home_team_name.is_empty() || away_team_name.is_empty() {
+        return Err(String::from("Both teams are required"));
+    }
+
+    let home_team = find_team(home_team_name)?;
+    let away_team = find_team(away_team_name)?;
+    if home_team == away_team {
+        return Err(String::from("A team can't play against itself"));
+    }
+
+    let round = record_field(record, Some(columns.round))
+        .parse::<u16>()
+        .ok()
+        .filter(|round| *round > 0)
+        .ok_or_else(|| String::from("The round must be a number bigger than 0"))?;
+
+    let (home_score, away_score, played) = match (
+        record_field(record, columns.home_score),
+        record_field(record, columns.away_score),
+    ) {
+        ("", "") => (0, 0, false),
+        ("", _) | (_, "") => {
+            return Err(String::from(
+                "Both scores are required to set the result of the fixture",
+            ));
+        }
+        (home_score, away_score) => match (home_score.parse::<u8>(), away_score.parse::<u8>()) {
+            (Ok(home_score), Ok(away_score)) => (home_score, away_score, true),
+            _ => return Err(String::from("The scores must be numbers from 0 to 255")),
+        },
+    };
+
+    Ok((
+        home_team,
+        away_team,
+        ImportedFixtureDTO {
+            row,
+            home_team_name: home_team_name.to_string(),
+            away_team_name: away_team_name.to_string(),
+            round,
+            home_score,
+            away_score,
+            played,
+        },
+    ))
+}
+
+impl League {
+    /// Responsible to check the rows of a league import without saving them
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `import_data` - A `&LeagueImportDTO` reference with the CSV files
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(LeagueImportReportDTO)` - The rows which would be imported and the errors of the invalid rows
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's no file to import
+    /// * * The league does not exist, the user doesn't have permission to import on it or the league is completed
+    /// * * The fixtures are imported on a double elimination league
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn preview_league_import(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        import_data: &LeagueImportDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<LeagueImportReportDTO, HTTPException> {
+        // Nothing is saved, the transaction only locks the league while the rows are checked
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        let plan = League::plan_league_import(
+            user_id,
+            tournament_id,
+            league_id,
+            import_data,
+            &mut transaction,
+            db_pool,
+        )
+        .await?;
+
+        Ok(plan.report)
+    }
+
+    /// Responsible to import teams and fixtures on a league from CSV files. Nothing is saved when a row is invalid,
+    /// otherwise the teams are created and added to the league and the fixtures are created with their results on a single transaction
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `import_data` - A `LeagueImportDTO` struct with the CSV files
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(LeagueImportReportDTO)` - The imported rows, with the id of the created teams
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * There's no file to import
+    /// * * The league does not exist, the user doesn't have permission to import on it or the league is completed
+    /// * * The fixtures are imported on a double elimination league
+    /// * `HTTPException::BadRequestWithData` - If a row is invalid, with the `LeagueImportReportDTO` which lists the invalid rows
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn import_league(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        import_data: LeagueImportDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<LeagueImportReportDTO, HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        let LeagueImportPlan {
+            mut report,
+            fixture_teams,
+        } = League::plan_league_import(
+            user_id,
+            tournament_id,
+            league_id,
+            &import_data,
+            &mut transaction,
+            db_pool,
+        )
+        .await?;
+
+        if !report.errors.is_empty() {
+            return Err(HTTPException::BadRequestWithData(
+                String::from("The import has invalid rows, nothing was saved"),
+                serde_json::to_value(&report).unwrap(),
+            ));
+        }
+
+        let mut created_team_ids: HashMap<String, u64> = HashMap::new();
+        for team in report.teams.iter_mut() {
+            let team_id = match team.team_id {
+                Some(team_id) => team_id,
+                None => {
+                    let team_id = sqlx::query!(
+                        "
+                    INSERT INTO `teams` (`name`, `tournament_id`)
+                    VALUES (?, ?)
+                    ",
+                        team.name,
+                        tournament_id
+                    )
+                    .execute(&mut *transaction)
+                    .await
+                    .or_else(|_error| {
+                        Err(HTTPException::Internal(String::from(
+                            "Failed while importing the teams. Nothing was saved, please try again later",
+                        )))
+                    })?
+                    .last_insert_id();
+
+                    created_team_ids.insert(team.name.clone(), team_id);
+                    team_id
+                }
+            };
+            team.team_id = Some(team_id);
+
+            if team.added_to_league {
+                sqlx::query!(
+                    "
+                INSERT INTO `teams_leagues` (`team_id`, `league_id`)
+                VALUES (?, ?)
+                ",
+                    team_id,
+                    league_id
+                )
+                .execute(&mut *transaction)
+                .await
+                .or_else(|_error| {
+                    Err(HTTPException::Internal(String::from(
+                        "Failed while importing the teams. Nothing was saved, please try again later",
+                    )))
+                })?;
+            }
+        }
+
+        let team_id = |team: &ImportTeam| match team {
+            ImportTeam::Existing(team_id) => *team_id,
+            ImportTeam::New(name) => created_team_ids.get(name).copied().unwrap_or(0),
+        };
+        for (fixture, (home_team, away_team)) in report.fixtures.iter().zip(&fixture_teams) {
+            sqlx::query!(
+                "
+            CALL generate_fixture(?, ?, ?, ?, ?)
+            ",
+                team_id(home_team),
+                team_id(away_team),
+                league_id,
+                fixture.round,
+                user_id
+            )
+            .execute(&mut *transaction)
+            .await
+            .or_else(|exception| {
+                if let Some(database_error) = exception.as_database_error() {
+                    if let Some(code) = database_error.code() {
+                        if code == MYSQL_CUSTOM_ERROR {
+                            let message = database_error.message();
+                            return Err(HTTPException::BadRequest(format!(
+                                "fixtures row {}: {}",
+                                fixture.row, message
+                            )));
+                        }
+                    }
+                }
+
+                Err(HTTPException::Internal(String::from(
+                    "Failed while importing the fixtures. Nothing was saved, please try again later",
+                )))
+            })?;
+
+            if fixture.played {
+                sqlx::query!(
+                    "
+                UPDATE `fixtures` SET home_score = (?), away_score = (?), played = TRUE
+                WHERE id = LAST_INSERT_ID()
+                ",
+                    fixture.home_score,
+                    fixture.away_score
+                )
+                .execute(&mut *transaction)
+                .await
+                .or_else(|_error| {
+                    Err(HTTPException::Internal(String::from(
+                        "Failed while importing the fixtures. Nothing was saved, please try again later",
+                    )))
+                })?;
+            }
+        }
+
+        if !report.fixtures.is_empty() {
+            report.league_completed = League::complete_league_if_finished(
+                user_id,
+                tournament_id,
+                league_id,
+                &mut transaction,
+            )
+            .await?;
+        }
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while saving the import. Nothing was saved, please try again later",
+            )))
+        })?;
+
+        Ok(report)
+    }
+
+    /// Responsible to check the rows of a league import.
+    /// A team row reuses the tournament team with the same name, and a fixture row refers to the league teams, including the imported ones, by their names
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If there's no file, the user can't import on the league or the fixtures are imported on a double elimination league
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn plan_league_import(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        import_data: &LeagueImportDTO,
+        transaction: &mut Transaction<'_, MySql>,
+        db_pool: &Pool<MySql>,
+    ) -> Result<LeagueImportPlan, HTTPException> {
+        if import_data.teams.is_none() && import_data.fixtures.is_none() {
+            return Err(HTTPException::BadRequest(String::from(
+                "The import needs a teams or a fixtures file",
+            )));
+        }
+
+        League::check_league_is_open(user_id, tournament_id, league_id, transaction).await?;
+
+        let league = sqlx::query!(
+            "
+        SELECT LeagueRow.format
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON LeagueRow.tournament_id = TournamentRow.id
+        WHERE TournamentRow.id = (?) AND TournamentRow.user_id = (?) AND LeagueRow.id = (?)
+        ",
+            tournament_id,
+            user_id,
+            league_id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league. Please try again later",
+            )))
+        })?;
+
+        let Some(league) = league else {
+            return Err(HTTPException::BadRequest(String::from(
+                "Failed to import on the league. Please make sure the tournament/league exists and you have permission to access it",
+            )));
+        };
+
+        if import_data.fixtures.is_some()
+            && LeagueFormat::from_sql(&league.format) == LeagueFormat::DoubleElimination
+        {
+            return Err(HTTPException::BadRequest(String::from(
+                "The fixtures of a double elimination league are created by its bracket",
+            )));
+        }
+
+        let tournament_teams = sqlx::query!(
+            "
+        SELECT TeamRow.id, TeamRow.name
+        FROM `teams` as TeamRow
+        WHERE TeamRow.tournament_id = (?)
+        ",
+            tournament_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the tournament teams. Please try again later",
+            )))
+        })?;
+
+        let league_team_ids: HashSet<u64> = sqlx::query!(
+            "
+        SELECT TeamLeagueRow.team_id
+        FROM `teams_leagues` as TeamLeagueRow
+        WHERE TeamLeagueRow.league_id = (?)
+        ",
+            league_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league teams. Please try again later",
+            )))
+        })?
+        .into_iter()
+        .map(|team| team.team_id)
+        .collect();
+
+        let mut report = LeagueImportReportDTO::default();
+        let mut fixture_teams = vec![];
+        let mut league_teams: Vec<(String, ImportTeam)> = tournament_teams
+            .iter()
+            .filter(|team| league_team_ids.contains(&team.id))
+            .map(|team| (team.name.clone(), ImportTeam::Existing(team.id)))
+            .collect();
+
+        if let Some(teams_file) = &import_data.teams {
+            let mut records = parse_csv(teams_file).into_iter();
+            let name_column = records
+                .next()
+                .and_then(|(_, header)| header_column(&header, "name"));
+
+            match name_column {
+                None => report.errors.push(ImportRowErrorDTO {
+                    file: ImportFile::Teams,
+                    row: 1,
+                    message: String::from("The header must have the name column"),
+                }),
+                Some(name_column) => {
+                    let mut file_rows: HashMap<String, usize> = HashMap::new();
+                    for (row, record) in records {
+                        let name = record_field(&record, Some(name_column)).to_string();
+                        let same_name: Vec<u64> = tournament_teams
+                            .iter()
+                            .filter(|team| team.name == name)
+                            .map(|team| team.id)
+                            .collect();
+                        let existing_team_id = same_name.first().copied();
+                        let added_to_league = existing_team_id
+                            .map_or(true, |team_id| !league_team_ids.contains(&team_id));
+
+                        let error = if name.is_empty() {
+                            Some(String::from("The team name can't be empty"))
+                        } else if name.len() > 40 {
+                            Some(String::from("A team name cannot exceed 40 characters"))
+                        } else if let Some(first_row) = file_rows.get(&name) {
+                            Some(format!(
+                                "The team {} is already on the row {}",
+                                name, first_row
+                            ))
+                        } else if same_name.len() > 1 {
+                            Some(format!(
+                                "More than one team of the tournament is named {}",
+                                name
+                            ))
+                        } else if added_to_league && league_teams.len() >= 24 {
+                            Some(String::from(
+                                "The league is full, it can't have more than 24 teams",
+                            ))
+                        } else {
+                            None
+                        };
+
+                        if let Some(message) = error {
+                            report.errors.push(ImportRowErrorDTO {
+                                file: ImportFile::Teams,
+                                row,
+                                message,
+                            });
+                            continue;
+                        }
+
+                        file_rows.insert(name.clone(), row);
+                        if added_to_league {
+                            league_teams.push((
+                                name.clone(),
+                                existing_team_id
+                                    .map_or(ImportTeam::New(name.clone()), ImportTeam::Existing),
+                            ));
+                        }
+
+                        report.teams.push(ImportedTeamDTO {
+                            row,
+                            name,
+                            team_id: existing_team_id,
+                            created: existing_team_id.is_none(),
+                            added_to_league,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(fixtures_file) = &import_data.fixtures {
+            let mut records = parse_csv(fixtures_file).into_iter();
+            let header = records.next().map(|(_, header)| header).unwrap_or_default();
+
+            let columns = match (
+                header_column(&header, "home_team"),
+                header_column(&header, "away_team"),
+                header_column(&header, "round"),
+            ) {
+                (Some(home_team), Some(away_team), Some(round)) => Some(FixtureColumns {
+                    home_team,
+                    away_team,
+                    round,
+                    home_score: header_column(&header, "home_score"),
+                    away_score: header_column(&header, "away_score"),
+                }),
+                _ => {
+                    report.errors.push(ImportRowErrorDTO {
+                        file: ImportFile::Fixtures,
+                        row: 1,
+                        message: String::from(
+                            "The header must have the home_team, away_team and round columns",
+                        ),
+                    });
+                    None
+                }
+            };
+
+            if let Some(columns) = columns {
+                // The teams which already play on each round, counting the league fixtures
+                let mut busy_teams: HashMap<u16, HashSet<ImportTeam>> = HashMap::new();
+                let league_fixtures = sqlx::query!(
+                    "
+                SELECT FixtureRow.home_team_id, FixtureRow.away_team_id, FixtureRow.round
+                FROM `fixtures` as FixtureRow
+                WHERE FixtureRow.league_id = (?)
+                ",
+                    league_id
+                )
+                .fetch_all(db_pool)
+                .await
+                .or_else(|_error| {
+                    Err(HTTPException::Internal(String::from(
+                        "Failed to get the league fixtures. Please try again later",
+                    )))
+                })?;
+                for fixture in league_fixtures {
+                    let round_teams = busy_teams.entry(fixture.round).or_default();
+                    round_teams.insert(ImportTeam::Existing(fixture.home_team_id));
+                    round_teams.insert(ImportTeam::Existing(fixture.away_team_id));
+                }
+
+                for (row, record) in records {
+                    let parsed = parse_fixture_row(row, &record, &columns, &league_teams).and_then(
+                        |(home_team, away_team, fixture)| {
+                            let round_teams = busy_teams.entry(fixture.round).or_default();
+                            if round_teams.contains(&home_team) || round_teams.contains(&away_team)
+                            {
+                                return Err(format!(
+                                    "One of the teams already plays on the round {}",
+                                    fixture.round
+                                ));
+                            }
+
+                            round_teams.insert(home_team.clone());
+                            round_teams.insert(away_team.clone());
+                            Ok((home_team, away_team, fixture))
+                        },
+                    );
+
+                    match parsed {
+                        Ok((home_team, away_team, fixture)) => {
+                            fixture_teams.push((home_team, away_team));
+                            report.fixtures.push(fixture);
+                        }
+                        Err(message) => report.errors.push(ImportRowErrorDTO {
+                            file: ImportFile::Fixtures,
+                            row,
+                            message,
+                        }),
+                    }
+                }
+            }
+        }
+
+        Ok(LeagueImportPlan {
+            report,
+            fixture_teams,
+        })
+    }
+}
@@ -1,14 +1,68 @@
-use sqlx::{MySql, Pool};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    mem,
+};
+
+use chrono::NaiveDate;
+use sqlx::{Executor, MySql, Pool, QueryBuilder, Transaction};
 
 use crate::{
+    constants::FORFEIT_GOALS,
     models::{
-        fixture::FixtureDataDTO,
-        league::{League, LeagueInformationDTO, LeagueRegisterDTO, TeamStandingTable},
+        bracket::BracketMatch,
+        fixture::{FixtureDataDTO, FixtureListQuery, FixtureSort},
+        league::{
+            ArchiveListQuery, ArchivedFixture, ArchivedLeagueDTO, ArchivedSeasonDTO,
+            ArchivedStanding, League, LeagueFormat, LeagueInformationDTO, LeagueListQuery,
+            LeagueRegisterDTO, LeagueSort, LeagueStandingsTable, LeagueZone, NewSeasonDTO,
+            NewSeasonLeagueDTO, RoundPosition, StandingVenue, TeamPositionProgression,
+            TeamStandingTable, WithdrawalPolicy,
+        },
+        pagination::{contains_pattern, Pagination, PaginationData, SortOrder},
         team::{Team, TeamInfoDTO},
+        tie::Tie,
+        tournament::Tournament,
     },
     responses::HTTPException,
 };
 
+/// How many of the latest results are shown on the standings form
+const STANDING_FORM_SIZE: usize = 5;
+
+/// How many zones a league can highlight on the standing table
+const MAX_LEAGUE_ZONES: usize = 10;
+
+#[derive(sqlx::FromRow)]
+struct FixtureListRow {
+    id: u64,
+    home_team_id: u64,
+    away_team_id: u64,
+    home_score: u8,
+    away_score: u8,
+    played: bool,
+    round: u16,
+    home_team_name: String,
+    away_team_name: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct ArchivedLeagueRow {
+    id: u64,
+    name: String,
+    champion_id: Option<u64>,
+    champion_name: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct LeagueListRow {
+    id: u64,
+    tournament_id: u64,
+    name: String,
+    completed: bool,
+    format: String,
+}
+
 impl League {
     /// Responsible to create a new league for the specified tournament_id
     ///
@@ -27,6 +81,7 @@ impl League {
     /// * * If the tournament does not exist
     /// * * If the user does not own the tournament
     /// * * The name of the league is too long
+    /// * * The swiss rounds are sent on a league which is not swiss, or they're zero
     pub async fn create_league(
         user_id: u64,
         tournament_id: u64,
@@ -39,20 +94,35 @@ impl League {
             )));
         }
 
+        if let Some(zones) = &league_data.zones {
+            validate_league_zones(zones)?;
+        }
+
+        let format = league_data.format.unwrap_or_default();
+        validate_swiss_rounds(format, league_data.swiss_rounds)?;
+
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
         let query = sqlx::query!(
             "
-        INSERT INTO `leagues` (`name`, `completed`, `tournament_id`)
-        SELECT (?), (?), (?)
+        INSERT INTO `leagues` (`name`, `completed`, `tournament_id`, `format`, `swiss_rounds`)
+        SELECT (?), (?), (?), (?), (?)
         FROM `tournaments` as TournamentRow
         WHERE TournamentRow.id = (?) AND TournamentRow.user_id = (?)
         ",
             league_data.name,
             league_data.completed,
             tournament_id,
+            format.as_sql(),
+            league_data.swiss_rounds,
             tournament_id,
             user_id
         )
-        .execute(db_pool)
+        .execute(&mut *transaction)
         .await;
 
         let league_id = match query {
@@ -71,6 +141,16 @@ impl League {
             }
         };
 
+        if let Some(zones) = &league_data.zones {
+            League::replace_league_zones(league_id, zones, &mut transaction).await?;
+        }
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to create a new league. Please try again later",
+            )))
+        })?;
+
         Ok(league_id)
     }
 
@@ -92,6 +172,7 @@ impl League {
     /// * * The tournament or league does not exist
     /// * * The user id does not own the tournament which is related to the league
     /// * * The league name is too long
+    /// * * The swiss rounds are sent on a league which is not swiss, they're zero or less than the rounds already paired
     pub async fn edit_league(
         user_id: u64,
         tournament_id: u64,
@@ -105,21 +186,90 @@ impl League {
             )));
         }
 
+        if let Some(zones) = &edit_data.zones {
+            validate_league_zones(zones)?;
+        }
+
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        // Locking the league row, the changes to the league fixtures check the league is open with the same lock,
+        // so they wait until the league is edited instead of changing a league which is being completed
+        let current_league = sqlx::query!(
+            "
+        SELECT LeagueRow.completed, LeagueRow.format, LeagueRow.swiss_rounds,
+        (SELECT COUNT(*) FROM `fixtures` as FixtureRow WHERE FixtureRow.league_id = LeagueRow.id) as fixtures_quantity,
+        (SELECT MAX(FixtureRow.round) FROM `fixtures` as FixtureRow WHERE FixtureRow.league_id = LeagueRow.id) as last_round
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON LeagueRow.tournament_id = TournamentRow.id
+        WHERE TournamentRow.id = (?) AND TournamentRow.user_id = (?) AND LeagueRow.id = (?)
+        FOR UPDATE
+        ",
+            tournament_id,
+            user_id,
+            league_id
+        )
+        .fetch_optional(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to edit the league. Please try again later",
+            )))
+        })?;
+
+        let current_league = match current_league {
+            None => {
+                return Err(HTTPException::BadRequest(String::from("Failed to edit the league. Make sure the tournament, league exists and that you also own the tournament")));
+            }
+            Some(league) => league,
+        };
+        let was_completed = current_league.completed == 1;
+
+        // The fixtures are paired according to the format, so it can only change before they're created
+        let current_format = LeagueFormat::from_sql(&current_league.format);
+        let format = edit_data.format.unwrap_or(current_format);
+        if format != current_format && current_league.fixtures_quantity > 0 {
+            return Err(HTTPException::BadRequest(String::from(
+                "The league format can't be changed after its fixtures are created",
+            )));
+        }
+
+        // Changing the format drops the swiss rounds, and the rounds which were already paired can't be removed
+        validate_swiss_rounds(format, edit_data.swiss_rounds)?;
+        let swiss_rounds = match format {
+            LeagueFormat::Swiss => edit_data.swiss_rounds.or(current_league.swiss_rounds),
+            _ => None,
+        };
+        if let (Some(swiss_rounds), Some(last_round)) = (swiss_rounds, current_league.last_round) {
+            if swiss_rounds < last_round {
+                return Err(HTTPException::BadRequest(format!(
+                    "The swiss league already paired {} rounds",
+                    last_round
+                )));
+            }
+        }
+
         let query = sqlx::query!(
             "
         UPDATE `leagues` as LeagueRow
         INNER JOIN `tournaments` as TournamentRow
             ON LeagueRow.tournament_id = TournamentRow.id
-        SET LeagueRow.name = (?), LeagueRow.completed = (?)
+        SET LeagueRow.name = (?), LeagueRow.completed = (?), LeagueRow.format = (?), LeagueRow.swiss_rounds = (?)
         WHERE TournamentRow.id = (?) AND TournamentRow.user_id = (?) AND LeagueRow.id = (?)
         ",
             edit_data.name,
             edit_data.completed,
+            format.as_sql(),
+            swiss_rounds,
             tournament_id,
             user_id,
             league_id
         )
-        .execute(db_pool)
+        .execute(&mut *transaction)
         .await;
 
         match query {
@@ -138,239 +288,760 @@ impl League {
             }
         };
 
+        if let Some(zones) = &edit_data.zones {
+            League::replace_league_zones(league_id, zones, &mut transaction).await?;
+        }
+
+        if edit_data.completed && !was_completed {
+            // Completing the league by hand also archives it
+            let standing_table = League::get_final_standing_table(
+                user_id,
+                tournament_id,
+                league_id,
+                format,
+                &mut transaction,
+            )
+            .await?;
+            League::save_league_archive(league_id, &standing_table, &mut transaction).await?;
+        } else if !edit_data.completed && was_completed {
+            // The league is reopened, it's archived again when it's completed
+            League::delete_league_archive(league_id, &mut transaction).await?;
+        }
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to edit the league. Please try again later",
+            )))
+        })?;
+
         Ok(())
     }
 
-    /// Responsible to delete a league
+    /// Responsible to replace the league zones by the given ones.
+    /// It runs on the transaction which created or edited the league, so the league ownership is already checked
     ///
     /// # Arguments
-    /// * `user_id` - `u64` integer which represents the user id
-    /// * `tournament_id` `u64` integer which represents the tournament id
-    /// * `league_id - `u64` integer which represents the league id
-    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
-    ///
-    /// # Return
-    /// `Ok(())` - Indicates that the operation was successful
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `zones` - `&[LeagueZone]` slice with the new zones, an empty slice removes every zone
+    /// * `transaction` - `&mut Transaction<MySql>` reference for the running database transaction
     ///
     /// # Errors
-    ///
     /// * `HTTPException::Internal` - If the database query fails
-    /// * `HTTPException::BadRequest`
-    /// * * The tournament does not exist
-    /// * * The league does not exist
-    /// * * The user who's doing the action does not have permission to execute it
-    pub async fn remove_league(
-        user_id: u64,
-        tournament_id: u64,
+    async fn replace_league_zones(
         league_id: u64,
-        db_pool: &Pool<MySql>,
+        zones: &[LeagueZone],
+        transaction: &mut Transaction<'_, MySql>,
     ) -> Result<(), HTTPException> {
-        let query = sqlx::query!(
-            "
-        DELETE LeagueRow
-        FROM `leagues` as LeagueRow
-        INNER JOIN `tournaments` as TournamentRow
-            ON LeagueRow.tournament_id = TournamentRow.id
-        WHERE LeagueRow.id = (?)
-        AND TournamentRow.user_id = (?)
-        AND TournamentRow.id = (?)
-        ",
-            league_id,
-            user_id,
-            tournament_id
+        sqlx::query!(
+            "DELETE FROM `league_zones` WHERE `league_id` = (?)",
+            league_id
         )
-        .execute(db_pool)
-        .await;
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to save the league zones. Please try again later",
+            )))
+        })?;
 
-        match query {
-            Err(_) => return Err(HTTPException::Internal(String::from(""))),
-            Ok(result) => {
-                if result.rows_affected() < 1 {
-                    return Err(HTTPException::BadRequest(String::from("")));
-                }
+        if zones.is_empty() {
+            return Ok(());
+        }
 
-                result
-            }
-        };
+        let mut query = QueryBuilder::new(
+            "INSERT INTO `league_zones` (`league_id`, `label`, `color`, `start_position`, `end_position`) ",
+        );
+        query.push_values(zones, |mut row, zone| {
+            row.push_bind(league_id)
+                .push_bind(zone.label.clone())
+                .push_bind(zone.color.clone())
+                .push_bind(zone.start_position)
+                .push_bind(zone.end_position);
+        });
+
+        query
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to save the league zones. Please try again later",
+                )))
+            })?;
 
         Ok(())
     }
 
-    /// Function responsible to get the league information
+    /// Responsible to get the league zones, on the same order they were defined
     ///
     /// # Arguments
-    /// * `user_id` - `u64` integer which represents the user id
-    /// * `tournament_id` - `u64` integer which represents the tournament id
     /// * `league_id` - `u64` integer which represents the league id
     /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
     ///
-    /// # Returns
-    /// `Ok(LeagueInformationDTO)` - `LeagueInformationDTO` struct containing the league information
-    ///
     /// # Errors
     /// * `HTTPException::Internal` - If the database query fails
-    /// * `HTTPException::BadRequest`
-    /// * * The tournament does not exist
-    /// * * The league does not exist
-    /// * * The tournament is private and the user does not own the tournament
-    pub async fn get_league(
-        user_id: u64,
-        tournament_id: u64,
+    async fn get_league_zones(
         league_id: u64,
         db_pool: &Pool<MySql>,
-    ) -> Result<LeagueInformationDTO, HTTPException> {
-        let query = sqlx::query!(
+    ) -> Result<Vec<LeagueZone>, HTTPException> {
+        let zones = sqlx::query_as!(
+            LeagueZone,
             "
-        SELECT LeagueRow.id, LeagueRow.tournament_id, LeagueRow.completed, LeagueRow.name
-        FROM `leagues` as LeagueRow
-        INNER JOIN `tournaments` as TournamentRow
-            ON TournamentRow.id = LeagueRow.tournament_id
-        WHERE LeagueRow.id = (?) AND LeagueRow.tournament_id = (?) AND
-        (
-            (TournamentRow.public = false AND TournamentRow.user_id = (?))
-            OR
-            (TournamentRow.public = true)
-        )
+        SELECT ZoneRow.label, ZoneRow.color, ZoneRow.start_position, ZoneRow.end_position
+        FROM `league_zones` as ZoneRow
+        WHERE ZoneRow.league_id = (?)
+        ORDER BY ZoneRow.id ASC
         ",
-            league_id,
-            tournament_id,
-            user_id
+            league_id
         )
-        .fetch_optional(db_pool)
-        .await;
-
-        let league_info = match query {
-            Err(_) => {
-                return Err(HTTPException::Internal(String::from(
-                    "Failed to get the league. Please try again later",
-                )))
-            }
-            Ok(data) => match data {
-                None => {
-                    return Err(HTTPException::BadRequest(String::from("Failed to get the league. Please make sure the league, tournament exists and you have permissions to access the tournament")));
-                }
-
-                Some(value) => value,
-            },
-        };
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league zones. Please try again later",
+            )))
+        })?;
 
-        Ok(LeagueInformationDTO {
-            id: league_info.id,
-            name: league_info.name,
-            tournament_id: league_info.tournament_id,
-            completed: league_info.completed == 1,
-        })
+        Ok(zones)
     }
 
-    /// Function responsible to add a team to the league
+    /// Responsible to check if the league can be changed. A completed league is locked until it's reopened by editing it.
+    /// The league row is locked until the transaction ends, so the league can't be completed, reopened or have its fixtures
+    /// changed by another request between this check and the changes saved on the transaction
     ///
     /// # Arguments
     /// * `user_id` - `u64` integer which represents the user id
     /// * `tournament_id` - `u64` integer which represents the tournament id
     /// * `league_id` - `u64` integer which represents the league id
-    /// * `team_id` - `u64` integer which represents the team id
-    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    /// * `transaction` - `&mut Transaction<MySql>` reference for the transaction which saves the changes
     ///
     /// # Returns
-    /// * `Ok(())` - Indicates that the operation is successful
+    /// * `Ok(())` - The league is not completed. It's also returned when the league is not found, so the operation itself tells it
     ///
     /// # Errors
     /// * `HTTPException::Internal` - If the database query fails
-    /// * `HTTPException::BadRequest`
-    /// * * The league or team does not exists
-    /// * * The user id does not own the tournament
-    /// * * The team is already on the league
-    pub async fn add_team_to_league(
+    /// * `HTTPException::BadRequest` - If the league is completed
+    pub async fn check_league_is_open(
         user_id: u64,
         tournament_id: u64,
         league_id: u64,
-        team_id: u64,
-        db_pool: &Pool<MySql>,
+        transaction: &mut Transaction<'_, MySql>,
     ) -> Result<(), HTTPException> {
-        let teams_quantity =
-            Self::get_teams_quantity_from_league(user_id, league_id, db_pool).await?;
-        if teams_quantity >= 24 {
-            return Err(HTTPException::BadRequest(String::from(
-                "The league is full",
-            )));
-        }
-
-        let query = sqlx::query!(
+        let league = sqlx::query!(
             "
-        INSERT INTO `teams_leagues` (`league_id`, `team_id`)
-        SELECT (?), (?)
-        FROM `tournaments` as TournamentRow
-        INNER JOIN `leagues` as LeagueRow
+        SELECT LeagueRow.completed
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
             ON LeagueRow.tournament_id = TournamentRow.id
-        WHERE LeagueRow.id = (?) AND LeagueRow.tournament_id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?)
+        WHERE TournamentRow.id = (?) AND TournamentRow.user_id = (?) AND LeagueRow.id = (?)
+        FOR UPDATE
         ",
-        league_id, team_id, league_id, tournament_id, tournament_id, user_id
+            tournament_id,
+            user_id,
+            league_id
         )
-        .execute(db_pool)
-        .await;
-
-        match query {
-            Err(error) => {
-                let database_error = match error.as_database_error() {
-                    Some(e) => e,
-                    None => {
-                        return Err(HTTPException::Internal(String::from(
-                            "Something unknown happened. Please try again later",
-                        )));
-                    }
-                };
-
-                // Handles unique key violation
-                // This happens when the user is trying to add a existing team to the league
-                if database_error.is_unique_violation() {
-                    return Err(HTTPException::BadRequest(String::from(
-                        "Operation failed, the team may be already on the league",
-                    )));
-                }
+        .fetch_optional(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league. Please try again later",
+            )))
+        })?;
 
-                return Err(HTTPException::Internal(String::from(
-                    "Something unknown happened. Please try again later",
+        if let Some(league) = league {
+            if league.completed == 1 {
+                return Err(HTTPException::BadRequest(String::from(
+                    "The league is completed. Reopen it by editing the league before changing it",
                 )));
             }
-            Ok(data) => {
-                if data.rows_affected() < 1 {
-                    return Err(HTTPException::BadRequest(String::from("Failed to add the team to the league. Please make sure you own the tournament and the team/league exists")));
-                }
-
-                data
-            }
-        };
+        }
 
         Ok(())
     }
 
-    /// Function responsible to remove a team from the league
+    /// Responsible to complete the league once every fixture is played and every tie is decided, freezing the final standing table which tells the champion.
+    /// A swiss league is completed once its `swiss_rounds` are played, and a double elimination league once its bracket has a champion.
+    /// It runs on the transaction which saved the result, after the league is locked by `check_league_is_open`,
+    /// so the league is completed with the same results which are committed
     ///
     /// # Arguments
     /// * `user_id` - `u64` integer which represents the user id
     /// * `tournament_id` - `u64` integer which represents the tournament id
     /// * `league_id` - `u64` integer which represents the league id
-    /// * `team_id` - `u64` integer which represents the team id
-    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    /// * `transaction` - `&mut Transaction<MySql>` reference for the transaction which saved the result
     ///
     /// # Returns
-    /// * `Ok(())` - Indicates that the operation is successful
+    /// * `Ok(bool)` - `true` if the league is completed once the transaction is committed
     ///
     /// # Errors
     /// * `HTTPException::Internal` - If the database query fails
-    /// * `HTTPException::BadRequest`
-    /// * * The league or team does not exists
-    /// * * The user id does not own the tournament
-    /// * * The team is not on the league
-    pub async fn remove_team_from_league(
+    pub async fn complete_league_if_finished(
         user_id: u64,
         tournament_id: u64,
         league_id: u64,
-        team_id: u64,
-        db_pool: &Pool<MySql>,
-    ) -> Result<(), HTTPException> {
-        let query = sqlx::query!("
-        DELETE TeamsLeaguesRow
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<bool, HTTPException> {
+        let league = sqlx::query!(
+            "
+        SELECT LeagueRow.format, LeagueRow.swiss_rounds,
+        COUNT(FixtureRow.id) as fixtures_quantity,
+        COUNT(CASE WHEN FixtureRow.played = FALSE THEN 1 END) as pending_fixtures,
+        MAX(FixtureRow.round) as last_round
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        LEFT JOIN `fixtures` as FixtureRow
+            ON FixtureRow.league_id = LeagueRow.id
+        WHERE LeagueRow.id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?) AND LeagueRow.completed = FALSE
+        GROUP BY LeagueRow.id
+        ",
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .fetch_optional(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to check if the league is finished. Please try again later",
+            )))
+        })?;
+
+        let Some(league) = league else {
+            return Ok(false);
+        };
+        if league.fixtures_quantity == 0 {
+            return Ok(false);
+        }
+
+        let format = LeagueFormat::from_sql(&league.format);
+        let finished = match format {
+            // The grand final reset is only played when the losers bracket champion wins the grand final
+            LeagueFormat::DoubleElimination => {
+                BracketMatch::get_bracket_champion(league_id, transaction)
+                    .await?
+                    .is_some()
+            }
+            // A swiss league without rounds set is only completed by hand
+            LeagueFormat::Swiss => {
+                league.pending_fixtures == 0
+                    && league
+                        .swiss_rounds
+                        .is_some_and(|swiss_rounds| league.last_round.unwrap_or(0) >= swiss_rounds)
+            }
+            // A tie level after both legs is still pending until its extra time or penalties are set
+            LeagueFormat::RoundRobin => {
+                league.pending_fixtures == 0
+                    && Tie::check_ties_decided(league_id, transaction).await?
+            }
+        };
+        if !finished {
+            return Ok(false);
+        }
+
+        let standing_table = League::get_final_standing_table(
+            user_id,
+            tournament_id,
+            league_id,
+            format,
+            transaction,
+        )
+        .await?;
+
+        sqlx::query!(
+            "UPDATE `leagues` SET `completed` = TRUE WHERE `id` = (?)",
+            league_id
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to complete the league. Please try again later",
+            )))
+        })?;
+
+        League::save_league_archive(league_id, &standing_table, transaction).await?;
+
+        Ok(true)
+    }
+
+    /// Responsible to get the standing table which is archived when the league is completed.
+    /// The points don't tell the winner of a bracket, so the champion of a double elimination league is put first
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `format` - `LeagueFormat` enum, how the league fixtures are paired
+    /// * `transaction` - `&mut Transaction<MySql>` reference for the transaction which completes the league
+    ///
+    /// # Returns
+    /// * `Ok(Vec<TeamStandingTable>)` - The overall standing table, from the champion to the last team
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn get_final_standing_table(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        format: LeagueFormat,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<Vec<TeamStandingTable>, HTTPException> {
+        let mut standing_table = League::get_league_venue_standing_table(
+            league_id,
+            user_id,
+            tournament_id,
+            None,
+            StandingVenue::Overall,
+            &mut **transaction,
+        )
+        .await?;
+
+        match format {
+            LeagueFormat::Swiss => {
+                League::add_swiss_tiebreaks(
+                    league_id,
+                    None,
+                    &mut standing_table,
+                    &mut **transaction,
+                )
+                .await?;
+            }
+            LeagueFormat::DoubleElimination => {
+                let champion_team_id =
+                    BracketMatch::get_bracket_champion(league_id, transaction).await?;
+                if let Some(index) = standing_table
+                    .iter()
+                    .position(|standing| Some(standing.team_id) == champion_team_id)
+                {
+                    let champion = standing_table.remove(index);
+                    standing_table.insert(0, champion);
+                }
+            }
+            LeagueFormat::RoundRobin => {}
+        }
+
+        Ok(standing_table)
+    }
+
+    /// Responsible to archive a completed league, saving its final standing table and fixtures with the team names,
+    /// so the season stays the same if the teams are renamed or deleted later. The archive saved before is replaced
+    ///
+    /// # Arguments
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `standing_table` - `&[TeamStandingTable]` slice with the overall standing table, already ordered
+    /// * `transaction` - `&mut Transaction<MySql>` reference for the running database transaction
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn save_league_archive(
+        league_id: u64,
+        standing_table: &[TeamStandingTable],
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), HTTPException> {
+        League::delete_league_archive(league_id, transaction).await?;
+
+        sqlx::query!(
+            "
+        INSERT INTO `league_final_fixtures` (`league_id`, `fixture_id`, `round`, `home_team_id`, `home_team_name`, `away_team_id`, `away_team_name`, `home_score`, `away_score`, `played`, `playing_date`)
+        SELECT FixtureRow.league_id, FixtureRow.id, FixtureRow.round, FixtureRow.home_team_id, HomeTeamRow.name, FixtureRow.away_team_id, AwayTeamRow.name,
+        FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played, FixtureRow.playing_date
+        FROM `fixtures` as FixtureRow
+        INNER JOIN `teams` as HomeTeamRow
+            ON HomeTeamRow.id = FixtureRow.home_team_id
+        INNER JOIN `teams` as AwayTeamRow
+            ON AwayTeamRow.id = FixtureRow.away_team_id
+        WHERE FixtureRow.league_id = (?)
+        ",
+            league_id
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to save the final fixtures. Please try again later",
+            )))
+        })?;
+
+        if standing_table.is_empty() {
+            return Ok(());
+        }
+
+        let mut query = QueryBuilder::new(
+            "INSERT INTO `league_final_standings` (`league_id`, `position`, `team_id`, `team_name`, `matches_played`, `total_points`, `win`, `draw`, `loss`, `goals_scored`, `goals_against`, `goal_difference`, `form`) ",
+        );
+        query.push_values(
+            standing_table.iter().enumerate(),
+            |mut row, (index, standing)| {
+                row.push_bind(league_id)
+                    .push_bind(index as u16 + 1)
+                    .push_bind(standing.team_id)
+                    .push_bind(standing.team_name.clone())
+                    .push_bind(standing.matches_played)
+                    .push_bind(standing.total_points)
+                    .push_bind(standing.win)
+                    .push_bind(standing.draw)
+                    .push_bind(standing.loss)
+                    .push_bind(standing.goals_scored)
+                    .push_bind(standing.goals_against)
+                    .push_bind(standing.goal_difference)
+                    .push_bind(standing.form.clone());
+            },
+        );
+
+        query
+            .build()
+            .execute(&mut **transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to save the final standings. Please try again later",
+                )))
+            })?;
+
+        Ok(())
+    }
+
+    /// Responsible to delete the archived standing table and fixtures of a league
+    ///
+    /// # Arguments
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `transaction` - `&mut Transaction<MySql>` reference for the running database transaction
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn delete_league_archive(
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), HTTPException> {
+        sqlx::query!(
+            "DELETE FROM `league_final_standings` WHERE `league_id` = (?)",
+            league_id
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to delete the final standings. Please try again later",
+            )))
+        })?;
+
+        sqlx::query!(
+            "DELETE FROM `league_final_fixtures` WHERE `league_id` = (?)",
+            league_id
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to delete the final fixtures. Please try again later",
+            )))
+        })?;
+
+        Ok(())
+    }
+
+    /// Responsible to delete a league
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` `u64` integer which represents the tournament id
+    /// * `league_id - `u64` integer which represents the league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Return
+    /// `Ok(())` - Indicates that the operation was successful
+    ///
+    /// # Errors
+    ///
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest`
+    /// * * The tournament does not exist
+    /// * * The league does not exist
+    /// * * The user who's doing the action does not have permission to execute it
+    pub async fn remove_league(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let query = sqlx::query!(
+            "
+        DELETE LeagueRow
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON LeagueRow.tournament_id = TournamentRow.id
+        WHERE LeagueRow.id = (?)
+        AND TournamentRow.user_id = (?)
+        AND TournamentRow.id = (?)
+        ",
+            league_id,
+            user_id,
+            tournament_id
+        )
+        .execute(db_pool)
+        .await;
+
+        match query {
+            Err(_) => return Err(HTTPException::Internal(String::from(""))),
+            Ok(result) => {
+                if result.rows_affected() < 1 {
+                    return Err(HTTPException::BadRequest(String::from("")));
+                }
+
+                result
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Function responsible to get the league information
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// `Ok(LeagueInformationDTO)` - `LeagueInformationDTO` struct containing the league information
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest`
+    /// * * The tournament does not exist
+    /// * * The league does not exist
+    /// * * The tournament is private and the user does not own the tournament
+    pub async fn get_league(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<LeagueInformationDTO, HTTPException> {
+        let query = sqlx::query!(
+            "
+        SELECT LeagueRow.id, LeagueRow.tournament_id, LeagueRow.completed, LeagueRow.name, LeagueRow.fixture_seed, LeagueRow.format,
+        LeagueRow.swiss_rounds
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        WHERE LeagueRow.id = (?) AND LeagueRow.tournament_id = (?) AND
+        (
+            (TournamentRow.public = false AND TournamentRow.user_id = (?))
+            OR
+            (TournamentRow.public = true)
+        )
+        ",
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .fetch_optional(db_pool)
+        .await;
+
+        let league_info = match query {
+            Err(_) => {
+                return Err(HTTPException::Internal(String::from(
+                    "Failed to get the league. Please try again later",
+                )))
+            }
+            Ok(data) => match data {
+                None => {
+                    return Err(HTTPException::BadRequest(String::from("Failed to get the league. Please make sure the league, tournament exists and you have permissions to access the tournament")));
+                }
+
+                Some(value) => value,
+            },
+        };
+
+        let zones = League::get_league_zones(league_info.id, db_pool).await?;
+
+        let champion = sqlx::query!(
+            "
+        SELECT StandingRow.team_id, StandingRow.team_name
+        FROM `league_final_standings` as StandingRow
+        WHERE StandingRow.league_id = (?) AND StandingRow.position = 1
+        ",
+            league_info.id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league. Please try again later",
+            )))
+        })?
+        .map(|champion| TeamInfoDTO {
+            id: champion.team_id,
+            name: champion.team_name,
+        });
+
+        Ok(LeagueInformationDTO {
+            id: league_info.id,
+            name: league_info.name,
+            tournament_id: league_info.tournament_id,
+            completed: league_info.completed == 1,
+            zones: Some(zones),
+            champion,
+            fixture_seed: league_info.fixture_seed,
+            format: LeagueFormat::from_sql(&league_info.format),
+            swiss_rounds: league_info.swiss_rounds,
+        })
+    }
+
+    /// Function responsible to add a team to the league
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `team_id` - `u64` integer which represents the team id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(())` - Indicates that the operation is successful
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest`
+    /// * * The league or team does not exists
+    /// * * The user id does not own the tournament
+    /// * * The team is already on the league
+    /// * * The league is completed
+    pub async fn add_team_to_league(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        team_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+
+        let teams_quantity =
+            Self::get_teams_quantity_from_league(user_id, league_id, db_pool).await?;
+        if teams_quantity >= 24 {
+            return Err(HTTPException::BadRequest(String::from(
+                "The league is full",
+            )));
+        }
+
+        let query = sqlx::query!(
+            "
+        INSERT INTO `teams_leagues` (`league_id`, `team_id`)
+        SELECT (?), (?)
+        FROM `tournaments` as TournamentRow
+        INNER JOIN `leagues` as LeagueRow
+            ON LeagueRow.tournament_id = TournamentRow.id
+        WHERE LeagueRow.id = (?) AND LeagueRow.tournament_id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?)
+        ",
+        league_id, team_id, league_id, tournament_id, tournament_id, user_id
+        )
+        .execute(&mut *transaction)
+        .await;
+
+        match query {
+            Err(error) => {
+                let database_error = match error.as_database_error() {
+                    Some(e) => e,
+                    None => {
+                        return Err(HTTPException::Internal(String::from(
+                            "Something unknown happened. Please try again later",
+                        )));
+                    }
+                };
+
+                // Handles unique key violation
+                // This happens when the user is trying to add a existing team to the league
+                if database_error.is_unique_violation() {
+                    return Err(HTTPException::BadRequest(String::from(
+                        "Operation failed, the team may be already on the league",
+                    )));
+                }
+
+                return Err(HTTPException::Internal(String::from(
+                    "Something unknown happened. Please try again later",
+                )));
+            }
+            Ok(data) => {
+                if data.rows_affected() < 1 {
+                    return Err(HTTPException::BadRequest(String::from("Failed to add the team to the league. Please make sure you own the tournament and the team/league exists")));
+                }
+
+                data
+            }
+        };
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something unknown happened. Please try again later",
+            )))
+        })?;
+
+        Ok(())
+    }
+
+    /// Function responsible to withdraw a team from the league. The `policy` tells what happens to the team fixtures:
+    /// * `Expunge` - Every fixture of the team is deleted, a tie loses both of its legs and the tie itself
+    /// * `Forfeit` - The played fixtures are kept and the pending ones are awarded to the opponents by `FORFEIT_GOALS` to 0.
+    /// A tie which is not decided yet is awarded as a whole, both of its legs are forfeited and its extra time and penalties removed
+    ///
+    /// Either way the standing table only counts the remaining results, and the league is completed if every fixture left is played.
+    /// A double elimination league only takes the `Forfeit` policy, so the results of its bracket are kept. The bracket is advanced with the forfeits
+    /// and the opponents of the team get a walkover on the matches it didn't start
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `team_id` - `u64` integer which represents the team id
+    /// * `policy` - `WithdrawalPolicy` enum, what happens to the team fixtures
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(())` - Indicates that the operation is successful
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest`
+    /// * * The league or team does not exists
+    /// * * The user id does not own the tournament
+    /// * * The team is not on the league
+    /// * * The league is completed
+    /// * * The league is a double elimination league and the policy is `Expunge`
+    pub async fn remove_team_from_league(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        team_id: u64,
+        policy: WithdrawalPolicy,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+
+        if policy == WithdrawalPolicy::Expunge
+            && League::get_league_format(league_id, db_pool).await?
+                == Some(LeagueFormat::DoubleElimination)
+        {
+            return Err(HTTPException::BadRequest(String::from(
+                "The bracket results of a double elimination league can't be deleted, withdraw the team with the forfeit policy",
+            )));
+        }
+
+        let query = sqlx::query!("
+        DELETE TeamsLeaguesRow
         FROM `teams_leagues` as TeamsLeaguesRow
 
         INNER JOIN `leagues` as LeagueRow
@@ -381,117 +1052,605 @@ impl League {
             On TournamentRow.id = LeagueRow.tournament_id 
 
         WHERE LeagueRow.id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?) AND TeamRow.id = (?)
-        ", league_id, tournament_id, user_id, team_id).execute(db_pool).await;
+        ", league_id, tournament_id, user_id, team_id).execute(&mut *transaction).await;
+
+        match query {
+            Err(_) => {
+                return Err(HTTPException::Internal(String::from("Something unknown happened when deleting a team from the tournament. Please try again later")))
+            }
+            Ok(result) => {
+                if result.rows_affected() < 1 {
+                    return Err(HTTPException::BadRequest(String::from("Failed to delete the team from the league. Please make sure the team/league/tournament exists and that you own the tournament")))
+                }
+
+                result
+            }
+        };
+
+        // The ownership was checked by the query above, so the fixtures are only filtered by the league
+        match policy {
+            WithdrawalPolicy::Expunge => {
+                sqlx::query!(
+                    "
+                DELETE FROM `fixtures`
+                WHERE league_id = (?) AND (home_team_id = (?) OR away_team_id = (?))
+                ",
+                    league_id,
+                    team_id,
+                    team_id
+                )
+                .execute(&mut *transaction)
+                .await
+            }
+            WithdrawalPolicy::Forfeit => {
+                League::forfeit_team_ties(league_id, team_id, &mut transaction).await?;
+
+                sqlx::query!(
+                    "
+                UPDATE `fixtures`
+                SET played = TRUE,
+                home_score = CASE WHEN home_team_id = (?) THEN 0 ELSE (?) END,
+                away_score = CASE WHEN away_team_id = (?) THEN 0 ELSE (?) END
+                WHERE league_id = (?) AND played = FALSE AND (home_team_id = (?) OR away_team_id = (?))
+                ",
+                    team_id,
+                    FORFEIT_GOALS,
+                    team_id,
+                    FORFEIT_GOALS,
+                    league_id,
+                    team_id,
+                    team_id
+                )
+                .execute(&mut *transaction)
+                .await
+            }
+        }
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to update the team fixtures. Please try again later",
+            )))
+        })?;
+
+        BracketMatch::advance_bracket_matches(user_id, league_id, &mut transaction).await?;
+
+        League::complete_league_if_finished(user_id, tournament_id, league_id, &mut transaction)
+            .await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to withdraw the team from the league. Please try again later",
+            )))
+        })?;
+
+        Ok(())
+    }
+
+    /// Responsible to award the ties of a withdrawn team which are not decided yet to its opponents.
+    /// Forfeiting only the pending leg could leave the tie to the withdrawn team, so both legs are forfeited
+    /// and the extra time and penalties are removed
+    ///
+    /// # Arguments
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `team_id` - `u64` integer which represents the withdrawn team id
+    /// * `transaction` - `&mut Transaction<MySql>` reference for the transaction which withdraws the team
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn forfeit_team_ties(
+        league_id: u64,
+        team_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<(), HTTPException> {
+        let ties = Tie::get_ties_from_league(league_id, transaction).await?;
+        if ties.is_empty() {
+            return Ok(());
+        }
+
+        let fixtures = Tie::get_leg_scores_from_league(league_id, transaction).await?;
+        for tie in ties {
+            let (Some(first_leg), Some(second_leg)) = (
+                fixtures.get(&tie.first_leg_id),
+                fixtures.get(&tie.second_leg_id),
+            ) else {
+                continue;
+            };
+            let team_plays = first_leg.home_team_id == team_id || first_leg.away_team_id == team_id;
+            if !team_plays
+                || tie
+                    .aggregate(first_leg, second_leg)
+                    .winner_team_id
+                    .is_some()
+            {
+                continue;
+            }
+
+            sqlx::query!(
+                "
+            UPDATE `fixtures`
+            SET played = TRUE,
+            home_score = CASE WHEN home_team_id = (?) THEN 0 ELSE (?) END,
+            away_score = CASE WHEN away_team_id = (?) THEN 0 ELSE (?) END
+            WHERE id IN (?, ?)
+            ",
+                team_id,
+                FORFEIT_GOALS,
+                team_id,
+                FORFEIT_GOALS,
+                tie.first_leg_id,
+                tie.second_leg_id
+            )
+            .execute(&mut **transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to update the team fixtures. Please try again later",
+                )))
+            })?;
+
+            sqlx::query!(
+                "
+            UPDATE `ties`
+            SET extra_time_home_score = NULL, extra_time_away_score = NULL,
+            penalties_home_score = NULL, penalties_away_score = NULL
+            WHERE id = (?)
+            ",
+                tie.id
+            )
+            .execute(&mut **transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to update the team ties. Please try again later",
+                )))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Responsible to get the league fixtures
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Fixture>)` - `Vec<Fixture>` vector containing the fixtures
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the league does not exist
+    pub async fn get_league_fixtures(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<Vec<FixtureDataDTO>, HTTPException> {
+        let query = sqlx::query!(
+            "
+            SELECT FixtureRow.*, HomeTeamRow.name as home_team_name, AwayTeamRow.name as away_team_name
+            FROM `fixtures` as FixtureRow
+            INNER JOIN `leagues` as LeagueRow
+                ON LeagueRow.id = FixtureRow.league_id
+            INNER JOIN `tournaments` as TournamentRow
+                ON TournamentRow.id = LeagueRow.tournament_id
+            INNER JOIN `teams` as HomeTeamRow
+                ON HomeTeamRow.id = FixtureRow.home_team_id
+            INNER JOIN `teams` as AwayTeamRow
+                ON AwayTeamRow.id = FixtureRow.away_team_id
+            WHERE FixtureRow.league_id = (?) AND TournamentRow.id = (?) AND
+            (
+                (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
+                OR
+                (TournamentRow.public = TRUE)
+            )
+            ORDER BY FixtureRow.round ASC
+        ",
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .fetch_all(db_pool)
+        .await;
+
+        let fixtures = match query {
+            Err(_) => {
+                return Err(HTTPException::Internal(String::from("Something wrong happened while getting the league fixtures. Please try again later")))
+            }
+            Ok(data) => data
+        };
+
+        Ok(fixtures
+            .into_iter()
+            .map(|fixture| FixtureDataDTO {
+                id: fixture.id,
+                home_team: TeamInfoDTO {
+                    id: fixture.home_team_id,
+                    name: fixture.home_team_name,
+                },
+                away_team: TeamInfoDTO {
+                    id: fixture.away_team_id,
+                    name: fixture.away_team_name,
+                },
+                home_score: fixture.home_score,
+                away_score: fixture.away_score,
+                played: fixture.played == 1,
+                round: fixture.round,
+            })
+            .collect())
+    }
+
+    /// Responsible to get a page of the league fixtures, filtered and sorted by the query string
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `list_query` - `&FixtureListQuery` reference with the pagination, filters and sorting
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok((Vec<FixtureDataDTO>, PaginationData))` - The fixtures on the page and the pagination information
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the `from` or `to` dates are invalid, the league does not exist, or the tournament is private and the user does not own it
+    pub async fn get_league_fixtures_page(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        list_query: &FixtureListQuery,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(Vec<FixtureDataDTO>, PaginationData), HTTPException> {
+        League::get_league(user_id, tournament_id, league_id, db_pool).await?;
+        let pagination = Pagination::new(list_query.limit, list_query.offset);
+        let date_from = parse_date_filter(&list_query.from)?;
+        let date_to = parse_date_filter(&list_query.to)?;
+
+        let mut count_query = QueryBuilder::new("SELECT COUNT(*)");
+        push_fixture_list_filters(
+            &mut count_query,
+            user_id,
+            tournament_id,
+            league_id,
+            list_query,
+            date_from,
+            date_to,
+        );
+
+        let total = count_query
+            .build_query_scalar::<i64>()
+            .fetch_one(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from("Something wrong happened while getting the league fixtures. Please try again later")))
+            })?;
+
+        let mut query = QueryBuilder::new(
+            "SELECT FixtureRow.id, FixtureRow.home_team_id, FixtureRow.away_team_id, FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played, FixtureRow.round, HomeTeamRow.name as home_team_name, AwayTeamRow.name as away_team_name",
+        );
+        push_fixture_list_filters(
+            &mut query,
+            user_id,
+            tournament_id,
+            league_id,
+            list_query,
+            date_from,
+            date_to,
+        );
+
+        // The sort column comes from a fixed list, so it's safe to push it into the query
+        let sort_column = match list_query.sort.unwrap_or(FixtureSort::Round) {
+            FixtureSort::Round => "FixtureRow.round",
+            FixtureSort::Date => "FixtureRow.playing_date",
+            FixtureSort::Id => "FixtureRow.id",
+        };
+        let sort_order = list_query.order.unwrap_or(SortOrder::Asc);
+        query.push(format!(
+            " ORDER BY {} {}, FixtureRow.id ASC",
+            sort_column,
+            sort_order.as_sql()
+        ));
+        query
+            .push(" LIMIT ")
+            .push_bind(pagination.limit)
+            .push(" OFFSET ")
+            .push_bind(pagination.offset);
+
+        let fixtures = query
+            .build_query_as::<FixtureListRow>()
+            .fetch_all(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from("Something wrong happened while getting the league fixtures. Please try again later")))
+            })?;
+
+        let fixtures = fixtures
+            .into_iter()
+            .map(|fixture| FixtureDataDTO {
+                id: fixture.id,
+                home_team: TeamInfoDTO {
+                    id: fixture.home_team_id,
+                    name: fixture.home_team_name,
+                },
+                away_team: TeamInfoDTO {
+                    id: fixture.away_team_id,
+                    name: fixture.away_team_name,
+                },
+                home_score: fixture.home_score,
+                away_score: fixture.away_score,
+                played: fixture.played,
+                round: fixture.round,
+            })
+            .collect();
+
+        Ok((fixtures, pagination.with_total(total as u64)))
+    }
+
+    /// Responsible to get a page of the tournament leagues, filtered and sorted by the query string
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `list_query` - `&LeagueListQuery` reference with the pagination, filters and sorting
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok((Vec<LeagueInformationDTO>, PaginationData))` - The leagues on the page and the pagination information
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the tournament does not exist, or it's private and the user does not own it
+    pub async fn get_tournament_leagues_page(
+        user_id: u64,
+        tournament_id: u64,
+        list_query: &LeagueListQuery,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(Vec<LeagueInformationDTO>, PaginationData), HTTPException> {
+        Tournament::check_tournament_access(Some(user_id), tournament_id, db_pool).await?;
+        let pagination = Pagination::new(list_query.limit, list_query.offset);
+
+        let mut count_query = QueryBuilder::new("SELECT COUNT(*)");
+        push_league_list_filters(&mut count_query, user_id, tournament_id, list_query);
+
+        let total = count_query
+            .build_query_scalar::<i64>()
+            .fetch_one(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to search the leagues on the tournament. Please try again later",
+                )))
+            })?;
 
-        match query {
-            Err(_) => {
-                return Err(HTTPException::Internal(String::from("Something unknown happened when deleting a team from the tournament. Please try again later")))
-            }
-            Ok(result) => {
-                if result.rows_affected() < 1 {
-                    return Err(HTTPException::BadRequest(String::from("Failed to delete the team from the league. Please make sure the team/league/tournament exists and that you own the tournament")))
-                }
+        let mut query = QueryBuilder::new(
+            "SELECT LeagueRow.id, LeagueRow.tournament_id, LeagueRow.name, LeagueRow.completed, LeagueRow.format",
+        );
+        push_league_list_filters(&mut query, user_id, tournament_id, list_query);
 
-                result
-            }
+        // The sort column comes from a fixed list, so it's safe to push it into the query
+        let sort_column = match list_query.sort.unwrap_or(LeagueSort::Id) {
+            LeagueSort::Name => "LeagueRow.name",
+            LeagueSort::Id => "LeagueRow.id",
         };
+        let sort_order = list_query.order.unwrap_or(SortOrder::Asc);
+        query.push(format!(
+            " ORDER BY {} {}, LeagueRow.id ASC",
+            sort_column,
+            sort_order.as_sql()
+        ));
+        query
+            .push(" LIMIT ")
+            .push_bind(pagination.limit)
+            .push(" OFFSET ")
+            .push_bind(pagination.offset);
 
-        Ok(())
+        let leagues = query
+            .build_query_as::<LeagueListRow>()
+            .fetch_all(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to search the leagues on the tournament. Please try again later",
+                )))
+            })?;
+
+        let leagues = leagues
+            .into_iter()
+            .map(|league| LeagueInformationDTO {
+                id: league.id,
+                name: league.name,
+                tournament_id: league.tournament_id,
+                completed: league.completed,
+                zones: None,
+                champion: None,
+                fixture_seed: None,
+                format: LeagueFormat::from_sql(&league.format),
+                swiss_rounds: None,
+            })
+            .collect();
+
+        Ok((leagues, pagination.with_total(total as u64)))
     }
 
-    /// Responsible to get the league fixtures
+    /// Function responsible to get the league standing tables: the overall table and the tables with only the home or away fixtures
     ///
     /// # Arguments
+    /// * `league_id` - `u64` integer which represents the league id
     /// * `user_id` - `u64` integer which represents the user id
     /// * `tournament_id` - `u64` integer which represents the tournament id
-    /// * `league_id` - `u64` integer which represents the league id
+    /// * `after_round` - `Option<u16>` value, if present only the fixtures until this round are counted, showing the table as it was after the round
     /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
     ///
     /// # Returns
-    /// * `Ok(Vec<Fixture>)` - `Vec<Fixture>` vector containing the fixtures
+    /// * `Ok(LeagueStandingsTable)` - `LeagueStandingsTable` struct containing the overall, home and away tables
     ///
     /// # Errors
     /// * `HTTPException::Internal` - If the database query fails
-    /// * `HTTPException::BadRequest` - If the league does not exist
-    pub async fn get_league_fixtures(
+    pub async fn get_league_standing_table(
+        league_id: u64,
         user_id: u64,
         tournament_id: u64,
-        league_id: u64,
+        after_round: Option<u16>,
         db_pool: &Pool<MySql>,
-    ) -> Result<Vec<FixtureDataDTO>, HTTPException> {
-        let query = sqlx::query!(
+    ) -> Result<LeagueStandingsTable, HTTPException> {
+        let mut tables = vec![];
+        for venue in [
+            StandingVenue::Overall,
+            StandingVenue::Home,
+            StandingVenue::Away,
+        ] {
+            tables.push(
+                League::get_league_venue_standing_table(
+                    league_id,
+                    user_id,
+                    tournament_id,
+                    after_round,
+                    venue,
+                    db_pool,
+                )
+                .await?,
+            );
+        }
+
+        let away = tables.pop().unwrap_or_default();
+        let home = tables.pop().unwrap_or_default();
+        let mut overall = tables.pop().unwrap_or_default();
+
+        // The table is empty when the user can't access the league, so the zones are only read when there's something to mark
+        if !overall.is_empty() {
+            if League::get_league_format(league_id, db_pool).await? == Some(LeagueFormat::Swiss) {
+                League::add_swiss_tiebreaks(league_id, after_round, &mut overall, db_pool).await?;
+            }
+
+            let zones = League::get_league_zones(league_id, db_pool).await?;
+            mark_standing_zones(&mut overall, &zones);
+        }
+
+        Ok(LeagueStandingsTable {
+            overall,
+            home,
+            away,
+        })
+    }
+
+    /// Responsible to add the Buchholz and Sonneborn-Berger tiebreaks to the overall table of a swiss league, ordering it again by them.
+    /// The teams with the same points are ordered by Buchholz, then Sonneborn-Berger and then the usual stats
+    ///
+    /// # Arguments
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `after_round` - `Option<u16>` value, if present only the fixtures until this round are counted
+    /// * `standing_table` - The overall standing table, the user access to it is already checked
+    /// * `executor` - The `&Pool<MySql>` database pool, or the connection of a running transaction
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    async fn add_swiss_tiebreaks<'c, E>(
+        league_id: u64,
+        after_round: Option<u16>,
+        standing_table: &mut [TeamStandingTable],
+        executor: E,
+    ) -> Result<(), HTTPException>
+    where
+        E: Executor<'c, Database = MySql>,
+    {
+        let fixtures = sqlx::query!(
             "
-            SELECT FixtureRow.*, HomeTeamRow.name as home_team_name, AwayTeamRow.name as away_team_name
-            FROM `fixtures` as FixtureRow
-            INNER JOIN `leagues` as LeagueRow
-                ON LeagueRow.id = FixtureRow.league_id
-            INNER JOIN `tournaments` as TournamentRow
-                ON TournamentRow.id = LeagueRow.tournament_id
-            INNER JOIN `teams` as HomeTeamRow
-                ON HomeTeamRow.id = FixtureRow.home_team_id
-            INNER JOIN `teams` as AwayTeamRow
-                ON AwayTeamRow.id = FixtureRow.away_team_id
-            WHERE FixtureRow.league_id = (?) AND TournamentRow.id = (?) AND
-            (
-                (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
-                OR
-                (TournamentRow.public = TRUE)
-            )
-            ORDER BY FixtureRow.round ASC
+        SELECT FixtureRow.home_team_id, FixtureRow.away_team_id, FixtureRow.home_score, FixtureRow.away_score
+        FROM `fixtures` as FixtureRow
+        WHERE FixtureRow.league_id = (?) AND FixtureRow.played = TRUE AND FixtureRow.round <= (?)
         ",
             league_id,
-            tournament_id,
-            user_id
+            after_round.unwrap_or(u16::MAX)
         )
-        .fetch_all(db_pool)
-        .await;
-
-        let fixtures = match query {
-            Err(_) => {
-                return Err(HTTPException::Internal(String::from("Something wrong happened while getting the league fixtures. Please try again later")))
-            }
-            Ok(data) => data
-        };
+        .fetch_all(executor)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something wrong happened while getting the league standing table. Please try again later",
+            )))
+        })?;
 
-        Ok(fixtures
+        let fixtures: Vec<(u64, u64, u8, u8)> = fixtures
             .into_iter()
-            .map(|fixture| FixtureDataDTO {
-                id: fixture.id,
-                home_team: TeamInfoDTO {
-                    id: fixture.home_team_id,
-                    name: fixture.home_team_name,
-                },
-                away_team: TeamInfoDTO {
-                    id: fixture.away_team_id,
-                    name: fixture.away_team_name,
-                },
-                home_score: fixture.home_score,
-                away_score: fixture.away_score,
-                played: fixture.played == 1,
-                round: fixture.round,
+            .map(|fixture| {
+                (
+                    fixture.home_team_id,
+                    fixture.away_team_id,
+                    fixture.home_score,
+                    fixture.away_score,
+                )
             })
-            .collect())
+            .collect();
+        set_swiss_tiebreaks(standing_table, &fixtures);
+        standing_table.sort_by(compare_swiss_standings);
+
+        Ok(())
+    }
+
+    /// Responsible to get how the league fixtures are paired
+    ///
+    /// # Arguments
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(Option<LeagueFormat>)` - The league format, `None` if the league does not exist.
+    /// It doesn't check the user access to the league, so it should be used after it's checked
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_league_format(
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<Option<LeagueFormat>, HTTPException> {
+        let league = sqlx::query!(
+            "SELECT LeagueRow.format FROM `leagues` as LeagueRow WHERE LeagueRow.id = (?)",
+            league_id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league. Please try again later",
+            )))
+        })?;
+
+        Ok(league.map(|league| LeagueFormat::from_sql(&league.format)))
     }
 
-    /// Function responsible to get the league standing table
+    /// Function responsible to get the league standing table counting only the fixtures from the `venue`
     ///
     /// # Arguments
     /// * `league_id` - `u64` integer which represents the league id
     /// * `user_id` - `u64` integer which represents the user id
     /// * `tournament_id` - `u64` integer which represents the tournament id
-    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    /// * `after_round` - `Option<u16>` value, if present only the fixtures until this round are counted
+    /// * `venue` - `StandingVenue` enum, which fixtures sides are counted
+    /// * `executor` - The `&Pool<MySql>` database pool, or the connection of a running transaction
     ///
     /// # Returns
-    /// * `Ok(Vec<TeamStandingTable>)` - `Vec<TeamStandingTable>` vector containing the league standing table
+    /// * `Ok(Vec<TeamStandingTable>)` - `Vec<TeamStandingTable>` vector containing the league standing table.
+    /// Every team of the league is on it, the teams without a fixture until `after_round` with every stat as zero
     ///
     /// # Errors
     /// * `HTTPException::Internal` - If the database query fails
-    pub async fn get_league_standing_table(
+    async fn get_league_venue_standing_table<'c, E>(
         league_id: u64,
         user_id: u64,
         tournament_id: u64,
-        db_pool: &Pool<MySql>,
-    ) -> Result<Vec<TeamStandingTable>, HTTPException> {
+        after_round: Option<u16>,
+        venue: StandingVenue,
+        executor: E,
+    ) -> Result<Vec<TeamStandingTable>, HTTPException>
+    where
+        E: Executor<'c, Database = MySql>,
+    {
+        let last_round = after_round.unwrap_or(u16::MAX);
+        let venue = venue.as_sql();
+
         // This is relatively a big query, so i'm commenting everything out
         // We're basically organizing a league table, so we can see the teams with best performances on the league
         // The teams table contains information about the teams which plays the league
@@ -501,34 +1660,53 @@ impl League {
             -- Selecting all necessary tem properties, team name and team id
             SELECT TeamRow.id as team_id, TeamRow.name as team_name, 
             -- Selecting results row which tells the team performance and that we can organize/order it later
-            ResultRow.total_points, ResultRow.win, ResultRow.draw, ResultRow.loss, ResultRow.goals_scored, ResultRow.goals_against, ResultRow.goal_difference
-            -- Getting the teams so we can check it's stats
-            FROM `teams` as TeamRow
+            ResultRow.matches_played, ResultRow.total_points, ResultRow.win, ResultRow.draw, ResultRow.loss, ResultRow.goals_scored, ResultRow.goals_against, ResultRow.goal_difference, ResultRow.form
+            -- Getting the teams which plays the league so we can check it's stats
+            FROM `teams_leagues` as TeamLeagueRow
+            INNER JOIN `teams` as TeamRow
+                ON TeamRow.id = TeamLeagueRow.team_id
+            INNER JOIN `leagues` as LeagueRow
+                ON LeagueRow.id = TeamLeagueRow.league_id
             -- Making sure that if the tournament is private, only the tournament creator can access it
             INNER JOIN `tournaments` as TournamentRow
-                ON (
+                ON TournamentRow.id = LeagueRow.tournament_id AND (
                     (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
                     OR
                     (TournamentRow.public = TRUE)
                 )
-            JOIN (
+            -- Teams without any fixture are still on the table, with every stat as NULL
+            LEFT JOIN (
                 -- Selecting the properties to organize the league table
                 -- Using CAST() to convert BigDecimals to UNSIGNED 64 BITS INTEGER
                 -- We should downgrade the datatype because it's unnecessary big
                 SELECT team_id, 
+                CAST(SUM(matches_played) as UNSIGNED) as matches_played,
                 CAST(SUM(total_points) as UNSIGNED) as total_points,
                 CAST(SUM(win) as UNSIGNED) as win,
                 CAST(SUM(draw) as UNSIGNED) as draw,
                 CAST(SUM(loss) as UNSIGNED)  as loss,
                 CAST(SUM(goals_scored) as UNSIGNED) as goals_scored,
                 CAST(SUM(goals_against) as UNSIGNED) as goals_against,
-                CAST((SUM(goals_scored) - SUM(goals_against)) as SIGNED) as goal_difference
+                CAST((SUM(goals_scored) - SUM(goals_against)) as SIGNED) as goal_difference,
+                -- The results of the last played fixtures, from the oldest to the newest one. E.g. "WWDLW"
+                -- GROUP_CONCAT ignores the NULL results from the fixtures that weren't played
+                REVERSE(SUBSTRING(GROUP_CONCAT(result ORDER BY round DESC, fixture_id DESC SEPARATOR ''), 1, (?))) as form
                 
                 FROM (
                     -- Calculating the results from it's home performance
                         (
                             -- Selecting the home side of the team from the game fixture
-                            SELECT FixtureRow.home_team_id as team_id,
+                            SELECT FixtureRow.home_team_id as team_id, FixtureRow.id as fixture_id, FixtureRow.round,
+
+                            -- Counts the played matches, the draws or losses alone can't tell it
+                            CASE WHEN FixtureRow.played = TRUE THEN 1 ELSE 0 END as matches_played,
+
+                            -- The letter used on the form, NULL while the fixture isn't played
+                            CASE
+                                WHEN FixtureRow.played = TRUE AND FixtureRow.home_score > FixtureRow.away_score THEN 'W'
+                                WHEN FixtureRow.played = TRUE AND FixtureRow.home_score = FixtureRow.away_score THEN 'D'
+                                WHEN FixtureRow.played = TRUE THEN 'L'
+                            END as result,
                             
                             -- Handles points
                             -- WIN = 3 points
@@ -577,14 +1755,23 @@ impl League {
                             -- Makes sure the stats is only valid for it's league
                             -- We don't want count the team stats if he plays in more than 1 league
                             FROM `fixtures` as FixtureRow
-                                WHERE FixtureRow.league_id = (?)
+                                -- The home side is skipped on the away table
+                                WHERE FixtureRow.league_id = (?) AND FixtureRow.round <= (?) AND (?) <> 'away'
                         )
                     -- We'll union everything from both cases
                     UNION ALL
                     -- Calculating the team performance from it's away performance
                     -- The operations bellow is basically the same as above, so i'm not commenting that out
                         (
-                            SELECT FixtureRow.away_team_id as team_id,
+                            SELECT FixtureRow.away_team_id as team_id, FixtureRow.id as fixture_id, FixtureRow.round,
+
+                            CASE WHEN FixtureRow.played = TRUE THEN 1 ELSE 0 END as matches_played,
+
+                            CASE
+                                WHEN FixtureRow.played = TRUE AND FixtureRow.away_score > FixtureRow.home_score THEN 'W'
+                                WHEN FixtureRow.played = TRUE AND FixtureRow.away_score = FixtureRow.home_score THEN 'D'
+                                WHEN FixtureRow.played = TRUE THEN 'L'
+                            END as result,
                             
                             CASE
                                 WHEN FixtureRow.played = TRUE AND FixtureRow.away_score > FixtureRow.home_score THEN 3
@@ -621,47 +1808,682 @@ impl League {
                             END as goals_against
                         
                             FROM `fixtures` as FixtureRow
-                                WHERE FixtureRow.league_id = (?)
+                                -- The away side is skipped on the home table
+                                WHERE FixtureRow.league_id = (?) AND FixtureRow.round <= (?) AND (?) <> 'home'
                         )
                 ) as SubQuery -- As we're grouping multiple queries, we'll rename to "SubQuery" so MySql understands this is a group of queries
 
                 -- Grouping the collected stats by the team id
                 GROUP BY team_id
             ) as ResultRow ON TeamRow.id = ResultRow.team_id -- Making that the teams stats doesnt repeat
-            -- Making sure the tournament is valid
-            WHERE TournamentRow.id = (?)
+            -- Making sure the league and tournament are valid
+            WHERE TeamLeagueRow.league_id = (?) AND TournamentRow.id = (?)
             -- Finally, we're ordering the table accordingly:
             -- Team with most points
             -- Team with most goals difference
             -- Team with most goals scored
             -- Team with less goals against
-            ORDER BY ResultRow.total_points DESC, ResultRow.goal_difference DESC, ResultRow.goals_scored DESC, ResultRow.goals_against ASC
-        "#, user_id, league_id, league_id, tournament_id
-        ).fetch_all(db_pool).await;
+            -- The team id is only used so teams with the same stats are always shown on the same order
+            ORDER BY ResultRow.total_points DESC, ResultRow.goal_difference DESC, ResultRow.goals_scored DESC, ResultRow.goals_against ASC, TeamRow.id ASC
+        "#, user_id, STANDING_FORM_SIZE as u32, league_id, last_round, venue, league_id, last_round, venue, league_id, tournament_id
+        ).fetch_all(executor).await;
+
+        let standing_table = match query {
+            Err(_) => {
+                return Err(HTTPException::Internal(String::from("Something wrong happened while getting the league standing table. Please try again later")));
+            }
+            Ok(data) => data,
+        };
+
+        let standing_table: Vec<TeamStandingTable> = standing_table
+            .into_iter()
+            .map(|standing| TeamStandingTable {
+                team_id: standing.team_id,
+                team_name: standing.team_name,
+                matches_played: standing.matches_played.unwrap_or(0) as u8,
+                total_points: standing.total_points.unwrap_or(0) as u8,
+                win: standing.win.unwrap_or(0) as u8,
+                draw: standing.draw.unwrap_or(0) as u8,
+                loss: standing.loss.unwrap_or(0) as u8,
+                goals_scored: standing.goals_scored.unwrap_or(0) as u8,
+                goals_against: standing.goals_against.unwrap_or(0) as u8,
+                goal_difference: standing.goal_difference.unwrap_or(0) as i16,
+                form: standing.form.unwrap_or_default(),
+                zone: None,
+                buchholz: None,
+                sonneborn_berger: None,
+            })
+            .collect();
+
+        Ok(standing_table)
+    }
+
+    /// Responsible to get the position of every team after each round of the league, so the league progression can be charted.
+    /// The played fixtures are read once and the table is updated round by round, instead of building a new table for every round.
+    /// A swiss league orders each round by the Buchholz and Sonneborn-Berger tiebreaks of that round, the same way as its standing table
+    ///
+    /// # Arguments
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(Vec<TeamPositionProgression>)` - The positions of each team, one for every round with a played fixture.
+    /// It's empty if the league does not have any team
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the league does not exist, or the tournament is private and the user does not own it
+    pub async fn get_league_standing_progression(
+        league_id: u64,
+        user_id: u64,
+        tournament_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<Vec<TeamPositionProgression>, HTTPException> {
+        let league = League::get_league(user_id, tournament_id, league_id, db_pool).await?;
+
+        let teams = sqlx::query!(
+            "
+            SELECT TeamRow.id, TeamRow.name
+            FROM `teams_leagues` as TeamLeagueRow
+            INNER JOIN `teams` as TeamRow
+                ON TeamRow.id = TeamLeagueRow.team_id
+            WHERE TeamLeagueRow.league_id = (?)
+            ORDER BY TeamRow.id ASC
+        ",
+            league_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something wrong happened while getting the league standing progression. Please try again later",
+            )))
+        })?;
+
+        if teams.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let fixtures = sqlx::query!(
+            "
+            SELECT FixtureRow.home_team_id, FixtureRow.away_team_id, FixtureRow.home_score, FixtureRow.away_score, FixtureRow.round
+            FROM `fixtures` as FixtureRow
+            WHERE FixtureRow.league_id = (?) AND FixtureRow.played = TRUE
+            ORDER BY FixtureRow.round ASC
+        ",
+            league_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something wrong happened while getting the league standing progression. Please try again later",
+            )))
+        })?;
+
+        let mut standing_table: Vec<TeamStandingTable> = vec![];
+        let mut progression: Vec<TeamPositionProgression> = vec![];
+        let mut team_indexes: HashMap<u64, usize> = HashMap::new();
+        for (index, team) in teams.into_iter().enumerate() {
+            team_indexes.insert(team.id, index);
+            standing_table.push(TeamStandingTable {
+                team_id: team.id,
+                team_name: team.name.clone(),
+                matches_played: 0,
+                total_points: 0,
+                win: 0,
+                draw: 0,
+                loss: 0,
+                goals_scored: 0,
+                goals_against: 0,
+                goal_difference: 0,
+                form: String::new(),
+                zone: None,
+                buchholz: None,
+                sonneborn_berger: None,
+            });
+            progression.push(TeamPositionProgression {
+                team_id: team.id,
+                team_name: team.name,
+                positions: vec![],
+            });
+        }
+
+        // The swiss tiebreaks of each round only count the fixtures until it
+        let mut played_fixtures: Vec<(u64, u64, u8, u8)> = vec![];
+        let mut fixtures = fixtures.into_iter().peekable();
+        while let Some(fixture) = fixtures.next() {
+            played_fixtures.push((
+                fixture.home_team_id,
+                fixture.away_team_id,
+                fixture.home_score,
+                fixture.away_score,
+            ));
+
+            for (team_id, goals_scored, goals_against) in [
+                (fixture.home_team_id, fixture.home_score, fixture.away_score),
+                (fixture.away_team_id, fixture.away_score, fixture.home_score),
+            ] {
+                // Teams removed from the league after playing are not on the table anymore
+                if let Some(index) = team_indexes.get(&team_id) {
+                    add_result_to_standing(
+                        &mut standing_table[*index],
+                        goals_scored,
+                        goals_against,
+                    );
+                }
+            }
+
+            // Once every fixture of the round is counted, the positions are taken
+            let round_finished = match fixtures.peek() {
+                Some(next_fixture) => next_fixture.round != fixture.round,
+                None => true,
+            };
+            if round_finished {
+                let mut ranking: Vec<usize> = (0..standing_table.len()).collect();
+                if league.format == LeagueFormat::Swiss {
+                    set_swiss_tiebreaks(&mut standing_table, &played_fixtures);
+                    ranking.sort_by(|a, b| {
+                        compare_swiss_standings(&standing_table[*a], &standing_table[*b])
+                    });
+                } else {
+                    ranking.sort_by(|a, b| {
+                        compare_standings(&standing_table[*a], &standing_table[*b])
+                    });
+                }
+
+                for (position, index) in ranking.into_iter().enumerate() {
+                    progression[index].positions.push(RoundPosition {
+                        round: fixture.round,
+                        position: position as u32 + 1,
+                        total_points: standing_table[index].total_points,
+                    });
+                }
+            }
+        }
+
+        Ok(progression)
+    }
+
+    /// Responsible to get a page of the tournament archive, the completed leagues and their champions, the latest first
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `list_query` - `&ArchiveListQuery` reference with the pagination
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok((Vec<ArchivedLeagueDTO>, PaginationData))` - The archived leagues on the page and the pagination information
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the tournament does not exist, or it's private and the user does not own it
+    pub async fn get_tournament_archive(
+        user_id: u64,
+        tournament_id: u64,
+        list_query: &ArchiveListQuery,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(Vec<ArchivedLeagueDTO>, PaginationData), HTTPException> {
+        Tournament::check_tournament_access(Some(user_id), tournament_id, db_pool).await?;
+        let pagination = Pagination::new(list_query.limit, list_query.offset);
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "
+        SELECT COUNT(*)
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        WHERE LeagueRow.tournament_id = (?) AND LeagueRow.completed = TRUE AND
+        (
+            (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
+            OR
+            (TournamentRow.public = TRUE)
+        )
+        ",
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .fetch_one(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the tournament archive. Please try again later",
+            )))
+        })?;
+
+        let leagues = sqlx::query_as::<_, ArchivedLeagueRow>(
+            "
+        SELECT LeagueRow.id, LeagueRow.name, ChampionRow.team_id as champion_id, ChampionRow.team_name as champion_name
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        LEFT JOIN `league_final_standings` as ChampionRow
+            ON ChampionRow.league_id = LeagueRow.id AND ChampionRow.position = 1
+        WHERE LeagueRow.tournament_id = (?) AND LeagueRow.completed = TRUE AND
+        (
+            (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
+            OR
+            (TournamentRow.public = TRUE)
+        )
+        ORDER BY LeagueRow.id DESC
+        LIMIT ? OFFSET ?
+        ",
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .bind(pagination.limit)
+        .bind(pagination.offset)
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the tournament archive. Please try again later",
+            )))
+        })?;
+
+        let leagues = leagues
+            .into_iter()
+            .map(|league| ArchivedLeagueDTO {
+                id: league.id,
+                name: league.name,
+                champion: match (league.champion_id, league.champion_name) {
+                    (Some(id), Some(name)) => Some(TeamInfoDTO { id, name }),
+                    _ => None,
+                },
+            })
+            .collect();
 
-        let standing_table = match query {
-            Err(_) => {
-                return Err(HTTPException::Internal(String::from("Something wrong happened while getting the league standing table. Please try again later")));
+        Ok((leagues, pagination.with_total(total as u64)))
+    }
+
+    /// Responsible to get an archived league: the standing table and fixtures saved when it was completed.
+    /// They're kept as they were, even if the teams are renamed or deleted later
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `league_id` - `u64` integer which represents the league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(ArchivedSeasonDTO)` - `ArchivedSeasonDTO` struct with the final standing table, fixtures and champion
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest`
+    /// * * The tournament or league does not exist
+    /// * * The league is not completed
+    /// * * The tournament is private and the user does not own the tournament
+    pub async fn get_archived_season(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<ArchivedSeasonDTO, HTTPException> {
+        let league = sqlx::query!(
+            "
+        SELECT LeagueRow.id, LeagueRow.name
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        WHERE LeagueRow.id = (?) AND LeagueRow.tournament_id = (?) AND LeagueRow.completed = TRUE AND
+        (
+            (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
+            OR
+            (TournamentRow.public = TRUE)
+        )
+        ",
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the archived league. Please try again later",
+            )))
+        })?;
+
+        let league = match league {
+            None => {
+                return Err(HTTPException::BadRequest(String::from("Failed to get the archived league. Please make sure the league is completed, the tournament exists and you have permissions to access it")));
             }
-            Ok(data) => data,
+            Some(league) => league,
         };
 
-        let standing_table: Vec<TeamStandingTable> = standing_table
+        let standings = sqlx::query_as!(
+            ArchivedStanding,
+            "
+        SELECT StandingRow.position, StandingRow.team_id, StandingRow.team_name, StandingRow.matches_played, StandingRow.total_points,
+        StandingRow.win, StandingRow.draw, StandingRow.loss, StandingRow.goals_scored, StandingRow.goals_against, StandingRow.goal_difference, StandingRow.form
+        FROM `league_final_standings` as StandingRow
+        WHERE StandingRow.league_id = (?)
+        ORDER BY StandingRow.position ASC
+        ",
+            league.id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the archived league. Please try again later",
+            )))
+        })?;
+
+        let fixtures = sqlx::query!(
+            "
+        SELECT FixtureRow.fixture_id, FixtureRow.round, FixtureRow.home_team_id, FixtureRow.home_team_name, FixtureRow.away_team_id, FixtureRow.away_team_name,
+        FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played, FixtureRow.playing_date
+        FROM `league_final_fixtures` as FixtureRow
+        WHERE FixtureRow.league_id = (?)
+        ORDER BY FixtureRow.round ASC, FixtureRow.fixture_id ASC
+        ",
+            league.id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the archived league. Please try again later",
+            )))
+        })?;
+
+        let fixtures = fixtures
             .into_iter()
-            .map(|standing| TeamStandingTable {
-                team_id: standing.team_id,
-                team_name: standing.team_name,
-                total_points: standing.total_points.unwrap_or(0) as u8,
-                win: standing.win.unwrap_or(0) as u8,
-                draw: standing.draw.unwrap_or(0) as u8,
-                loss: standing.loss.unwrap_or(0) as u8,
-                goals_scored: standing.goals_scored.unwrap_or(0) as u8,
-                goals_against: standing.goals_against.unwrap_or(0) as u8,
-                goal_difference: standing.goal_difference.unwrap_or(0) as i16,
+            .map(|fixture| ArchivedFixture {
+                fixture_id: fixture.fixture_id,
+                round: fixture.round,
+                home_team: TeamInfoDTO {
+                    id: fixture.home_team_id,
+                    name: fixture.home_team_name,
+                },
+                away_team: TeamInfoDTO {
+                    id: fixture.away_team_id,
+                    name: fixture.away_team_name,
+                },
+                home_score: fixture.home_score,
+                away_score: fixture.away_score,
+                played: fixture.played == 1,
+                playing_date: fixture.playing_date,
             })
             .collect();
 
-        Ok(standing_table)
+        let champion = standings.first().map(|standing| TeamInfoDTO {
+            id: standing.team_id,
+            name: standing.team_name.clone(),
+        });
+
+        Ok(ArchivedSeasonDTO {
+            id: league.id,
+            name: league.name,
+            champion,
+            standings,
+            fixtures,
+        })
+    }
+
+    /// Responsible to start a new season of a tournament which leagues are tiered divisions.
+    /// A new league is created for every division carrying over its teams, while the top teams of each division are promoted
+    /// to the division above and the bottom ones are relegated to the division below, based on the final standings saved when each league was completed.
+    /// Every league of the new season is saved on a single transaction
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `season_data` - `NewSeasonDTO` struct with the divisions, from the top to the bottom tier, and how many teams move between them
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(Vec<NewSeasonLeagueDTO>)` - The leagues of the new season, on the same order of the divisions
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest`
+    /// * * There's no division, a league is on more than one division or a league name is too long
+    /// * * A league does not exist on the tournament or the user does not own the tournament
+    /// * * A league is not completed yet
+    /// * * A team is on the final standings of more than one division
+    /// * * A division does not have enough teams to be promoted and relegated
+    pub async fn create_new_season(
+        user_id: u64,
+        tournament_id: u64,
+        season_data: NewSeasonDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<Vec<NewSeasonLeagueDTO>, HTTPException> {
+        let divisions = season_data.divisions;
+        if divisions.is_empty() {
+            return Err(HTTPException::BadRequest(String::from(
+                "The new season must have at least one division",
+            )));
+        }
+
+        if divisions.iter().any(|division| division.name.len() > 20) {
+            return Err(HTTPException::BadRequest(String::from(
+                "The name of the league is too long",
+            )));
+        }
+
+        let mut league_ids: Vec<u64> = divisions
+            .iter()
+            .map(|division| division.league_id)
+            .collect();
+        league_ids.sort();
+        league_ids.dedup();
+        if league_ids.len() != divisions.len() {
+            return Err(HTTPException::BadRequest(String::from(
+                "A league can't be on more than one division",
+            )));
+        }
+
+        let mut query = QueryBuilder::new(
+            "
+        SELECT LeagueRow.id, LeagueRow.tournament_id, LeagueRow.name, LeagueRow.completed
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        WHERE TournamentRow.id = ",
+        );
+        query
+            .push_bind(tournament_id)
+            .push(" AND TournamentRow.user_id = ")
+            .push_bind(user_id)
+            .push(" AND LeagueRow.id IN (");
+        let mut separated = query.separated(", ");
+        for league_id in &league_ids {
+            separated.push_bind(*league_id);
+        }
+        query.push(")");
+
+        let leagues = query
+            .build_query_as::<LeagueListRow>()
+            .fetch_all(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to create the new season. Please try again later",
+                )))
+            })?;
+
+        if leagues.len() != divisions.len() {
+            return Err(HTTPException::BadRequest(String::from("Failed to create the new season. Make sure the leagues exists on the tournament and that you also own the tournament")));
+        }
+
+        if let Some(league) = leagues.iter().find(|league| !league.completed) {
+            return Err(HTTPException::BadRequest(format!(
+                "The league \"{}\" must be completed before starting a new season",
+                league.name
+            )));
+        }
+
+        // The final standings of each division, from the champion to the last team, as they were frozen when the league was completed.
+        // The teams deleted after the league was completed are left out
+        let mut final_standings: Vec<Vec<u64>> = vec![];
+        let mut season_teams: HashSet<u64> = HashSet::new();
+        for division in &divisions {
+            let standings = sqlx::query!(
+                "
+            SELECT StandingRow.team_id, StandingRow.team_name
+            FROM `league_final_standings` as StandingRow
+            INNER JOIN `teams` as TeamRow
+                ON TeamRow.id = StandingRow.team_id AND TeamRow.tournament_id = (?)
+            WHERE StandingRow.league_id = (?)
+            ORDER BY StandingRow.position ASC
+            ",
+                tournament_id,
+                division.league_id
+            )
+            .fetch_all(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to create the new season. Please try again later",
+                )))
+            })?;
+
+            // A team can only play one division of the season
+            if let Some(standing) = standings
+                .iter()
+                .find(|standing| !season_teams.insert(standing.team_id))
+            {
+                return Err(HTTPException::BadRequest(format!(
+                    "The team \"{}\" is on more than one division",
+                    standing.team_name
+                )));
+            }
+
+            final_standings.push(
+                standings
+                    .into_iter()
+                    .map(|standing| standing.team_id)
+                    .collect(),
+            );
+        }
+
+        let exchanged_teams = season_data.exchanged_teams as usize;
+        let last_division = divisions.len() - 1;
+        let mut staying: Vec<Vec<u64>> = vec![vec![]; divisions.len()];
+        let mut promoted: Vec<Vec<u64>> = vec![vec![]; divisions.len()];
+        let mut relegated: Vec<Vec<u64>> = vec![vec![]; divisions.len()];
+        for (index, teams) in final_standings.iter().enumerate() {
+            // The top division has no one to be promoted to, and the bottom division no one to be relegated to
+            let promoted_quantity = if index > 0 { exchanged_teams } else { 0 };
+            let relegated_quantity = if index < last_division {
+                exchanged_teams
+            } else {
+                0
+            };
+
+            if promoted_quantity + relegated_quantity > teams.len() {
+                return Err(HTTPException::BadRequest(format!(
+                    "The league \"{}\" does not have enough teams to promote and relegate {} teams",
+                    divisions[index].name, exchanged_teams
+                )));
+            }
+
+            let relegation_start = teams.len() - relegated_quantity;
+            if index > 0 {
+                promoted[index - 1].extend_from_slice(&teams[..promoted_quantity]);
+            }
+            staying[index].extend_from_slice(&teams[promoted_quantity..relegation_start]);
+            if index < last_division {
+                relegated[index + 1].extend_from_slice(&teams[relegation_start..]);
+            }
+        }
+
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        let mut new_leagues: Vec<NewSeasonLeagueDTO> = vec![];
+        for (index, division) in divisions.into_iter().enumerate() {
+            let league_id = sqlx::query!(
+                "
+            INSERT INTO `leagues` (`name`, `completed`, `tournament_id`, `format`, `swiss_rounds`)
+            SELECT (?), FALSE, (?), LeagueRow.format, LeagueRow.swiss_rounds
+            FROM `leagues` as LeagueRow
+            WHERE LeagueRow.id = (?)
+            ",
+                division.name,
+                tournament_id,
+                division.league_id
+            )
+            .execute(&mut *transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to create the new season. Please try again later",
+                )))
+            })?
+            .last_insert_id();
+
+            // The zones are kept from the previous season
+            sqlx::query!(
+                "
+            INSERT INTO `league_zones` (`league_id`, `label`, `color`, `start_position`, `end_position`)
+            SELECT (?), ZoneRow.label, ZoneRow.color, ZoneRow.start_position, ZoneRow.end_position
+            FROM `league_zones` as ZoneRow
+            WHERE ZoneRow.league_id = (?)
+            ORDER BY ZoneRow.id ASC
+            ",
+                league_id,
+                division.league_id
+            )
+            .execute(&mut *transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to create the new season. Please try again later",
+                )))
+            })?;
+
+            let teams = [
+                staying[index].as_slice(),
+                promoted[index].as_slice(),
+                relegated[index].as_slice(),
+            ]
+            .concat();
+
+            if !teams.is_empty() {
+                let mut query =
+                    QueryBuilder::new("INSERT INTO `teams_leagues` (`team_id`, `league_id`) ");
+                query.push_values(&teams, |mut row, team_id| {
+                    row.push_bind(*team_id).push_bind(league_id);
+                });
+
+                query
+                    .build()
+                    .execute(&mut *transaction)
+                    .await
+                    .or_else(|_error| {
+                        Err(HTTPException::Internal(String::from(
+                            "Failed to create the new season. Please try again later",
+                        )))
+                    })?;
+            }
+
+            new_leagues.push(NewSeasonLeagueDTO {
+                id: league_id,
+                name: division.name,
+                previous_league_id: division.league_id,
+                teams,
+                promoted: mem::take(&mut promoted[index]),
+                relegated: mem::take(&mut relegated[index]),
+            });
+        }
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to create the new season. Please try again later",
+            )))
+        })?;
+
+        Ok(new_leagues)
     }
 
     /// Responsible to get the quantity of teams from the league
@@ -737,6 +2559,7 @@ pub async fn get_teams_from_league(
             ON TournamentRow.id = LeagueRow.tournament_id    
 
         WHERE TeamLeagueRow.league_id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?)
+        ORDER BY TeamRow.id
     ",
         league_id,
         tournament_id,
@@ -765,3 +2588,295 @@ pub async fn get_teams_from_league(
 
     Ok(teams)
 }
+
+/// Adds a played fixture to the team standing, the same way the standing table query counts it
+fn add_result_to_standing(standing: &mut TeamStandingTable, goals_scored: u8, goals_against: u8) {
+    let result = match goals_scored.cmp(&goals_against) {
+        Ordering::Greater => {
+            standing.win = standing.win.saturating_add(1);
+            standing.total_points = standing.total_points.saturating_add(3);
+            'W'
+        }
+        Ordering::Equal => {
+            standing.draw = standing.draw.saturating_add(1);
+            standing.total_points = standing.total_points.saturating_add(1);
+            'D'
+        }
+        Ordering::Less => {
+            standing.loss = standing.loss.saturating_add(1);
+            'L'
+        }
+    };
+
+    standing.matches_played = standing.matches_played.saturating_add(1);
+    standing.form.push(result);
+    if standing.form.len() > STANDING_FORM_SIZE {
+        standing.form.remove(0);
+    }
+
+    standing.goals_scored = standing.goals_scored.saturating_add(goals_scored);
+    standing.goals_against = standing.goals_against.saturating_add(goals_against);
+    standing.goal_difference = standing.goals_scored as i16 - standing.goals_against as i16;
+}
+
+/// Checks the swiss rounds sent when creating or editing a league
+///
+/// # Errors
+/// * `HTTPException::BadRequest` - If the league is not a swiss league, or the rounds are zero
+fn validate_swiss_rounds(
+    format: LeagueFormat,
+    swiss_rounds: Option<u16>,
+) -> Result<(), HTTPException> {
+    match swiss_rounds {
+        Some(_) if format != LeagueFormat::Swiss => Err(HTTPException::BadRequest(String::from(
+            "Only the swiss leagues have a number of rounds",
+        ))),
+        Some(0) => Err(HTTPException::BadRequest(String::from(
+            "A swiss league must have at least one round",
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Checks the zones sent when creating or editing a league
+///
+/// # Errors
+/// * `HTTPException::BadRequest` - If there are too many zones, or a zone label, colour or positions are invalid
+fn validate_league_zones(zones: &[LeagueZone]) -> Result<(), HTTPException> {
+    if zones.len() > MAX_LEAGUE_ZONES {
+        return Err(HTTPException::BadRequest(format!(
+            "A league can't have more than {} zones",
+            MAX_LEAGUE_ZONES
+        )));
+    }
+
+    for zone in zones {
+        if zone.label.trim().is_empty() || zone.label.len() > 30 {
+            return Err(HTTPException::BadRequest(String::from(
+                "The zone label must have between 1 and 30 characters",
+            )));
+        }
+
+        let valid_color = zone.color.len() == 7
+            && zone.color.starts_with('#')
+            && zone.color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid_color {
+            return Err(HTTPException::BadRequest(format!(
+                "The zone \"{}\" colour must be hexadecimal, e.g. #1E90FF",
+                zone.label
+            )));
+        }
+
+        // Both positions must be counted from the same side of the table, otherwise the range depends on the teams quantity
+        let same_side = (zone.start_position > 0 && zone.end_position > 0)
+            || (zone.start_position < 0 && zone.end_position < 0);
+        if !same_side || zone.start_position > zone.end_position {
+            return Err(HTTPException::BadRequest(format!(
+                "The zone \"{}\" positions are invalid. Use positive positions counted from the top or negative positions counted from the bottom, with the start before the end",
+                zone.label
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Marks each standing with the zone of its position. Negative zone positions are counted from the bottom of the table.
+/// When zones overlap, the one defined first is used
+fn mark_standing_zones(standing_table: &mut [TeamStandingTable], zones: &[LeagueZone]) {
+    let teams_quantity = standing_table.len() as i32;
+    let to_position = |zone_position: i16| -> i32 {
+        if zone_position < 0 {
+            teams_quantity + zone_position as i32 + 1
+        } else {
+            zone_position as i32
+        }
+    };
+
+    for (index, standing) in standing_table.iter_mut().enumerate() {
+        let position = index as i32 + 1;
+        standing.zone = zones
+            .iter()
+            .find(|zone| {
+                to_position(zone.start_position) <= position
+                    && position <= to_position(zone.end_position)
+            })
+            .cloned();
+    }
+}
+
+/// Responsible to set the Buchholz and Sonneborn-Berger tiebreaks of a swiss standing table: the points of the opponents of a team,
+/// and the points of the opponents it beat plus half the points of the ones it drew with
+///
+/// # Arguments
+/// * `standing_table` - The overall standing table, the teams which are not on it count as 0 points
+/// * `fixtures` - The played fixtures counted on the table, as `(home_team_id, away_team_id, home_score, away_score)`
+fn set_swiss_tiebreaks(standing_table: &mut [TeamStandingTable], fixtures: &[(u64, u64, u8, u8)]) {
+    let points: HashMap<u64, u16> = standing_table
+        .iter()
+        .map(|standing| (standing.team_id, standing.total_points as u16))
+        .collect();
+    let mut tiebreaks: HashMap<u64, (u16, f32)> = HashMap::new();
+    for &(home_team_id, away_team_id, home_score, away_score) in fixtures {
+        for (team_id, opponent_id, goals_scored, goals_against) in [
+            (home_team_id, away_team_id, home_score, away_score),
+            (away_team_id, home_team_id, away_score, home_score),
+        ] {
+            let opponent_points = points.get(&opponent_id).copied().unwrap_or(0);
+            let (buchholz, sonneborn_berger) = tiebreaks.entry(team_id).or_default();
+            *buchholz += opponent_points;
+            *sonneborn_berger += match goals_scored.cmp(&goals_against) {
+                Ordering::Greater => opponent_points as f32,
+                Ordering::Equal => opponent_points as f32 / 2.0,
+                Ordering::Less => 0.0,
+            };
+        }
+    }
+
+    for standing in standing_table.iter_mut() {
+        let (buchholz, sonneborn_berger) = tiebreaks
+            .get(&standing.team_id)
+            .copied()
+            .unwrap_or_default();
+        standing.buchholz = Some(buchholz);
+        standing.sonneborn_berger = Some(sonneborn_berger);
+    }
+}
+
+/// Orders the standings of a swiss league: the teams with the same points are ordered by Buchholz, then Sonneborn-Berger
+/// and then the same way as `compare_standings`
+fn compare_swiss_standings(a: &TeamStandingTable, b: &TeamStandingTable) -> Ordering {
+    b.total_points
+        .cmp(&a.total_points)
+        .then(b.buchholz.cmp(&a.buchholz))
+        .then(
+            b.sonneborn_berger
+                .partial_cmp(&a.sonneborn_berger)
+                .unwrap_or(Ordering::Equal),
+        )
+        .then(compare_standings(a, b))
+}
+
+/// Orders the standings the same way the standing table query does: most points, best goal difference, most goals scored,
+/// less goals against and finally the team id
+fn compare_standings(a: &TeamStandingTable, b: &TeamStandingTable) -> Ordering {
+    b.total_points
+        .cmp(&a.total_points)
+        .then(b.goal_difference.cmp(&a.goal_difference))
+        .then(b.goals_scored.cmp(&a.goals_scored))
+        .then(a.goals_against.cmp(&b.goals_against))
+        .then(a.team_id.cmp(&b.team_id))
+}
+
+/// Parses the optional `YYYY-MM-DD` date used to filter listings
+///
+/// # Errors
+/// * `HTTPException::BadRequest` - If the date is not on the expected format
+fn parse_date_filter(date: &Option<String>) -> Result<Option<NaiveDate>, HTTPException> {
+    match date {
+        None => Ok(None),
+        Some(value) => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(Some)
+            .or_else(|_error| {
+                Err(HTTPException::BadRequest(format!(
+                    "Invalid date {}. Dates must be on the YYYY-MM-DD format",
+                    value
+                )))
+            }),
+    }
+}
+
+/// Pushes the `FROM`/`WHERE` clauses of the league fixtures listing, so the page and the count use the same filters
+fn push_fixture_list_filters(
+    query: &mut QueryBuilder<'_, MySql>,
+    user_id: u64,
+    tournament_id: u64,
+    league_id: u64,
+    list_query: &FixtureListQuery,
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+) {
+    query.push(
+        "
+            FROM `fixtures` as FixtureRow
+            INNER JOIN `leagues` as LeagueRow
+                ON LeagueRow.id = FixtureRow.league_id
+            INNER JOIN `tournaments` as TournamentRow
+                ON TournamentRow.id = LeagueRow.tournament_id
+            INNER JOIN `teams` as HomeTeamRow
+                ON HomeTeamRow.id = FixtureRow.home_team_id
+            INNER JOIN `teams` as AwayTeamRow
+                ON AwayTeamRow.id = FixtureRow.away_team_id
+            WHERE FixtureRow.league_id = ",
+    );
+    query.push_bind(league_id);
+    query
+        .push(" AND TournamentRow.id = ")
+        .push_bind(tournament_id);
+    query
+        .push(" AND ((TournamentRow.public = FALSE AND TournamentRow.user_id = ")
+        .push_bind(user_id)
+        .push(") OR (TournamentRow.public = TRUE))");
+
+    if let Some(round) = list_query.round {
+        query.push(" AND FixtureRow.round = ").push_bind(round);
+    }
+
+    if let Some(team_id) = list_query.team_id {
+        query
+            .push(" AND (FixtureRow.home_team_id = ")
+            .push_bind(team_id)
+            .push(" OR FixtureRow.away_team_id = ")
+            .push_bind(team_id)
+            .push(")");
+    }
+
+    if let Some(played) = list_query.played {
+        query.push(" AND FixtureRow.played = ").push_bind(played);
+    }
+
+    if let Some(date_from) = date_from {
+        query
+            .push(" AND DATE(FixtureRow.playing_date) >= ")
+            .push_bind(date_from);
+    }
+
+    if let Some(date_to) = date_to {
+        query
+            .push(" AND DATE(FixtureRow.playing_date) <= ")
+            .push_bind(date_to);
+    }
+}
+
+/// Pushes the `FROM`/`WHERE` clauses of the tournament leagues listing, so the page and the count use the same filters
+fn push_league_list_filters(
+    query: &mut QueryBuilder<'_, MySql>,
+    user_id: u64,
+    tournament_id: u64,
+    list_query: &LeagueListQuery,
+) {
+    query.push(
+        "
+            FROM `leagues` as LeagueRow
+            INNER JOIN `tournaments` as TournamentRow
+                ON TournamentRow.id = LeagueRow.tournament_id
+            WHERE LeagueRow.tournament_id = ",
+    );
+    query.push_bind(tournament_id);
+    query
+        .push(" AND ((TournamentRow.public = FALSE AND TournamentRow.user_id = ")
+        .push_bind(user_id)
+        .push(") OR (TournamentRow.public = TRUE))");
+
+    if let Some(name) = &list_query.name {
+        query
+            .push(" AND LeagueRow.name LIKE ")
+            .push_bind(contains_pattern(name));
+    }
+
+    if let Some(completed) = list_query.completed {
+        query
+            .push(" AND LeagueRow.completed = ")
+            .push_bind(completed);
+    }
+}
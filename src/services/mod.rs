@@ -2,4 +2,9 @@ pub mod league_service;
 pub mod team_service;
 pub mod tournament_service;
 pub mod user_service;
-pub mod fixture_service;
\ No newline at end of file
+pub mod fixture_service;
+pub mod bracket_service;
+pub mod tie_service;
+pub mod import_service;
+pub mod export_service;
+pub mod calendar_service;
\ No newline at end of file
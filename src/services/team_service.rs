@@ -1,10 +1,32 @@
-use sqlx::{MySql, Pool};
+use sqlx::{MySql, Pool, QueryBuilder};
 
 use crate::{
-    models::team::{Team, TeamInfoDTO, TeamRegisterDTO},
+    models::{
+        league::League,
+        pagination::{contains_pattern, Pagination, PaginationData, SortOrder},
+        team::{
+            HeadToHeadDTO, HeadToHeadMeeting, MatchResult, Team, TeamFormEntry, TeamInfoDTO,
+            TeamLeagueSummary, TeamListQuery, TeamProfileDTO, TeamRecord, TeamRegisterDTO,
+            TeamSort, TeamUpcomingFixture,
+        },
+        tournament::Tournament,
+    },
     responses::HTTPException,
 };
 
+/// How many of the latest results are shown on the team form
+const TEAM_FORM_SIZE: usize = 5;
+/// How many of the next fixtures are shown on the team profile
+const TEAM_NEXT_FIXTURES_SIZE: usize = 5;
+/// How many of the latest meetings are shown on the head to head
+const HEAD_TO_HEAD_MEETINGS_SIZE: u32 = 5;
+
+#[derive(sqlx::FromRow)]
+struct TeamListRow {
+    id: u64,
+    name: String,
+}
+
 impl Team {
     /// Responsible to create a team on the tournament which is specified by `tournament_id`
     ///
@@ -129,6 +151,83 @@ impl Team {
         })
     }
 
+    /// Responsible to get a page of the tournament teams, filtered and sorted by the query string
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `list_query` - `&TeamListQuery` reference with the pagination, filters and sorting
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok((Vec<TeamInfoDTO>, PaginationData))` - The teams on the page and the pagination information
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the tournament does not exist, or it's private and the user does not own it
+    pub async fn get_tournament_teams_page(
+        user_id: u64,
+        tournament_id: u64,
+        list_query: &TeamListQuery,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(Vec<TeamInfoDTO>, PaginationData), HTTPException> {
+        Tournament::check_tournament_access(Some(user_id), tournament_id, db_pool).await?;
+        let pagination = Pagination::new(list_query.limit, list_query.offset);
+
+        let mut count_query = QueryBuilder::new("SELECT COUNT(*)");
+        push_team_list_filters(&mut count_query, user_id, tournament_id, list_query);
+
+        let total = count_query
+            .build_query_scalar::<i64>()
+            .fetch_one(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to search the teams on the tournament. Please try again later",
+                )))
+            })?;
+
+        let mut query = QueryBuilder::new("SELECT TeamRow.id, TeamRow.name");
+        push_team_list_filters(&mut query, user_id, tournament_id, list_query);
+
+        // The sort column comes from a fixed list, so it's safe to push it into the query
+        let sort_column = match list_query.sort.unwrap_or(TeamSort::Id) {
+            TeamSort::Name => "TeamRow.name",
+            TeamSort::Id => "TeamRow.id",
+        };
+        let sort_order = list_query.order.unwrap_or(SortOrder::Asc);
+        query.push(format!(
+            " ORDER BY {} {}, TeamRow.id ASC",
+            sort_column,
+            sort_order.as_sql()
+        ));
+        query
+            .push(" LIMIT ")
+            .push_bind(pagination.limit)
+            .push(" OFFSET ")
+            .push_bind(pagination.offset);
+
+        let teams = query
+            .build_query_as::<TeamListRow>()
+            .fetch_all(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to search the teams on the tournament. Please try again later",
+                )))
+            })?;
+
+        let teams = teams
+            .into_iter()
+            .map(|team| TeamInfoDTO {
+                id: team.id,
+                name: team.name,
+            })
+            .collect();
+
+        Ok((teams, pagination.with_total(total as u64)))
+    }
+
     /// Responsible to edit the team by it's `id`, `tournament_id` and `user_id`
     ///
     /// # Arguments
@@ -192,9 +291,12 @@ impl Team {
         Ok(())
     }
 
-    /// Responsible to edit the team by it's `id` and `user_id`
+    /// Responsible to delete the team by it's `id` and `user_id`.
+    /// The team fixtures and leagues entries are deleted with it, so it can only be deleted once every league it played is completed.
+    /// The completed leagues still show it on their archive
     ///
     /// # Arguments
+    /// * `tournament_id` - `u64` integer which represents the tournament id
     /// * `user_id` - `u64` integer which represents the id of the user doing the action. The user id must be equal to the tournament user_id row when deleting the team
     /// * `team_id` - `u64` integer which represents the team id that will be deleted
     /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
@@ -204,18 +306,64 @@ impl Team {
     ///
     /// # Errors
     /// * `HTTPException::Internal` - If the database query fails
-    /// * `HTTPException::BadRequest`:
+    /// * `HTTPException::BadRequest`
+    /// * * The team does not exist or the user does not own the tournament
+    /// * * The team is on a league, or has fixtures on a league, which is not completed
     pub async fn delete_team(
         tournament_id: u64,
         user_id: u64,
         team_id: u64,
         db_pool: &Pool<MySql>,
     ) -> Result<(), HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        // Locking the leagues of the team, so they can't be reopened while the team is deleted.
+        // A withdrawn team is not on the league anymore, but its fixtures may still be there
+        let leagues = sqlx::query!(
+            "
+        SELECT LeagueRow.name, LeagueRow.completed
+        FROM `leagues` as LeagueRow
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        WHERE TournamentRow.id = (?) AND TournamentRow.user_id = (?) AND (
+            LeagueRow.id IN (SELECT TeamLeagueRow.league_id FROM `teams_leagues` as TeamLeagueRow WHERE TeamLeagueRow.team_id = (?))
+            OR
+            LeagueRow.id IN (SELECT FixtureRow.league_id FROM `fixtures` as FixtureRow WHERE FixtureRow.home_team_id = (?) OR FixtureRow.away_team_id = (?))
+        )
+        FOR UPDATE
+        ",
+            tournament_id,
+            user_id,
+            team_id,
+            team_id,
+            team_id
+        )
+        .fetch_all(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to delete the team on the database. Please try again later",
+            )))
+        })?;
+
+        // Deleting the team deletes its fixtures, which would change the results of a league still being played
+        if let Some(league) = leagues.iter().find(|league| league.completed == 0) {
+            return Err(HTTPException::BadRequest(format!(
+                "The team plays the league \"{}\" which is not completed. Withdraw the team from the league before deleting it",
+                league.name
+            )));
+        }
+
         let query = sqlx::query!(
             "
         DELETE TeamRow
         FROM `teams`as TeamRow
         INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = TeamRow.tournament_id
         WHERE TeamRow.id = (?)
         AND TeamRow.tournament_id = (?)
         AND TournamentRow.user_id = (?)
@@ -224,7 +372,7 @@ impl Team {
             tournament_id,
             user_id
         )
-        .execute(db_pool)
+        .execute(&mut *transaction)
         .await;
 
         match query {
@@ -240,6 +388,407 @@ impl Team {
             }
         };
 
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to delete the team on the database. Please try again later",
+            )))
+        })?;
+
         Ok(())
     }
+
+    /// Responsible to get the team profile: the leagues it plays with its position on each standings table,
+    /// the latest results, the next fixtures and the record across every league of the tournament
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `team_id` - `u64` integer which represents the team id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(TeamProfileDTO)` - `TeamProfileDTO` struct with the team profile
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - The team or tournament does not exist, or the tournament is private and the user does not own it
+    pub async fn get_team_profile(
+        user_id: u64,
+        tournament_id: u64,
+        team_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<TeamProfileDTO, HTTPException> {
+        // It also makes sure the user can access the tournament, so the queries below don't need to check it again
+        let team = Team::get_team_by_id_and_tournament_id(user_id, tournament_id, team_id, db_pool)
+            .await?;
+
+        let leagues = sqlx::query!(
+            "
+            SELECT LeagueRow.id, LeagueRow.name, LeagueRow.completed
+            FROM `leagues` as LeagueRow
+            INNER JOIN `teams_leagues` as TeamLeagueRow
+                ON TeamLeagueRow.league_id = LeagueRow.id
+            WHERE TeamLeagueRow.team_id = (?) AND LeagueRow.tournament_id = (?)
+            ORDER BY LeagueRow.id ASC
+        ",
+            team_id,
+            tournament_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the team leagues. Please try again later",
+            )))
+        })?;
+
+        let mut league_summaries = vec![];
+        for league in leagues {
+            let standing_table =
+                League::get_league_standing_table(league.id, user_id, tournament_id, None, db_pool)
+                    .await?;
+
+            let team_standing = standing_table
+                .overall
+                .into_iter()
+                .enumerate()
+                .find(|(_, standing)| standing.team_id == team_id);
+
+            league_summaries.push(TeamLeagueSummary {
+                id: league.id,
+                name: league.name,
+                completed: league.completed == 1,
+                position: team_standing.as_ref().map(|(index, _)| *index as u32 + 1),
+                standing: team_standing.map(|(_, standing)| standing),
+            });
+        }
+
+        let fixtures = sqlx::query!(
+            "
+            SELECT FixtureRow.id, FixtureRow.league_id, FixtureRow.round, FixtureRow.playing_date,
+            FixtureRow.home_team_id, FixtureRow.away_team_id, FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played,
+            HomeTeamRow.name as home_team_name, AwayTeamRow.name as away_team_name
+            FROM `fixtures` as FixtureRow
+            INNER JOIN `leagues` as LeagueRow
+                ON LeagueRow.id = FixtureRow.league_id
+            INNER JOIN `teams` as HomeTeamRow
+                ON HomeTeamRow.id = FixtureRow.home_team_id
+            INNER JOIN `teams` as AwayTeamRow
+                ON AwayTeamRow.id = FixtureRow.away_team_id
+            WHERE LeagueRow.tournament_id = (?)
+            AND (FixtureRow.home_team_id = (?) OR FixtureRow.away_team_id = (?))
+            ORDER BY FixtureRow.playing_date ASC, FixtureRow.round ASC, FixtureRow.id ASC
+        ",
+            tournament_id,
+            team_id,
+            team_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the team fixtures. Please try again later",
+            )))
+        })?;
+
+        let mut record = TeamRecord::default();
+        let mut form = vec![];
+        let mut next_fixtures = vec![];
+
+        // The fixtures are ordered from the oldest to the newest one
+        for fixture in fixtures {
+            let home = fixture.home_team_id == team_id;
+            let opponent = if home {
+                TeamInfoDTO {
+                    id: fixture.away_team_id,
+                    name: fixture.away_team_name,
+                }
+            } else {
+                TeamInfoDTO {
+                    id: fixture.home_team_id,
+                    name: fixture.home_team_name,
+                }
+            };
+
+            if fixture.played == 1 {
+                let (goals_scored, goals_against) = if home {
+                    (fixture.home_score, fixture.away_score)
+                } else {
+                    (fixture.away_score, fixture.home_score)
+                };
+
+                form.push(TeamFormEntry {
+                    fixture_id: fixture.id,
+                    league_id: fixture.league_id,
+                    round: fixture.round,
+                    opponent,
+                    home,
+                    goals_scored,
+                    goals_against,
+                    result: add_result_to_record(&mut record, goals_scored, goals_against),
+                });
+            } else if next_fixtures.len() < TEAM_NEXT_FIXTURES_SIZE {
+                next_fixtures.push(TeamUpcomingFixture {
+                    fixture_id: fixture.id,
+                    league_id: fixture.league_id,
+                    round: fixture.round,
+                    opponent,
+                    home,
+                    playing_date: fixture.playing_date,
+                });
+            }
+        }
+
+        // Keeping only the latest results, the most recent one first
+        form.reverse();
+        form.truncate(TEAM_FORM_SIZE);
+
+        Ok(TeamProfileDTO {
+            id: team.id,
+            name: team.name,
+            leagues: league_summaries,
+            form,
+            next_fixtures,
+            record,
+        })
+    }
+
+    /// Responsible to compare two teams by the fixtures they played against each other on every league of the tournament
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the user id
+    /// * `tournament_id` - `u64` integer which represents the tournament id
+    /// * `team_id` - `u64` integer which represents the team id
+    /// * `opponent_id` - `u64` integer which represents the id of the team it's compared against
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(HeadToHeadDTO)` - `HeadToHeadDTO` struct with the record of both teams and their last meetings
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest`:
+    /// * * Both ids are the same team
+    /// * * One of the teams or the tournament does not exist, or the tournament is private and the user does not own it
+    pub async fn get_head_to_head(
+        user_id: u64,
+        tournament_id: u64,
+        team_id: u64,
+        opponent_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<HeadToHeadDTO, HTTPException> {
+        if team_id == opponent_id {
+            return Err(HTTPException::BadRequest(String::from(
+                "Cannot compare a team against itself",
+            )));
+        }
+
+        // It also makes sure the user can access the tournament, so the queries below don't need to check it again
+        let team = Team::get_team_by_id_and_tournament_id(user_id, tournament_id, team_id, db_pool)
+            .await?;
+        let opponent =
+            Team::get_team_by_id_and_tournament_id(user_id, tournament_id, opponent_id, db_pool)
+                .await?;
+
+        // The same aggregation as the league standing table, but only with the played fixtures between both teams,
+        // on every league of the tournament
+        let records = sqlx::query!(
+            r#"
+            SELECT team_id,
+            CAST(SUM(win) as UNSIGNED) as win,
+            CAST(SUM(draw) as UNSIGNED) as draw,
+            CAST(SUM(loss) as UNSIGNED) as loss,
+            CAST(SUM(goals_scored) as UNSIGNED) as goals_scored,
+            CAST(SUM(goals_against) as UNSIGNED) as goals_against,
+            CAST((SUM(goals_scored) - SUM(goals_against)) as SIGNED) as goal_difference
+            FROM (
+                -- The results from the home side of each fixture
+                (
+                    SELECT FixtureRow.home_team_id as team_id,
+                    CASE WHEN FixtureRow.home_score > FixtureRow.away_score THEN 1 ELSE 0 END as win,
+                    CASE WHEN FixtureRow.home_score = FixtureRow.away_score THEN 1 ELSE 0 END as draw,
+                    CASE WHEN FixtureRow.home_score < FixtureRow.away_score THEN 1 ELSE 0 END as loss,
+                    FixtureRow.home_score as goals_scored,
+                    FixtureRow.away_score as goals_against
+                    FROM `fixtures` as FixtureRow
+                    INNER JOIN `leagues` as LeagueRow
+                        ON LeagueRow.id = FixtureRow.league_id
+                    WHERE LeagueRow.tournament_id = (?) AND FixtureRow.played = TRUE
+                    AND FixtureRow.home_team_id IN (?, ?) AND FixtureRow.away_team_id IN (?, ?)
+                )
+                UNION ALL
+                -- The results from the away side of each fixture
+                (
+                    SELECT FixtureRow.away_team_id as team_id,
+                    CASE WHEN FixtureRow.away_score > FixtureRow.home_score THEN 1 ELSE 0 END as win,
+                    CASE WHEN FixtureRow.away_score = FixtureRow.home_score THEN 1 ELSE 0 END as draw,
+                    CASE WHEN FixtureRow.away_score < FixtureRow.home_score THEN 1 ELSE 0 END as loss,
+                    FixtureRow.away_score as goals_scored,
+                    FixtureRow.home_score as goals_against
+                    FROM `fixtures` as FixtureRow
+                    INNER JOIN `leagues` as LeagueRow
+                        ON LeagueRow.id = FixtureRow.league_id
+                    WHERE LeagueRow.tournament_id = (?) AND FixtureRow.played = TRUE
+                    AND FixtureRow.home_team_id IN (?, ?) AND FixtureRow.away_team_id IN (?, ?)
+                )
+            ) as SubQuery
+            GROUP BY team_id
+        "#,
+            tournament_id,
+            team_id,
+            opponent_id,
+            team_id,
+            opponent_id,
+            tournament_id,
+            team_id,
+            opponent_id,
+            team_id,
+            opponent_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to compare the teams. Please try again later",
+            )))
+        })?;
+
+        let mut team_record = TeamRecord::default();
+        let mut opponent_record = TeamRecord::default();
+        for record in records {
+            let win = record.win.unwrap_or(0) as u32;
+            let draw = record.draw.unwrap_or(0) as u32;
+            let loss = record.loss.unwrap_or(0) as u32;
+
+            let aggregated_record = TeamRecord {
+                played: win + draw + loss,
+                win,
+                draw,
+                loss,
+                goals_scored: record.goals_scored.unwrap_or(0) as u32,
+                goals_against: record.goals_against.unwrap_or(0) as u32,
+                goal_difference: record.goal_difference.unwrap_or(0) as i32,
+            };
+
+            if record.team_id == team_id {
+                team_record = aggregated_record;
+            } else {
+                opponent_record = aggregated_record;
+            }
+        }
+
+        let meetings = sqlx::query!(
+            "
+            SELECT FixtureRow.id, FixtureRow.league_id, LeagueRow.name as league_name, FixtureRow.round, FixtureRow.playing_date,
+            FixtureRow.home_team_id, FixtureRow.away_team_id, FixtureRow.home_score, FixtureRow.away_score,
+            HomeTeamRow.name as home_team_name, AwayTeamRow.name as away_team_name
+            FROM `fixtures` as FixtureRow
+            INNER JOIN `leagues` as LeagueRow
+                ON LeagueRow.id = FixtureRow.league_id
+            INNER JOIN `teams` as HomeTeamRow
+                ON HomeTeamRow.id = FixtureRow.home_team_id
+            INNER JOIN `teams` as AwayTeamRow
+                ON AwayTeamRow.id = FixtureRow.away_team_id
+            WHERE LeagueRow.tournament_id = (?) AND FixtureRow.played = TRUE
+            AND FixtureRow.home_team_id IN (?, ?) AND FixtureRow.away_team_id IN (?, ?)
+            ORDER BY FixtureRow.playing_date DESC, FixtureRow.round DESC, FixtureRow.id DESC
+            LIMIT ?
+        ",
+            tournament_id,
+            team_id,
+            opponent_id,
+            team_id,
+            opponent_id,
+            HEAD_TO_HEAD_MEETINGS_SIZE
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the meetings between the teams. Please try again later",
+            )))
+        })?;
+
+        let last_meetings = meetings
+            .into_iter()
+            .map(|meeting| HeadToHeadMeeting {
+                fixture_id: meeting.id,
+                league_id: meeting.league_id,
+                league_name: meeting.league_name,
+                round: meeting.round,
+                home_team: TeamInfoDTO {
+                    id: meeting.home_team_id,
+                    name: meeting.home_team_name,
+                },
+                away_team: TeamInfoDTO {
+                    id: meeting.away_team_id,
+                    name: meeting.away_team_name,
+                },
+                home_score: meeting.home_score,
+                away_score: meeting.away_score,
+                playing_date: meeting.playing_date,
+            })
+            .collect();
+
+        Ok(HeadToHeadDTO {
+            team,
+            opponent,
+            team_record,
+            opponent_record,
+            last_meetings,
+        })
+    }
+}
+
+/// Adds a played fixture to the team record
+///
+/// # Returns
+/// * `MatchResult` - The fixture result from the point of view of the team
+fn add_result_to_record(
+    record: &mut TeamRecord,
+    goals_scored: u8,
+    goals_against: u8,
+) -> MatchResult {
+    record.played += 1;
+    record.goals_scored += goals_scored as u32;
+    record.goals_against += goals_against as u32;
+    record.goal_difference = record.goals_scored as i32 - record.goals_against as i32;
+
+    if goals_scored > goals_against {
+        record.win += 1;
+        MatchResult::Win
+    } else if goals_scored == goals_against {
+        record.draw += 1;
+        MatchResult::Draw
+    } else {
+        record.loss += 1;
+        MatchResult::Loss
+    }
+}
+
+/// Pushes the `FROM`/`WHERE` clauses of the tournament teams listing, so the page and the count use the same filters
+fn push_team_list_filters(
+    query: &mut QueryBuilder<'_, MySql>,
+    user_id: u64,
+    tournament_id: u64,
+    list_query: &TeamListQuery,
+) {
+    query.push(
+        "
+            FROM `teams` as TeamRow
+            INNER JOIN `tournaments` as TournamentRow
+                ON TournamentRow.id = TeamRow.tournament_id
+            WHERE TeamRow.tournament_id = ",
+    );
+    query.push_bind(tournament_id);
+    query
+        .push(" AND ((TournamentRow.public = FALSE AND TournamentRow.user_id = ")
+        .push_bind(user_id)
+        .push(") OR (TournamentRow.public = TRUE))");
+
+    if let Some(name) = &list_query.name {
+        query
+            .push(" AND TeamRow.name LIKE ")
+            .push_bind(contains_pattern(name));
+    }
 }
@@ -0,0 +1,816 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use sqlx::{MySql, Pool, Transaction};
+
+use crate::{
+    constants::MYSQL_CUSTOM_ERROR,
+    models::{
+        bracket::BracketMatch,
+        fixture::{Fixture, FixtureDataDTO, NewFixtureDTO},
+        league::League,
+        tie::{LegScore, NewTieDTO, Tie, TieAggregate, TieDTO, TieDecider, TieExtraTimeDTO},
+    },
+    responses::HTTPException,
+};
+
+/// A `ties` row, the booleans are read as integers
+struct TieRow {
+    id: u64,
+    league_id: u64,
+    first_leg_id: u64,
+    second_leg_id: u64,
+    away_goals: i8,
+    extra_time_home_score: Option<u8>,
+    extra_time_away_score: Option<u8>,
+    penalties_home_score: Option<u8>,
+    penalties_away_score: Option<u8>,
+}
+
+impl From<TieRow> for Tie {
+    fn from(row: TieRow) -> Self {
+        Tie {
+            id: row.id,
+            league_id: row.league_id,
+            first_leg_id: row.first_leg_id,
+            second_leg_id: row.second_leg_id,
+            away_goals: row.away_goals == 1,
+            extra_time_home_score: row.extra_time_home_score,
+            extra_time_away_score: row.extra_time_away_score,
+            penalties_home_score: row.penalties_home_score,
+            penalties_away_score: row.penalties_away_score,
+        }
+    }
+}
+
+/// Responsible to create a tie leg on the league
+///
+/// # Returns
+/// * `Ok(u64)` - The id of the created fixture
+///
+/// # Errors
+/// * `HTTPException::BadRequest` - If a team is not on the league or the user doesn't have permission to create the fixture
+/// * `HTTPException::Internal` - If the database query fails
+async fn insert_leg(
+    user_id: u64,
+    league_id: u64,
+    home_team_id: u64,
+    away_team_id: u64,
+    round: u16,
+    transaction: &mut Transaction<'_, MySql>,
+) -> Result<u64, HTTPException> {
+    sqlx::query!(
+        "
+    CALL generate_fixture(?, ?, ?, ?, ?)
+    ",
+        home_team_id,
+        away_team_id,
+        league_id,
+        round,
+        user_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .or_else(|exception| {
+        if let Some(database_error) = exception.as_database_error() {
+            if let Some(code) = database_error.code() {
+                if code == MYSQL_CUSTOM_ERROR {
+                    let message = database_error.message();
+                    return Err(HTTPException::BadRequest(message.to_string()));
+                }
+            }
+        }
+
+        Err(HTTPException::Internal(String::from(
+            "Failed while creating the tie. Please try again later",
+        )))
+    })?;
+
+    let fixture_id = sqlx::query!("SELECT LAST_INSERT_ID() as id")
+        .fetch_one(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while creating the tie. Please try again later",
+            )))
+        })?
+        .id;
+
+    Ok(fixture_id)
+}
+
+impl Tie {
+    /// Responsible to compute the aggregate score of the tie and the team which advances.
+    /// The aggregate decides first, then the away goals when the tie allows them. When it's still level after both legs,
+    /// the second leg extra time goals are added, scored away by the second leg away team, and at last the penalties decide
+    ///
+    /// # Arguments
+    /// * `first_leg` - The first leg teams and score
+    /// * `second_leg` - The second leg teams and score
+    ///
+    /// # Returns
+    /// * `TieAggregate` - The aggregate score, the winner is only present once both legs are played and the tie is decided
+    pub fn aggregate(&self, first_leg: &LegScore, second_leg: &LegScore) -> TieAggregate {
+        let home_team_id = first_leg.home_team_id;
+        let away_team_id = first_leg.away_team_id;
+
+        // The goals of a team on both legs and how many of them were scored away
+        let goals = |team_id: u64| {
+            [first_leg, second_leg]
+                .iter()
+                .filter(|leg| leg.played)
+                .fold((0, 0), |(total, away), leg| {
+                    if leg.home_team_id == team_id {
+                        (total + leg.home_score as u16, away)
+                    } else if leg.away_team_id == team_id {
+                        (total + leg.away_score as u16, away + leg.away_score as u16)
+                    } else {
+                        (total, away)
+                    }
+                })
+        };
+        let (mut home_score, mut home_away_goals) = goals(home_team_id);
+        let (mut away_score, mut away_away_goals) = goals(away_team_id);
+
+        let decide = |home: u16, away: u16| match home.cmp(&away) {
+            Ordering::Greater => Some(home_team_id),
+            Ordering::Less => Some(away_team_id),
+            Ordering::Equal => None,
+        };
+        // The extra time and penalties scores are counted by the second leg teams
+        let first_leg_teams = |second_leg_home: u16, second_leg_away: u16| {
+            if second_leg.home_team_id == home_team_id {
+                (second_leg_home, second_leg_away)
+            } else {
+                (second_leg_away, second_leg_home)
+            }
+        };
+
+        let mut decision = None;
+        if first_leg.played && second_leg.played {
+            decision = decide(home_score, away_score).map(|winner| (winner, TieDecider::Aggregate));
+            if decision.is_none() && self.away_goals {
+                decision = decide(home_away_goals, away_away_goals)
+                    .map(|winner| (winner, TieDecider::AwayGoals));
+            }
+
+            if let (None, Some(extra_time_home), Some(extra_time_away)) = (
+                decision,
+                self.extra_time_home_score,
+                self.extra_time_away_score,
+            ) {
+                let (home_extra_time, away_extra_time) =
+                    first_leg_teams(extra_time_home as u16, extra_time_away as u16);
+                home_score += home_extra_time;
+                away_score += away_extra_time;
+                if second_leg.away_team_id == home_team_id {
+                    home_away_goals += home_extra_time;
+                } else {
+                    away_away_goals += away_extra_time;
+                }
+
+                decision =
+                    decide(home_score, away_score).map(|winner| (winner, TieDecider::ExtraTime));
+                if decision.is_none() && self.away_goals {
+                    decision = decide(home_away_goals, away_away_goals)
+                        .map(|winner| (winner, TieDecider::AwayGoals));
+                }
+
+                if let (None, Some(penalties_home), Some(penalties_away)) = (
+                    decision,
+                    self.penalties_home_score,
+                    self.penalties_away_score,
+                ) {
+                    let (home_penalties, away_penalties) =
+                        first_leg_teams(penalties_home as u16, penalties_away as u16);
+                    decision = decide(home_penalties, away_penalties)
+                        .map(|winner| (winner, TieDecider::Penalties));
+                }
+            }
+        }
+
+        TieAggregate {
+            home_team_id,
+            home_score,
+            away_team_id,
+            away_score,
+            winner_team_id: decision.map(|(winner, _)| winner),
+            decided_by: decision.map(|(_, decided_by)| decided_by),
+        }
+    }
+
+    /// Responsible to create a tie, both of its legs are created on the league.
+    /// The second leg is played at the first leg away team ground
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `tie_data` - A `NewTieDTO` struct with the teams and the rounds of both legs
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The id of the created tie
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * The user doesn't have permission to create the tie or the league is completed
+    /// * * The second leg is not played after the first leg
+    /// * * The teams are not on the league, they're the same team or one of them already plays on the rounds
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn create_tie(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        tie_data: NewTieDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<u64, HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+
+        if tie_data.second_leg_round <= tie_data.first_leg_round {
+            return Err(HTTPException::BadRequest(String::from(
+                "The second leg must be played on a round after the first leg",
+            )));
+        }
+
+        let legs = [
+            NewFixtureDTO {
+                home_team_id: tie_data.home_team_id,
+                away_team_id: tie_data.away_team_id,
+                round: tie_data.first_leg_round,
+            },
+            NewFixtureDTO {
+                home_team_id: tie_data.away_team_id,
+                away_team_id: tie_data.home_team_id,
+                round: tie_data.second_leg_round,
+            },
+        ];
+        for leg in &legs {
+            Fixture::check_fixture_pairing(user_id, tournament_id, league_id, None, leg, db_pool)
+                .await?;
+        }
+
+        let tie = Tie::insert_tie(
+            user_id,
+            league_id,
+            tie_data.home_team_id,
+            tie_data.away_team_id,
+            (tie_data.first_leg_round, tie_data.second_leg_round),
+            tie_data.away_goals,
+            &mut transaction,
+        )
+        .await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while creating the tie. Please try again later",
+            )))
+        })?;
+
+        Ok(tie.id)
+    }
+
+    /// Responsible to create both legs of a tie and the tie itself on a transaction, the pairing must be checked before
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `league_id` - The league id
+    /// * `home_team_id` - The first leg home team
+    /// * `away_team_id` - The first leg away team
+    /// * `rounds` - The rounds of the first and the second legs
+    /// * `away_goals` - If the away goals decide when the aggregate is level
+    /// * `transaction` - The transaction which the tie is saved on
+    ///
+    /// # Returns
+    /// * `Ok(Tie)` - The created tie
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If a team is not on the league or the user doesn't have permission to create the legs
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn insert_tie(
+        user_id: u64,
+        league_id: u64,
+        home_team_id: u64,
+        away_team_id: u64,
+        rounds: (u16, u16),
+        away_goals: bool,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<Tie, HTTPException> {
+        let first_leg_id = insert_leg(
+            user_id,
+            league_id,
+            home_team_id,
+            away_team_id,
+            rounds.0,
+            transaction,
+        )
+        .await?;
+        let second_leg_id = insert_leg(
+            user_id,
+            league_id,
+            away_team_id,
+            home_team_id,
+            rounds.1,
+            transaction,
+        )
+        .await?;
+
+        sqlx::query!(
+            "
+        INSERT INTO `ties` (`league_id`, `first_leg_id`, `second_leg_id`, `away_goals`)
+        VALUES (?, ?, ?, ?)
+        ",
+            league_id,
+            first_leg_id,
+            second_leg_id,
+            away_goals
+        )
+        .execute(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while creating the tie. Please try again later",
+            )))
+        })?;
+
+        let tie_id = sqlx::query!("SELECT LAST_INSERT_ID() as id")
+            .fetch_one(&mut **transaction)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed while creating the tie. Please try again later",
+                )))
+            })?
+            .id;
+
+        Ok(Tie {
+            id: tie_id,
+            league_id,
+            first_leg_id,
+            second_leg_id,
+            away_goals,
+            extra_time_home_score: None,
+            extra_time_away_score: None,
+            penalties_home_score: None,
+            penalties_away_score: None,
+        })
+    }
+
+    /// Responsible to get the ties of a league with their legs and aggregate scores
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(Vec<TieDTO>)` - The ties ordered by their id
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_league_ties(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<Vec<TieDTO>, HTTPException> {
+        let ties: Vec<Tie> = sqlx::query_as!(
+            TieRow,
+            "
+        SELECT TieRow.id, TieRow.league_id, TieRow.first_leg_id, TieRow.second_leg_id, TieRow.away_goals,
+            TieRow.extra_time_home_score, TieRow.extra_time_away_score,
+            TieRow.penalties_home_score, TieRow.penalties_away_score
+        FROM `ties` as TieRow
+        INNER JOIN `leagues` as LeagueRow
+            ON LeagueRow.id = TieRow.league_id
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        WHERE TieRow.league_id = (?) AND TournamentRow.id = (?) AND
+        (
+            (TournamentRow.public = FALSE AND TournamentRow.user_id = (?))
+            OR
+            (TournamentRow.public = TRUE)
+        )
+        ORDER BY TieRow.id
+        ",
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .fetch_all(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league ties. Please try again later",
+            )))
+        })?
+        .into_iter()
+        .map(Tie::from)
+        .collect();
+
+        if ties.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut fixtures: HashMap<u64, FixtureDataDTO> =
+            League::get_league_fixtures(user_id, tournament_id, league_id, db_pool)
+                .await?
+                .into_iter()
+                .map(|fixture| (fixture.id, fixture))
+                .collect();
+
+        // A leg only belongs to one tie
+        let ties = ties
+            .into_iter()
+            .filter_map(|tie| {
+                let first_leg = fixtures.remove(&tie.first_leg_id)?;
+                let second_leg = fixtures.remove(&tie.second_leg_id)?;
+                let aggregate =
+                    tie.aggregate(&LegScore::from(&first_leg), &LegScore::from(&second_leg));
+
+                Some(TieDTO {
+                    id: tie.id,
+                    first_leg,
+                    second_leg,
+                    away_goals: tie.away_goals,
+                    extra_time_home_score: tie.extra_time_home_score,
+                    extra_time_away_score: tie.extra_time_away_score,
+                    penalties_home_score: tie.penalties_home_score,
+                    penalties_away_score: tie.penalties_away_score,
+                    aggregate,
+                })
+            })
+            .collect();
+
+        Ok(ties)
+    }
+
+    /// Responsible to get a tie of a league with its legs and aggregate score
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `tie_id` - The tie id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(TieDTO)` - The tie
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the tie doesn't exist or the user doesn't have permission to access it
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_tie_by_id(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        tie_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<TieDTO, HTTPException> {
+        Tie::get_league_ties(user_id, tournament_id, league_id, db_pool)
+            .await?
+            .into_iter()
+            .find(|tie| tie.id == tie_id)
+            .ok_or_else(|| {
+                HTTPException::BadRequest(String::from(
+                    "Failed to get the tie. Please make sure it exists and you have permission to access it",
+                ))
+            })
+    }
+
+    /// Responsible to get the ties of a league on a transaction, used to resolve the two legged bracket matches
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `transaction` - The transaction which the ties are read on
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_ties_from_league(
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<Vec<Tie>, HTTPException> {
+        let ties = sqlx::query_as!(
+            TieRow,
+            "
+        SELECT TieRow.id, TieRow.league_id, TieRow.first_leg_id, TieRow.second_leg_id, TieRow.away_goals,
+            TieRow.extra_time_home_score, TieRow.extra_time_away_score,
+            TieRow.penalties_home_score, TieRow.penalties_away_score
+        FROM `ties` as TieRow
+        WHERE TieRow.league_id = (?)
+        ",
+            league_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league ties. Please try again later",
+            )))
+        })?
+        .into_iter()
+        .map(Tie::from)
+        .collect();
+
+        Ok(ties)
+    }
+
+    /// Responsible to get the teams and score of every fixture of a league on a transaction, used to compute the ties aggregates
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `transaction` - The transaction which the fixtures are read on
+    ///
+    /// # Returns
+    /// * `Ok(HashMap<u64, LegScore>)` - The fixtures indexed by their id
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_leg_scores_from_league(
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<HashMap<u64, LegScore>, HTTPException> {
+        let fixtures = sqlx::query!(
+            "
+        SELECT FixtureRow.id, FixtureRow.home_team_id, FixtureRow.away_team_id,
+            FixtureRow.home_score, FixtureRow.away_score, FixtureRow.played
+        FROM `fixtures` as FixtureRow
+        WHERE FixtureRow.league_id = (?)
+        ",
+            league_id
+        )
+        .fetch_all(&mut **transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league fixtures. Please try again later",
+            )))
+        })?
+        .into_iter()
+        .map(|fixture| {
+            (
+                fixture.id,
+                LegScore {
+                    home_team_id: fixture.home_team_id,
+                    away_team_id: fixture.away_team_id,
+                    home_score: fixture.home_score,
+                    away_score: fixture.away_score,
+                    played: fixture.played == 1,
+                },
+            )
+        })
+        .collect();
+
+        Ok(fixtures)
+    }
+
+    /// Responsible to check every tie of a league is decided on a transaction.
+    /// A tie level after both legs is only decided once its extra time or penalties are set
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `transaction` - The transaction which the ties are read on
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - `true` when every tie has a winner, or the league has no tie
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn check_ties_decided(
+        league_id: u64,
+        transaction: &mut Transaction<'_, MySql>,
+    ) -> Result<bool, HTTPException> {
+        let ties = Tie::get_ties_from_league(league_id, transaction).await?;
+        if ties.is_empty() {
+            return Ok(true);
+        }
+
+        let fixtures = Tie::get_leg_scores_from_league(league_id, transaction).await?;
+
+        Ok(ties.iter().all(|tie| {
+            match (
+                fixtures.get(&tie.first_leg_id),
+                fixtures.get(&tie.second_leg_id),
+            ) {
+                (Some(first_leg), Some(second_leg)) => tie
+                    .aggregate(first_leg, second_leg)
+                    .winner_team_id
+                    .is_some(),
+                _ => false,
+            }
+        }))
+    }
+
+    /// Responsible to set the second leg extra time and penalties of a tie.
+    /// The extra time is only played when the tie is level after both legs, and the penalties when it's still level after the extra time.
+    /// Deciding the tie completes the league when it was the last pending result
+    ///
+    /// # Arguments
+    /// * `user_id` - The user id
+    /// * `tournament_id` - The tournament id
+    /// * `league_id` - The league id
+    /// * `tie_id` - The tie id
+    /// * `extra_time_data` - A `TieExtraTimeDTO` struct with the extra time and penalties scores
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest`
+    /// * * The tie doesn't exist, the user doesn't have permission to edit it or the league is completed
+    /// * * A score is missing, or the extra time or penalties can't be played
+    /// * * The winner of a bracket tie changes after the next bracket matches are played
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn edit_tie_extra_time(
+        user_id: u64,
+        tournament_id: u64,
+        league_id: u64,
+        tie_id: u64,
+        extra_time_data: TieExtraTimeDTO,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        League::check_league_is_open(user_id, tournament_id, league_id, &mut transaction).await?;
+
+        if extra_time_data.extra_time_home_score.is_some()
+            != extra_time_data.extra_time_away_score.is_some()
+            || extra_time_data.penalties_home_score.is_some()
+                != extra_time_data.penalties_away_score.is_some()
+        {
+            return Err(HTTPException::BadRequest(String::from(
+                "The extra time and penalties need the scores of both teams",
+            )));
+        }
+
+        if extra_time_data.penalties_home_score.is_some()
+            && extra_time_data.extra_time_home_score.is_none()
+        {
+            return Err(HTTPException::BadRequest(String::from(
+                "The penalties are only taken after the extra time",
+            )));
+        }
+
+        let tie_data =
+            Tie::get_tie_by_id(user_id, tournament_id, league_id, tie_id, db_pool).await?;
+        let first_leg = LegScore::from(&tie_data.first_leg);
+        let second_leg = LegScore::from(&tie_data.second_leg);
+
+        let tie = Tie {
+            id: tie_data.id,
+            league_id,
+            first_leg_id: tie_data.first_leg.id,
+            second_leg_id: tie_data.second_leg.id,
+            away_goals: tie_data.away_goals,
+            extra_time_home_score: extra_time_data.extra_time_home_score,
+            extra_time_away_score: extra_time_data.extra_time_away_score,
+            penalties_home_score: extra_time_data.penalties_home_score,
+            penalties_away_score: extra_time_data.penalties_away_score,
+        };
+        let without_penalties = Tie {
+            penalties_home_score: None,
+            penalties_away_score: None,
+            ..tie.clone()
+        };
+        let without_extra_time = Tie {
+            extra_time_home_score: None,
+            extra_time_away_score: None,
+            ..without_penalties.clone()
+        };
+
+        if tie.extra_time_home_score.is_some() {
+            let both_legs_played = first_leg.played && second_leg.played;
+            if !both_legs_played
+                || without_extra_time
+                    .aggregate(&first_leg, &second_leg)
+                    .winner_team_id
+                    .is_some()
+            {
+                return Err(HTTPException::BadRequest(String::from(
+                    "The extra time is only played when the tie is level after both legs",
+                )));
+            }
+        }
+
+        if tie.penalties_home_score.is_some() {
+            if without_penalties
+                .aggregate(&first_leg, &second_leg)
+                .winner_team_id
+                .is_some()
+            {
+                return Err(HTTPException::BadRequest(String::from(
+                    "The penalties are only taken when the tie is level after the extra time",
+                )));
+            }
+
+            if tie.penalties_home_score == tie.penalties_away_score {
+                return Err(HTTPException::BadRequest(String::from(
+                    "A penalty shoot-out can't end on a draw",
+                )));
+            }
+        }
+
+        if tie.aggregate(&first_leg, &second_leg).winner_team_id
+            != tie_data.aggregate.winner_team_id
+        {
+            BracketMatch::check_bracket_tie(league_id, tie.first_leg_id, db_pool).await?;
+        }
+
+        let query = sqlx::query!(
+            "
+        UPDATE `ties` as TieRow
+        INNER JOIN `leagues` as LeagueRow
+            ON LeagueRow.id = TieRow.league_id
+        INNER JOIN `tournaments` as TournamentRow
+            ON TournamentRow.id = LeagueRow.tournament_id
+        SET TieRow.extra_time_home_score = (?), TieRow.extra_time_away_score = (?),
+            TieRow.penalties_home_score = (?), TieRow.penalties_away_score = (?)
+        WHERE TieRow.id = (?) AND LeagueRow.id = (?) AND TournamentRow.id = (?) AND TournamentRow.user_id = (?)
+        ",
+            tie.extra_time_home_score,
+            tie.extra_time_away_score,
+            tie.penalties_home_score,
+            tie.penalties_away_score,
+            tie_id,
+            league_id,
+            tournament_id,
+            user_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to edit the tie. Please try again later",
+            )))
+        })?;
+
+        if query.rows_affected() < 1 {
+            return Err(HTTPException::BadRequest(String::from(
+                "Failed to edit the tie. Please make sure it exists and you have permission to access it",
+            )));
+        }
+
+        // The winner of a two legged bracket match moves to its next match
+        BracketMatch::advance_bracket_matches(user_id, league_id, &mut transaction).await?;
+        League::complete_league_if_finished(user_id, tournament_id, league_id, &mut transaction)
+            .await?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to edit the tie. Please try again later",
+            )))
+        })?;
+
+        Ok(())
+    }
+
+    /// Responsible to refuse changing the teams of a tie leg by hand, both legs must keep the same teams
+    ///
+    /// # Arguments
+    /// * `league_id` - The league id
+    /// * `fixture_id` - The fixture id
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Errors
+    /// * `HTTPException::BadRequest` - If the fixture is a tie leg
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn check_not_tie_leg(
+        league_id: u64,
+        fixture_id: u64,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(), HTTPException> {
+        let tie = sqlx::query!(
+            "
+        SELECT TieRow.id
+        FROM `ties` as TieRow
+        WHERE TieRow.league_id = (?) AND (TieRow.first_leg_id = (?) OR TieRow.second_leg_id = (?))
+        ",
+            league_id,
+            fixture_id,
+            fixture_id
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to get the league ties. Please try again later",
+            )))
+        })?;
+
+        if tie.is_some() {
+            return Err(HTTPException::BadRequest(String::from(
+                "The legs of a tie are played by the same teams, so their teams can't be changed",
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,25 @@
 use sqlx::{MySql, Pool, QueryBuilder};
 
-use crate::{models::tournament::{Tournament, TournamentRegisterDTO, TournamentEditDTO, TournamentInformationData, LeagueInformationData, TeamInformationData}, responses::HTTPException};
+use crate::{
+    models::{
+        pagination::{contains_pattern, Pagination, PaginationData, SortOrder},
+        tournament::{
+            LeagueInformationData, TeamInformationData, Tournament, TournamentEditDTO,
+            TournamentInformationData, TournamentListQuery, TournamentRegisterDTO, TournamentSort,
+            TournamentSummaryData,
+        },
+    },
+    responses::HTTPException,
+};
+
+#[derive(sqlx::FromRow)]
+struct TournamentListRow {
+    id: u64,
+    name: String,
+    public: bool,
+    leagues_total: i64,
+    teams_total: i64,
+}
 
 impl Tournament {
     /// Responsible to create a new tournament for a user. If the operation succeeds it'll be returned a `Ok(u64)` value that represents the id of the new created tournament
@@ -172,7 +191,7 @@ impl Tournament {
         }
     }
 
-    /// Gets the tournament information data. Such as leagues and teams associated with
+    /// Responsible to check the tournament exists and the user can access it, which is when the tournament is public or the user owns it
     ///
     /// # Arguments
     /// * `user_id` - `Option<u64>` value which represents the user_id, if existing
@@ -180,16 +199,16 @@ impl Tournament {
     /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
     ///
     /// # Returns
-    /// `Ok(TournamentInformationData)` - `TournamentInformationData` struct with the tournament, leagues and teams information
+    /// `Ok(String)` - The tournament name
     ///
     /// # Error
     /// `HTTPException::Internal` - If something wrong happens with the database query
     /// `HTTPException::BadRequest` - If the tournament does not exists or it's private and other user is trying to access it
-    pub async fn get_tournament_information_by_id(
+    pub async fn check_tournament_access(
         user_id: Option<u64>,
         tournament_id: u64,
         db_pool: &Pool<MySql>,
-    ) -> Result<TournamentInformationData, HTTPException> {
+    ) -> Result<String, HTTPException> {
         let tournament = sqlx::query!(
             "
         SELECT id, name
@@ -204,26 +223,69 @@ impl Tournament {
         .fetch_optional(db_pool)
         .await;
 
-        let tournament = match tournament {
+        match tournament {
             Ok(optional_data) => match optional_data {
-                Some(data) => data,
+                Some(data) => Ok(data.name),
                 None => {
                     let formatted_message = format!("Failed to find the tournament with the id of {}. It may not exist or you don't have permission to access it", tournament_id);
-                    return Err(HTTPException::BadRequest(formatted_message));
+                    Err(HTTPException::BadRequest(formatted_message))
                 }
             },
-            Err(_) => {
-                return Err(HTTPException::Internal(String::from(
-                    "Failed to search for the tournament. Please try again later",
-                )));
-            }
-        };
+            Err(_) => Err(HTTPException::Internal(String::from(
+                "Failed to search for the tournament. Please try again later",
+            ))),
+        }
+    }
+
+    /// Gets the tournament information data. Such as a page of the leagues and teams associated with,
+    /// the other pages are listed by the tournament leagues and teams listings
+    ///
+    /// # Arguments
+    /// * `user_id` - `Option<u64>` value which represents the user_id, if existing
+    /// * `tournament_id` - `u64` integer which represents the id of the tournament
+    /// * `pagination` - `Pagination` struct with the page of leagues and teams
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// `Ok(TournamentInformationData)` - `TournamentInformationData` struct with the tournament, leagues and teams information
+    ///
+    /// # Error
+    /// `HTTPException::Internal` - If something wrong happens with the database query
+    /// `HTTPException::BadRequest` - If the tournament does not exists or it's private and other user is trying to access it
+    pub async fn get_tournament_information_by_id(
+        user_id: Option<u64>,
+        tournament_id: u64,
+        pagination: Pagination,
+        db_pool: &Pool<MySql>,
+    ) -> Result<TournamentInformationData, HTTPException> {
+        let name = Tournament::check_tournament_access(user_id, tournament_id, db_pool).await?;
+
+        let (leagues_total, teams_total) = sqlx::query_as::<_, (i64, i64)>(
+            "
+        SELECT
+        (SELECT COUNT(*) FROM `leagues` WHERE tournament_id = ?),
+        (SELECT COUNT(*) FROM `teams` WHERE tournament_id = ?)
+        ",
+        )
+        .bind(tournament_id)
+        .bind(tournament_id)
+        .fetch_one(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed to search for the tournament. Please try again later",
+            )))
+        })?;
 
         let leagues = sqlx::query!(
             "SELECT id, name, completed
             FROM `leagues`
-            WHERE tournament_id = ?",
-            tournament_id
+            WHERE tournament_id = ?
+            ORDER BY id ASC
+            LIMIT ? OFFSET ?",
+            tournament_id,
+            pagination.limit,
+            pagination.offset
         )
         .fetch_all(db_pool)
         .await;
@@ -240,8 +302,12 @@ impl Tournament {
         let teams = sqlx::query!(
             "SELECT id, name
             FROM `teams`
-            WHERE tournament_id = ?",
-            tournament_id
+            WHERE tournament_id = ?
+            ORDER BY id ASC
+            LIMIT ? OFFSET ?",
+            tournament_id,
+            pagination.limit,
+            pagination.offset
         )
         .fetch_all(db_pool)
         .await;
@@ -256,8 +322,8 @@ impl Tournament {
         };
 
         Ok(TournamentInformationData {
-            id: tournament.id,
-            name: tournament.name,
+            id: tournament_id,
+            name,
             leagues: leagues
                 .into_iter()
                 .map(|league| {
@@ -269,6 +335,7 @@ impl Tournament {
                     };
                 })
                 .collect(),
+            leagues_total: leagues_total as u64,
             teams: teams
                 .into_iter()
                 .map(|team| TeamInformationData {
@@ -276,6 +343,140 @@ impl Tournament {
                     name: team.name,
                 })
                 .collect(),
+            teams_total: teams_total as u64,
         })
     }
-}
\ No newline at end of file
+
+    /// Responsible to get a page of the tournaments which the user owns, public or private
+    ///
+    /// # Arguments
+    /// * `user_id` - `u64` integer which represents the tournaments owner
+    /// * `list_query` - `&TournamentListQuery` reference with the pagination, name search and sorting
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok((Vec<TournamentSummaryData>, PaginationData))` - The tournaments on the page and the pagination information
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_user_tournaments_page(
+        user_id: u64,
+        list_query: &TournamentListQuery,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(Vec<TournamentSummaryData>, PaginationData), HTTPException> {
+        Tournament::get_tournaments_page(Some(user_id), list_query, db_pool).await
+    }
+
+    /// Responsible to get a page of the public tournaments, so users can discover tournaments from other users
+    ///
+    /// # Arguments
+    /// * `list_query` - `&TournamentListQuery` reference with the pagination, name search and sorting
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok((Vec<TournamentSummaryData>, PaginationData))` - The tournaments on the page and the pagination information
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    pub async fn get_public_tournaments_page(
+        list_query: &TournamentListQuery,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(Vec<TournamentSummaryData>, PaginationData), HTTPException> {
+        Tournament::get_tournaments_page(None, list_query, db_pool).await
+    }
+
+    /// Lists the tournaments of `owner_id`, or the public tournaments when there's no owner.
+    /// Only the leagues and teams count of each tournament is listed, its leagues and teams have their own listings
+    async fn get_tournaments_page(
+        owner_id: Option<u64>,
+        list_query: &TournamentListQuery,
+        db_pool: &Pool<MySql>,
+    ) -> Result<(Vec<TournamentSummaryData>, PaginationData), HTTPException> {
+        let pagination = Pagination::new(list_query.limit, list_query.offset);
+
+        let mut count_query = QueryBuilder::new("SELECT COUNT(*)");
+        push_tournament_list_filters(&mut count_query, owner_id, list_query);
+
+        let total = count_query
+            .build_query_scalar::<i64>()
+            .fetch_one(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to search for the tournaments. Please try again later",
+                )))
+            })?;
+
+        let mut query = QueryBuilder::new(
+            "SELECT TournamentRow.id, TournamentRow.name, TournamentRow.public,
+            (SELECT COUNT(*) FROM `leagues` as LeagueRow WHERE LeagueRow.tournament_id = TournamentRow.id) as leagues_total,
+            (SELECT COUNT(*) FROM `teams` as TeamRow WHERE TeamRow.tournament_id = TournamentRow.id) as teams_total",
+        );
+        push_tournament_list_filters(&mut query, owner_id, list_query);
+
+        // The sort column comes from a fixed list, so it's safe to push it into the query
+        let sort_column = match list_query.sort.unwrap_or(TournamentSort::Id) {
+            TournamentSort::Name => "TournamentRow.name",
+            TournamentSort::Id => "TournamentRow.id",
+        };
+        let sort_order = list_query.order.unwrap_or(SortOrder::Asc);
+        query.push(format!(
+            " ORDER BY {} {}, TournamentRow.id ASC",
+            sort_column,
+            sort_order.as_sql()
+        ));
+        query
+            .push(" LIMIT ")
+            .push_bind(pagination.limit)
+            .push(" OFFSET ")
+            .push_bind(pagination.offset);
+
+        let tournaments = query
+            .build_query_as::<TournamentListRow>()
+            .fetch_all(db_pool)
+            .await
+            .or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed to search for the tournaments. Please try again later",
+                )))
+            })?;
+
+        let tournaments = tournaments
+            .into_iter()
+            .map(|tournament| TournamentSummaryData {
+                id: tournament.id,
+                name: tournament.name,
+                public: tournament.public,
+                leagues_total: tournament.leagues_total as u64,
+                teams_total: tournament.teams_total as u64,
+            })
+            .collect();
+
+        Ok((tournaments, pagination.with_total(total as u64)))
+    }
+}
+
+/// Pushes the `FROM` and `WHERE` clauses shared by the tournaments listing and its count.
+/// With an `owner_id` every tournament of the owner is listed, otherwise only the public ones
+fn push_tournament_list_filters(
+    query: &mut QueryBuilder<'_, MySql>,
+    owner_id: Option<u64>,
+    list_query: &TournamentListQuery,
+) {
+    query.push(" FROM `tournaments` as TournamentRow WHERE ");
+
+    match owner_id {
+        Some(owner_id) => {
+            query.push("TournamentRow.user_id = ").push_bind(owner_id);
+        }
+        None => {
+            query.push("TournamentRow.public = TRUE");
+        }
+    }
+
+    if let Some(name) = &list_query.name {
+        query
+            .push(" AND TournamentRow.name LIKE ")
+            .push_bind(contains_pattern(name));
+    }
+}
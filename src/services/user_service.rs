@@ -1,9 +1,10 @@
 use bcrypt::{hash, DEFAULT_COST, verify};
 use sqlx::{MySql, Pool};
 
-use crate::jwt_auth_handler::{self, UserToken};
+use crate::jwt_auth_handler::{self, JwtConfig, UserToken};
 use crate::models::user::UserLoginDTO;
 use crate::models::user::{User, UserSignUpDTO};
+use crate::oidc_handler::{self, OidcConfig};
 use crate::responses::{HTTPException, ErrorResponse};
 use email_address::*;
 
@@ -43,6 +44,7 @@ pub async fn create_user(
 ///
 /// # Arguments
 /// * `user_login` - `UserLoginDTO` struct which represents the user login credentials
+/// * `jwt_config` - `&JwtConfig` reference with the settings used to sign the token
 /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
 ///
 /// # Errors
@@ -50,10 +52,11 @@ pub async fn create_user(
 /// * Functions with the "?" on the end can throw a `HTTPException` error. For more details you should look onto them
 pub async fn user_login(
     user_login: UserLoginDTO,
+    jwt_config: &JwtConfig,
     db_pool: &Pool<MySql>,
 ) -> Result<String, HTTPException> {
     let user_id = User::authenticate(&user_login, &db_pool).await?;
-    let generated_jwt = match jwt_auth_handler::generate_token(user_id) {
+    let generated_jwt = match jwt_auth_handler::generate_token(user_id, jwt_config) {
         Err(_) => {
             return Err(HTTPException::Internal(String::from(
                 "Failed to generate the login authorization. Please try again later",
@@ -65,6 +68,42 @@ pub async fn user_login(
     Ok(generated_jwt)
 }
 
+/// Function responsible to finish the OpenID Connect login. It exchanges the authorization code, links the identity provider subject to a user
+/// and returns a token which contains the user_id, the same way `user_login` does
+///
+/// # Arguments
+/// * `code` - The authorization code received from the identity provider
+/// * `nonce` - The nonce sent on the authorization request
+/// * `link_user_id` - The signed in user which is linking the identity provider account, `None` when signing in
+/// * `oidc_config` - `&OidcConfig` reference with the identity provider settings
+/// * `jwt_config` - `&JwtConfig` reference with the settings used to sign the token
+/// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+///
+/// # Errors
+/// * `HTTPException::Internal` - If something wrong happens when trying to generate the token
+/// * Functions with the "?" on the end can throw a `HTTPException` error. For more details you should look onto them
+pub async fn oidc_login(
+    code: &str,
+    nonce: &str,
+    link_user_id: Option<u64>,
+    oidc_config: &OidcConfig,
+    jwt_config: &JwtConfig,
+    db_pool: &Pool<MySql>,
+) -> Result<String, HTTPException> {
+    let discovery = oidc_handler::fetch_discovery(oidc_config).await?;
+    let claims = oidc_handler::exchange_code(oidc_config, &discovery, code, nonce).await?;
+    let user_id = match link_user_id {
+        Some(user_id) => User::link_identity(user_id, &claims, db_pool).await?,
+        None => User::find_or_create_by_identity(&claims, db_pool).await?,
+    };
+
+    jwt_auth_handler::generate_token(user_id, jwt_config).or_else(|_error| {
+        Err(HTTPException::Internal(String::from(
+            "Failed to generate the login authorization. Please try again later",
+        )))
+    })
+}
+
 impl User {
     /// Returns a optional value of a `User`, or `None` if there's no user associated with the given `email` argument
     ///
@@ -197,4 +236,183 @@ impl User {
             }
         }
     }
+
+    /// Responsible to get the user linked to an identity provider subject, creating it on the first login.
+    /// An existing account with the same e-mail is never linked here, its owner has to sign in and link it with `link_identity`
+    ///
+    /// # Arguments
+    /// * `claims` - `&OidcClaims` reference with the validated identity provider claims
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - A `u64` integer that represents the id of the linked user
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::BadRequest` - If the identity provider did not share the user e-mail
+    /// * `HTTPException::Conflict` - If the e-mail is used by an account which is not linked to the identity provider subject
+    pub async fn find_or_create_by_identity(
+        claims: &oidc_handler::OidcClaims,
+        db_pool: &Pool<MySql>,
+    ) -> Result<u64, HTTPException> {
+        let identity = sqlx::query!(
+            "
+        SELECT IdentityRow.user_id
+        FROM `user_identities` as IdentityRow
+        WHERE IdentityRow.issuer = (?) AND IdentityRow.subject = (?)
+        ",
+            claims.iss,
+            claims.sub
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something wrong happened while looking for users. Please try again later",
+            )))
+        })?;
+
+        if let Some(identity) = identity {
+            return Ok(identity.user_id);
+        }
+
+        let email = match &claims.email {
+            Some(email) if EmailAddress::is_valid(email) => email.to_lowercase(),
+            _ => {
+                return Err(HTTPException::BadRequest(String::from(
+                    "The identity provider did not share a valid e-mail address",
+                )));
+            }
+        };
+
+        if User::find_user_by_email(&email, db_pool).await?.is_some() {
+            return Err(HTTPException::Conflict(String::from(
+                "There's a user with the same e-mail registered. Sign in with the password and link the identity provider account",
+            )));
+        }
+
+        // Users created by the identity provider can't sign in with a password,
+        // so we store the hash of a random value that nobody knows
+        let hashed_password =
+            hash(oidc_handler::generate_secret(32), DEFAULT_COST).or_else(|_error| {
+                Err(HTTPException::Internal(String::from(
+                    "Failed while saving the user. Please try again later",
+                )))
+            })?;
+        let name = claims.name.clone().unwrap_or_else(|| email.clone());
+
+        let mut transaction = db_pool.begin().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed initializing the database transaction",
+            )))
+        })?;
+
+        let user_id = sqlx::query!(
+            "
+        INSERT INTO `users` (`email`, `password`, `name`)
+        VALUES (?, ?, ?)
+        ",
+            email,
+            hashed_password,
+            name
+        )
+        .execute(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while saving the user. Please try again later",
+            )))
+        })?
+        .last_insert_id();
+
+        sqlx::query!(
+            "
+        INSERT INTO `user_identities` (`user_id`, `issuer`, `subject`)
+        VALUES (?, ?, ?)
+        ",
+            user_id,
+            claims.iss,
+            claims.sub
+        )
+        .execute(&mut *transaction)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while linking the user to the identity provider. Please try again later",
+            )))
+        })?;
+
+        transaction.commit().await.or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while saving the user. Please try again later",
+            )))
+        })?;
+
+        Ok(user_id)
+    }
+
+    /// Responsible to link an identity provider subject to the signed in user, so the user can sign in with it afterwards
+    ///
+    /// # Arguments
+    /// * `user_id` - The signed in user id
+    /// * `claims` - `&OidcClaims` reference with the validated identity provider claims
+    /// * `db_pool` - A `&Pool<MySql>` reference for the MySQL database connection
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - The user id
+    ///
+    /// # Errors
+    /// * `HTTPException::Internal` - If the database query fails
+    /// * `HTTPException::Conflict` - If the identity provider subject is linked to another user
+    pub async fn link_identity(
+        user_id: u64,
+        claims: &oidc_handler::OidcClaims,
+        db_pool: &Pool<MySql>,
+    ) -> Result<u64, HTTPException> {
+        let identity = sqlx::query!(
+            "
+        SELECT IdentityRow.user_id
+        FROM `user_identities` as IdentityRow
+        WHERE IdentityRow.issuer = (?) AND IdentityRow.subject = (?)
+        ",
+            claims.iss,
+            claims.sub
+        )
+        .fetch_optional(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Something wrong happened while looking for users. Please try again later",
+            )))
+        })?;
+
+        match identity {
+            Some(identity) if identity.user_id == user_id => return Ok(user_id),
+            Some(_) => {
+                return Err(HTTPException::Conflict(String::from(
+                    "The identity provider account is linked to another user",
+                )));
+            }
+            None => (),
+        }
+
+        sqlx::query!(
+            "
+        INSERT INTO `user_identities` (`user_id`, `issuer`, `subject`)
+        VALUES (?, ?, ?)
+        ",
+            user_id,
+            claims.iss,
+            claims.sub
+        )
+        .execute(db_pool)
+        .await
+        .or_else(|_error| {
+            Err(HTTPException::Internal(String::from(
+                "Failed while linking the user to the identity provider. Please try again later",
+            )))
+        })?;
+
+        Ok(user_id)
+    }
 }
@@ -0,0 +1,195 @@
+use jsonwebtoken::{jwk::JwkSet, Algorithm};
+use rocket::{http::Status, local::asynchronous::Client};
+
+use crate::{
+    build_rocket,
+    jwt_auth_handler::{decode_token, JwtConfig},
+    rocket,
+    test::{register_and_login, utilities::tournament_utilities::create_tournament_request},
+};
+
+// Getting the public key set which is used by other services to verify the tokens
+#[rocket::async_test]
+async fn get_jwks_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+
+    let response = client.get("/.well-known/jwks.json").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    response
+        .into_json::<JwkSet>()
+        .await
+        .expect("Expected a key set");
+}
+
+// Logging in and making sure the token follows the configured settings
+#[rocket::async_test]
+async fn login_token_follows_jwt_config() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let jwt_config = client
+        .rocket()
+        .state::<JwtConfig>()
+        .expect("Expected the JWT settings on the managed state");
+    let token_data = decode_token(authorization_token, jwt_config).expect("Expected a valid token");
+
+    assert_eq!(token_data.header.alg, jwt_config.algorithm);
+    assert_eq!(token_data.header.kid, jwt_config.key_id);
+    assert_eq!(token_data.claims.iss, jwt_config.issuer);
+    assert_eq!(token_data.claims.aud, jwt_config.audience);
+    assert_eq!(
+        token_data.claims.exp - token_data.claims.iat,
+        jwt_config.expiration
+    );
+}
+
+// Builds a key set with the given keys of the test key set
+fn test_key_set(key_ids: &[&str]) -> JwkSet {
+    let key_set: JwkSet = serde_json::from_str(include_str!("keys/jwks.json")).unwrap();
+
+    JwkSet {
+        keys: key_set
+            .keys
+            .into_iter()
+            .filter(|jwk| key_ids.contains(&jwk.common.key_id.as_deref().unwrap_or_default()))
+            .collect(),
+    }
+}
+
+async fn key_pair_client(
+    algorithm: Algorithm,
+    key_id: &str,
+    private_key: &[u8],
+    public_key_ids: &[&str],
+) -> Client {
+    let jwt_config = JwtConfig::from_key_pair(
+        algorithm,
+        key_id.to_string(),
+        private_key,
+        test_key_set(public_key_ids),
+    )
+    .expect("Expected valid JWT settings");
+
+    Client::tracked(build_rocket(jwt_config, None).await)
+        .await
+        .unwrap()
+}
+
+async fn published_key_ids(client: &Client) -> Vec<String> {
+    let response = client.get("/.well-known/jwks.json").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+
+    response
+        .into_json::<JwkSet>()
+        .await
+        .expect("Expected a key set")
+        .keys
+        .into_iter()
+        .filter_map(|jwk| jwk.common.key_id)
+        .collect()
+}
+
+async fn create_tournament_status(client: &Client, token: &String) -> Status {
+    let tournament_data = r#"{
+        "name": "signed tournament",
+        "public": false
+    }"#;
+
+    create_tournament_request(client, Some(token), tournament_data)
+        .await
+        .status()
+}
+
+// Signing the tokens with RS256 and rotating the signing key
+#[rocket::async_test]
+async fn rs256_key_rotation_test() {
+    let first_key = include_bytes!("keys/rsa-key-1.pem");
+    let second_key = include_bytes!("keys/rsa-key-2.pem");
+
+    // Only the first key is in use
+    let client = key_pair_client(Algorithm::RS256, "rsa-key-1", first_key, &["rsa-key-1"]).await;
+    let first_token = register_and_login(&client).await;
+
+    let header = jsonwebtoken::decode_header(&first_token).unwrap();
+    assert_eq!(header.alg, Algorithm::RS256);
+    assert_eq!(header.kid.as_deref(), Some("rsa-key-1"));
+    assert_eq!(published_key_ids(&client).await, vec!["rsa-key-1"]);
+    assert_eq!(
+        create_tournament_status(&client, &first_token).await,
+        Status::Created
+    );
+
+    // Signing with the second key while the first one is still on the key set
+    let client = key_pair_client(
+        Algorithm::RS256,
+        "rsa-key-2",
+        second_key,
+        &["rsa-key-1", "rsa-key-2"],
+    )
+    .await;
+    let second_token = register_and_login(&client).await;
+
+    let header = jsonwebtoken::decode_header(&second_token).unwrap();
+    assert_eq!(header.kid.as_deref(), Some("rsa-key-2"));
+    assert_eq!(
+        published_key_ids(&client).await,
+        vec!["rsa-key-1", "rsa-key-2"]
+    );
+    assert_eq!(
+        create_tournament_status(&client, &first_token).await,
+        Status::Created
+    );
+    assert_eq!(
+        create_tournament_status(&client, &second_token).await,
+        Status::Created
+    );
+
+    // Once the first key is removed from the key set, its tokens should be rejected
+    let client = key_pair_client(Algorithm::RS256, "rsa-key-2", second_key, &["rsa-key-2"]).await;
+
+    assert_eq!(published_key_ids(&client).await, vec!["rsa-key-2"]);
+    assert_eq!(
+        create_tournament_status(&client, &first_token).await,
+        Status::Unauthorized
+    );
+    assert_eq!(
+        create_tournament_status(&client, &second_token).await,
+        Status::Created
+    );
+}
+
+// Signing the tokens with EdDSA
+#[rocket::async_test]
+async fn eddsa_login_test() {
+    let client = key_pair_client(
+        Algorithm::EdDSA,
+        "ed25519-key-1",
+        include_bytes!("keys/ed25519-key-1.pem"),
+        &["ed25519-key-1"],
+    )
+    .await;
+    let token = register_and_login(&client).await;
+
+    let header = jsonwebtoken::decode_header(&token).unwrap();
+    assert_eq!(header.alg, Algorithm::EdDSA);
+    assert_eq!(header.kid.as_deref(), Some("ed25519-key-1"));
+    assert_eq!(published_key_ids(&client).await, vec!["ed25519-key-1"]);
+    assert_eq!(
+        create_tournament_status(&client, &token).await,
+        Status::Created
+    );
+
+    // A token signed by a key that is not on the key set should be rejected
+    let rsa_client = key_pair_client(
+        Algorithm::RS256,
+        "rsa-key-1",
+        include_bytes!("keys/rsa-key-1.pem"),
+        &["rsa-key-1"],
+    )
+    .await;
+    let rsa_token = register_and_login(&rsa_client).await;
+    assert_eq!(
+        create_tournament_status(&client, &rsa_token).await,
+        Status::Unauthorized
+    );
+}
@@ -0,0 +1,758 @@
+use rocket::{http::Status, local::asynchronous::Client};
+use serde_json::json;
+
+use crate::{
+    models::{
+        bracket::{BracketDTO, BracketSide},
+        league::LeagueInformationDTO,
+        tie::{TieDTO, TieDecider},
+    },
+    rocket,
+    test::{
+        register_and_login,
+        utilities::{
+            bracket_utilities::{generate_league_bracket_request, get_league_bracket_request},
+            fixture_utilities::{
+                create_fixture_request, delete_fixture_request, edit_fixture_request,
+                generate_fixtures_request,
+            },
+            league_utilities::{
+                add_team_to_league_request, create_league_request, edit_league_request,
+                get_league_request, withdraw_team_from_league_request,
+            },
+            team_utilities::create_team_request,
+            tie_utilities::{edit_tie_request, get_league_ties_request},
+            tournament_utilities::create_tournament_request,
+        },
+        APIResponse, IdData,
+    },
+};
+
+async fn get_league(
+    client: &Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LeagueInformationDTO {
+    let response = get_league_request(client, authorization_token, tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::Ok);
+
+    response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .expect("Expected the league data")
+        .data
+}
+
+async fn get_bracket(
+    client: &Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> BracketDTO {
+    let response =
+        get_league_bracket_request(client, authorization_token, tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::Ok);
+
+    response
+        .into_json::<APIResponse<BracketDTO>>()
+        .await
+        .expect("Expected the bracket data")
+        .data
+}
+
+async fn play_bracket_match(
+    client: &Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    match_number: u16,
+    home_score: u8,
+    away_score: u8,
+) -> Status {
+    let bracket = get_bracket(client, authorization_token, tournament_id, league_id).await;
+    let fixture_id = bracket.matches[match_number as usize - 1]
+        .fixture
+        .as_ref()
+        .expect("Expected the bracket match fixture")
+        .id;
+
+    play_bracket_fixture(
+        client,
+        authorization_token,
+        tournament_id,
+        league_id,
+        fixture_id,
+        home_score,
+        away_score,
+    )
+    .await
+}
+
+async fn play_bracket_fixture(
+    client: &Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    fixture_id: u64,
+    home_score: u8,
+    away_score: u8,
+) -> Status {
+    let result = json!({
+        "home_score": home_score,
+        "away_score": away_score,
+        "played": true
+    })
+    .to_string();
+
+    edit_fixture_request(
+        client,
+        authorization_token,
+        tournament_id,
+        league_id,
+        fixture_id,
+        &result,
+    )
+    .await
+    .status()
+}
+
+// Playing a double elimination bracket of 3 teams until the grand final reset
+#[rocket::async_test]
+async fn double_elimination_bracket_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "bracket",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "bracket",
+        "completed": false,
+        "format": "double_elimination"
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for number in 0..3 {
+        let team_data = json!({ "name": format!("Team {}", number) }).to_string();
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &team_data,
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    // The bracket leagues don't draw the fixtures all at once
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Nor create them one by one, the bracket creates them
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "home_team_id": team_ids[0], "away_team_id": team_ids[1], "round": 1 })
+            .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // The seeding must have every team of the league
+    let response = generate_league_bracket_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "team_ids": [team_ids[0], team_ids[1]] }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let generation_data = json!({
+        "team_ids": team_ids,
+        "grand_final_reset": true
+    })
+    .to_string();
+    let response = generate_league_bracket_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &generation_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let bracket = response
+        .into_json::<APIResponse<BracketDTO>>()
+        .await
+        .unwrap()
+        .data;
+
+    // 2 winners rounds, 2 losers rounds, the grand final and its reset
+    assert_eq!(bracket.matches.len(), 7);
+    assert_eq!(bracket.matches[5].bracket, BracketSide::GrandFinal);
+    assert_eq!(bracket.matches[6].bracket, BracketSide::GrandFinalReset);
+
+    // The first seed has a bye, the other seeds play the first fixture
+    assert!(bracket.matches[0].decided);
+    assert_eq!(bracket.matches[0].winner_team_id, Some(team_ids[0]));
+    assert_eq!(bracket.matches[0].winner_to_match, Some(3));
+    let first_fixture = bracket.matches[1].fixture.as_ref().unwrap();
+    assert_eq!(first_fixture.home_team.id, team_ids[1]);
+    assert_eq!(first_fixture.away_team.id, team_ids[2]);
+    assert_eq!(bracket.matches[1].loser_to_match, Some(4));
+    assert_eq!(
+        bracket
+            .matches
+            .iter()
+            .filter(|bracket_match| bracket_match.fixture.is_some())
+            .count(),
+        1
+    );
+
+    let response = generate_league_bracket_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &generation_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let other_user_token = register_and_login(&client).await;
+    let response =
+        get_league_bracket_request(&client, Some(&other_user_token), tournament_id, league_id)
+            .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let current_bracket = || {
+        get_bracket(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+    };
+    let play = |match_number: u16, home_score: u8, away_score: u8| {
+        play_bracket_match(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            match_number,
+            home_score,
+            away_score,
+        )
+    };
+
+    // The winner goes to the winners final, the loser gets a bye on the losers bracket
+    assert_eq!(play(2, 2, 1).await, Status::Ok);
+    let bracket = current_bracket().await;
+    assert_eq!(bracket.matches[2].home_team_id, Some(team_ids[0]));
+    assert_eq!(bracket.matches[2].away_team_id, Some(team_ids[1]));
+    assert!(bracket.matches[2].fixture.is_some());
+    assert_eq!(bracket.matches[3].winner_team_id, Some(team_ids[2]));
+
+    // A bracket fixture needs a winner
+    assert_eq!(play(3, 1, 1).await, Status::BadRequest);
+
+    // The loser of the winners final drops to the losers final
+    assert_eq!(play(3, 0, 1).await, Status::Ok);
+    let bracket = current_bracket().await;
+    let losers_final = bracket.matches[4].fixture.as_ref().unwrap();
+    assert_eq!(losers_final.home_team.id, team_ids[2]);
+    assert_eq!(losers_final.away_team.id, team_ids[0]);
+    assert_eq!(losers_final.round, 3);
+
+    // The winners final was already played, so the first result can't change its winner
+    assert_eq!(play(2, 0, 1).await, Status::BadRequest);
+
+    assert_eq!(play(5, 0, 3).await, Status::Ok);
+    let bracket = current_bracket().await;
+    let grand_final = bracket.matches[5].fixture.as_ref().unwrap();
+    assert_eq!(grand_final.home_team.id, team_ids[1]);
+    assert_eq!(grand_final.away_team.id, team_ids[0]);
+    assert_eq!(grand_final.round, 4);
+    assert!(bracket.matches[6].fixture.is_none());
+
+    // The losers bracket champion wins the grand final, so it's reset
+    assert_eq!(play(6, 0, 1).await, Status::Ok);
+    let bracket = current_bracket().await;
+    let reset_fixture = bracket.matches[6].fixture.as_ref().unwrap();
+    assert_eq!(reset_fixture.home_team.id, team_ids[1]);
+    assert_eq!(reset_fixture.away_team.id, team_ids[0]);
+    assert_eq!(bracket.champion_team_id, None);
+
+    let response = delete_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        reset_fixture.id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let league = get_league(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert!(!league.completed);
+
+    // The league is completed once the bracket has a champion, which is archived first
+    assert_eq!(play(7, 2, 0).await, Status::Ok);
+    let bracket = current_bracket().await;
+    assert_eq!(bracket.champion_team_id, Some(team_ids[1]));
+
+    let league = get_league(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert!(league.completed);
+    assert_eq!(
+        league.champion.map(|champion| champion.id),
+        Some(team_ids[1])
+    );
+}
+
+// A team withdrawn from the bracket gives a walkover to its opponents and the bracket keeps advancing
+#[rocket::async_test]
+async fn bracket_withdrawal_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "bracket withdrawal",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "bracket withdrawal",
+        "completed": false,
+        "format": "double_elimination"
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for number in 0..4 {
+        let team_data = json!({ "name": format!("Team {}", number) }).to_string();
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &team_data,
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    // The first round is 1 against 4 and 2 against 3, their winners meet on match 3 and their losers on match 4
+    let response = generate_league_bracket_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "team_ids": team_ids }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+
+    let current_bracket = || {
+        get_bracket(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+    };
+    let play = |match_number: u16, home_score: u8, away_score: u8| {
+        play_bracket_match(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            match_number,
+            home_score,
+            away_score,
+        )
+    };
+    let withdraw = |team_id: u64, policy: &'static str| {
+        withdraw_team_from_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+            policy,
+        )
+    };
+
+    assert_eq!(play(1, 2, 0).await, Status::Ok);
+
+    // The bracket results are kept, so the team can't be expunged
+    assert_eq!(
+        withdraw(team_ids[2], "expunge").await.status(),
+        Status::BadRequest
+    );
+
+    // The third seed withdraws before its first match, which is forfeited
+    assert_eq!(withdraw(team_ids[2], "forfeit").await.status(), Status::Ok);
+    let bracket = current_bracket().await;
+    let forfeited_fixture = bracket.matches[1].fixture.as_ref().unwrap();
+    assert!(forfeited_fixture.played);
+    assert_eq!(bracket.matches[1].winner_team_id, Some(team_ids[1]));
+    let winners_final = bracket.matches[2].fixture.as_ref().unwrap();
+    assert_eq!(winners_final.home_team.id, team_ids[0]);
+    assert_eq!(winners_final.away_team.id, team_ids[1]);
+
+    // Its losers bracket match is a walkover, without a fixture
+    assert!(bracket.matches[3].decided);
+    assert_eq!(bracket.matches[3].winner_team_id, Some(team_ids[3]));
+    assert!(bracket.matches[3].fixture.is_none());
+
+    // The next result advances the bracket without the withdrawn team
+    assert_eq!(play(3, 1, 0).await, Status::Ok);
+    let bracket = current_bracket().await;
+    let losers_final = bracket.matches[4].fixture.as_ref().unwrap();
+    assert_eq!(losers_final.home_team.id, team_ids[3]);
+    assert_eq!(losers_final.away_team.id, team_ids[1]);
+    assert!(bracket.matches.iter().skip(2).all(|bracket_match| {
+        bracket_match.fixture.as_ref().map_or(true, |fixture| {
+            fixture.home_team.id != team_ids[2] && fixture.away_team.id != team_ids[2]
+        })
+    }));
+
+    // A team withdrawn with a pending bracket fixture forfeits it
+    assert_eq!(withdraw(team_ids[3], "forfeit").await.status(), Status::Ok);
+    let bracket = current_bracket().await;
+    assert_eq!(bracket.matches[4].winner_team_id, Some(team_ids[1]));
+    let grand_final = bracket.matches[5].fixture.as_ref().unwrap();
+    assert_eq!(grand_final.home_team.id, team_ids[0]);
+    assert_eq!(grand_final.away_team.id, team_ids[1]);
+
+    assert_eq!(play(6, 2, 1).await, Status::Ok);
+    let bracket = current_bracket().await;
+    assert_eq!(bracket.champion_team_id, Some(team_ids[0]));
+
+    let league = get_league(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert!(league.completed);
+}
+
+// A two legged bracket match goes to the extra time, its winner is decided by the tie aggregate
+#[rocket::async_test]
+async fn two_legged_bracket_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "two legged bracket",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "two legged bracket",
+        "completed": false,
+        "format": "double_elimination"
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for number in 0..2 {
+        let team_data = json!({ "name": format!("Team {}", number) }).to_string();
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &team_data,
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    let generation_data = json!({
+        "team_ids": team_ids,
+        "two_legged": true
+    })
+    .to_string();
+    let response = generate_league_bracket_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &generation_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let bracket = response
+        .into_json::<APIResponse<BracketDTO>>()
+        .await
+        .unwrap()
+        .data;
+
+    // The winners match is played over two legs, the grand final on a single fixture
+    assert_eq!(bracket.matches.len(), 2);
+    let first_leg = bracket.matches[0].fixture.as_ref().unwrap();
+    let second_leg = bracket.matches[0].second_leg.as_ref().unwrap();
+    assert_eq!(first_leg.home_team.id, team_ids[0]);
+    assert_eq!(first_leg.round, 1);
+    assert_eq!(second_leg.home_team.id, team_ids[1]);
+    assert_eq!(second_leg.round, 2);
+    let second_leg_id = second_leg.id;
+
+    let current_bracket = || {
+        get_bracket(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+    };
+    let play = |fixture_id: u64, home_score: u8, away_score: u8| {
+        play_bracket_fixture(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture_id,
+            home_score,
+            away_score,
+        )
+    };
+
+    // The legs can end on a draw
+    assert_eq!(play(first_leg.id, 1, 1).await, Status::Ok);
+    assert_eq!(play(second_leg_id, 0, 0).await, Status::Ok);
+    let bracket = current_bracket().await;
+    assert!(!bracket.matches[0].decided);
+    assert!(bracket.matches[1].fixture.is_none());
+
+    let response = delete_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        second_leg_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = get_league_ties_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let tie_id = response
+        .into_json::<APIResponse<Vec<TieDTO>>>()
+        .await
+        .unwrap()
+        .data[0]
+        .id;
+
+    // The first leg home team scores on the second leg extra time
+    let response = edit_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &json!({
+            "extra_time_home_score": 0,
+            "extra_time_away_score": 1,
+            "penalties_home_score": null,
+            "penalties_away_score": null
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let bracket = current_bracket().await;
+    let aggregate = bracket.matches[0].aggregate.unwrap();
+    assert_eq!(aggregate.home_score, 2);
+    assert_eq!(aggregate.away_score, 1);
+    assert_eq!(aggregate.decided_by, Some(TieDecider::ExtraTime));
+    assert_eq!(bracket.matches[0].winner_team_id, Some(team_ids[0]));
+    let grand_final = bracket.matches[1].fixture.as_ref().unwrap();
+    assert_eq!(grand_final.home_team.id, team_ids[0]);
+    assert_eq!(grand_final.round, 3);
+    assert_eq!(bracket.matches[0].winner_to_match, Some(2));
+
+    // Without a reset the grand final winner is the champion, so the league is completed
+    assert_eq!(play(grand_final.id, 1, 0).await, Status::Ok);
+    let league = get_league(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert!(league.completed);
+    assert_eq!(
+        league.champion.map(|champion| champion.id),
+        Some(team_ids[0])
+    );
+
+    let response = edit_league_request(
+        &client,
+        Some(&authorization_token),
+        r#"{ "name": "two legged bracket", "completed": false }"#,
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The tie winner can't change once the grand final is played
+    let response = edit_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &json!({
+            "extra_time_home_score": 1,
+            "extra_time_away_score": 0,
+            "penalties_home_score": null,
+            "penalties_away_score": null
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let bracket = current_bracket().await;
+    assert_eq!(bracket.champion_team_id, Some(team_ids[0]));
+}
@@ -0,0 +1,317 @@
+use rocket::{
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+};
+use serde_json::json;
+
+use crate::{
+    models::fixture::FixtureDataDTO,
+    rocket,
+    test::{
+        register_and_login,
+        utilities::{
+            calendar_utilities::{get_league_calendar_request, get_team_calendar_request},
+            fixture_utilities::{
+                edit_fixture_request, generate_fixtures_request, get_league_fixtures_request,
+                schedule_fixtures_request,
+            },
+            league_utilities::{add_team_to_league_request, create_league_request},
+            team_utilities::create_team_request,
+            tournament_utilities::{create_tournament_request, edit_tournament_request},
+        },
+        APIResponse, IdData,
+    },
+};
+
+// The dated fixtures of a public tournament are on the league and team calendars without authentication
+#[rocket::async_test]
+async fn fixtures_calendar_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "calendars",
+        "public": true
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // The line break of the league name is escaped on the feed
+    let league_data = r#"{
+        "name": "Sunday\r\nleague",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for name in ["Athletic, Old Boys", "Rangers", "Rovers", "Albion"] {
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &json!({ "name": name }).to_string(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    let constraints = json!({
+        "start_date": "2024-03-02",
+        "days_between_rounds": 7
+    })
+    .to_string();
+    let response = schedule_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &constraints,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_fixtures_request(&client, None, tournament_id, league_id).await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    let played_fixture = &fixtures[0];
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        played_fixture.id,
+        r#"{
+            "home_score": 2,
+            "away_score": 1,
+            "played": true
+        }"#,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_calendar_request(&client, None, tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::Calendar));
+    // The feed is sent inline, so the calendar apps subscribe to it
+    assert_eq!(
+        response.headers().get_one("Content-Type"),
+        Some("text/calendar; charset=utf-8")
+    );
+    assert_eq!(
+        response.headers().get_one("Content-Disposition"),
+        Some(format!("inline; filename=\"league-{}.ics\"", league_id).as_str())
+    );
+    let calendar = response.into_string().await.unwrap();
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), fixtures.len());
+    // The rounds are dated as all day events, one week apart
+    assert!(calendar.contains("DTSTART;VALUE=DATE:20240302\r\n"));
+    assert!(calendar.contains("DTSTART;VALUE=DATE:20240309\r\n"));
+    assert!(calendar.contains(&format!(
+        "SUMMARY:{} 2 - 1 {}",
+        played_fixture.home_team.name.replace(',', "\\,"),
+        played_fixture.away_team.name.replace(',', "\\,")
+    )));
+    // The teams grounds are not known, so the events have no location
+    assert!(!calendar.contains("LOCATION:"));
+    assert!(calendar.contains("X-WR-CALNAME:Sunday\\nleague\r\n"));
+    assert!(!calendar.replace("\r\n", "").contains('\r'));
+    // The lines are folded at 75 bytes
+    assert!(calendar.lines().all(|line| line.len() <= 75));
+
+    let response = get_team_calendar_request(&client, None, tournament_id, team_ids[1]).await;
+    assert_eq!(response.status(), Status::Ok);
+    let team_calendar = response.into_string().await.unwrap();
+    assert_eq!(team_calendar.matches("BEGIN:VEVENT").count(), 3);
+    assert!(team_calendar.contains("X-WR-CALNAME:Rangers\r\n"));
+
+    // A private tournament calendar needs the owner authentication
+    let response = edit_tournament_request(
+        &client,
+        tournament_id,
+        Some(&authorization_token),
+        r#"{ "public": false }"#,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_calendar_request(&client, None, tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = get_team_calendar_request(&client, None, tournament_id, team_ids[1]).await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = get_league_calendar_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+}
+
+// The fixtures generated without a schedule are not dated, so they're left out of the calendars
+#[rocket::async_test]
+async fn undated_fixtures_calendar_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "undated calendars",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut league_ids = vec![];
+    for name in ["Scheduled league", "Undated league"] {
+        let response = create_league_request(
+            &client,
+            Some(&authorization_token),
+            &json!({ "name": name, "completed": false }).to_string(),
+            tournament_id,
+        )
+        .await;
+        let league_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        league_ids.push(league_id);
+    }
+
+    // Every team plays the scheduled league, the first three the undated one too
+    let mut team_ids = vec![];
+    for name in ["Rangers", "Rovers", "Albion", "United"] {
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &json!({ "name": name }).to_string(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        let team_league_ids = if team_ids.len() < 3 {
+            &league_ids[..]
+        } else {
+            &league_ids[..1]
+        };
+        for league_id in team_league_ids {
+            add_team_to_league_request(
+                &client,
+                Some(&authorization_token),
+                tournament_id,
+                *league_id,
+                team_id,
+            )
+            .await;
+        }
+        team_ids.push(team_id);
+    }
+
+    let response = schedule_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_ids[0],
+        &json!({ "start_date": "2024-03-02" }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_ids[1],
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_ids[1],
+    )
+    .await;
+    let undated_fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(undated_fixtures.len(), 3);
+
+    let response = get_league_calendar_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_ids[1],
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let calendar = response.into_string().await.unwrap();
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 0);
+
+    // The team plays 3 dated and 2 undated fixtures
+    let response = get_team_calendar_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        team_ids[0],
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let team_calendar = response.into_string().await.unwrap();
+    assert_eq!(team_calendar.matches("BEGIN:VEVENT").count(), 3);
+    assert!(team_calendar.contains("DTSTART;VALUE=DATE:20240302\r\n"));
+}
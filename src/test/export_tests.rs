@@ -0,0 +1,348 @@
+use rocket::{
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+};
+use serde_json::json;
+
+use crate::{
+    models::export::{LeagueExportDTO, TournamentExportDTO},
+    rocket,
+    test::{
+        register_and_login,
+        utilities::{
+            export_utilities::{
+                export_league_csv_request, export_league_request, export_tournament_csv_request,
+                export_tournament_request,
+            },
+            fixture_utilities::{create_fixture_request, edit_fixture_request},
+            import_utilities::import_league_request,
+            league_utilities::{add_team_to_league_request, create_league_request},
+            team_utilities::create_team_request,
+            tournament_utilities::create_tournament_request,
+        },
+        APIResponse, IdData,
+    },
+};
+
+async fn create_league(client: &Client, authorization_token: &String, tournament_id: u64) -> u64 {
+    let league_data = r#"{
+        "name": "exported league",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        client,
+        Some(authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+
+    response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id
+}
+
+async fn get_league_export(
+    client: &Client,
+    authorization_token: &String,
+    tournament_id: u64,
+    league_id: u64,
+) -> LeagueExportDTO {
+    let response =
+        export_league_request(client, Some(authorization_token), tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::Ok);
+
+    response
+        .into_json::<APIResponse<LeagueExportDTO>>()
+        .await
+        .expect("Expected the league export")
+        .data
+}
+
+// Exports a league as CSV and JSON, and imports the JSON export on another league
+#[rocket::async_test]
+async fn export_league_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "exports",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+    let league_id = create_league(&client, &authorization_token, tournament_id).await;
+
+    let mut team_ids = vec![];
+    for name in ["North", "South, FC", "=East"] {
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &json!({ "name": name }).to_string(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    let mut fixture_ids = vec![];
+    for (home_team, away_team, round) in [(0, 1, 1), (2, 0, 2)] {
+        let fixture_data = json!({
+            "home_team_id": team_ids[home_team],
+            "away_team_id": team_ids[away_team],
+            "round": round
+        })
+        .to_string();
+        let response = create_fixture_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            &fixture_data,
+        )
+        .await;
+        let fixture_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        fixture_ids.push(fixture_id);
+    }
+
+    let result = r#"{
+        "home_score": 3,
+        "away_score": 1,
+        "played": true
+    }"#;
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture_ids[0],
+        result,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let export = get_league_export(&client, &authorization_token, tournament_id, league_id).await;
+    assert_eq!(export.teams.len(), 3);
+    assert_eq!(export.fixtures.len(), 2);
+    assert_eq!(export.standing_table[0].team_id, team_ids[0]);
+    assert!(export.import.fixtures.is_some());
+
+    // The tournament is private
+    let other_user_token = register_and_login(&client).await;
+    let response =
+        export_league_request(&client, Some(&other_user_token), tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::BadRequest);
+    let response = export_league_csv_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        "fixtures.csv",
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = export_league_csv_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "fixtures.csv",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.content_type(), Some(ContentType::CSV));
+    let fixtures_file = response.into_string().await.unwrap();
+    let lines: Vec<&str> = fixtures_file.lines().collect();
+    assert_eq!(
+        lines[0],
+        "id,round,home_team_id,home_team,away_team_id,away_team,home_score,away_score,played"
+    );
+    assert_eq!(
+        lines[1],
+        format!(
+            "{},1,{},North,{},\"South, FC\",3,1,true",
+            fixture_ids[0], team_ids[0], team_ids[1]
+        )
+    );
+    // A pending fixture has no scores
+    assert!(lines[2].ends_with(",,,false"));
+
+    let response = export_league_csv_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "standing-table.csv",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let standing_file = response.into_string().await.unwrap();
+    assert_eq!(standing_file.lines().count(), 4);
+    assert!(standing_file
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with(&format!("1,{},North,1,3", team_ids[0])));
+    // The negative numbers are kept, only the text which would run as a formula is escaped
+    assert!(standing_file.contains(",1,3,-2,"));
+
+    let response = export_league_csv_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "teams.csv",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let teams_file = response.into_string().await.unwrap();
+    assert!(teams_file
+        .lines()
+        .any(|line| line == format!("{},'=East", team_ids[2])));
+
+    // The import files of the export create the same league again, the escaped team names are read back as they were
+    let copy_league_id = create_league(&client, &authorization_token, tournament_id).await;
+    let response = import_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        copy_league_id,
+        &serde_json::to_string(&export.import).unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+
+    let copy =
+        get_league_export(&client, &authorization_token, tournament_id, copy_league_id).await;
+    let team_ids_of = |export: &LeagueExportDTO| {
+        export
+            .teams
+            .iter()
+            .map(|team| team.id)
+            .collect::<Vec<u64>>()
+    };
+    assert_eq!(team_ids_of(&copy), team_ids_of(&export));
+    for (copied, original) in copy.fixtures.iter().zip(&export.fixtures) {
+        assert_eq!(copied.home_team.id, original.home_team.id);
+        assert_eq!(copied.away_team.id, original.away_team.id);
+        assert_eq!(copied.round, original.round);
+        assert_eq!(copied.home_score, original.home_score);
+        assert_eq!(copied.away_score, original.away_score);
+        assert_eq!(copied.played, original.played);
+    }
+
+    let response =
+        export_tournament_request(&client, Some(&authorization_token), tournament_id).await;
+    assert_eq!(response.status(), Status::Ok);
+    let tournament_export = response
+        .into_json::<APIResponse<TournamentExportDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(tournament_export.teams.len(), 3);
+    assert_eq!(tournament_export.leagues.len(), 2);
+
+    let response = export_tournament_csv_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        "fixtures.csv",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let tournament_fixtures_file = response.into_string().await.unwrap();
+    assert!(tournament_fixtures_file.starts_with("league_id,league,id,round,"));
+    assert_eq!(tournament_fixtures_file.lines().count(), 5);
+}
+
+// The team names starting with a tab or a carriage return are escaped too, some spreadsheet apps skip them and run the formula after them
+#[rocket::async_test]
+async fn export_csv_whitespace_formula_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "whitespace exports",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+    let league_id = create_league(&client, &authorization_token, tournament_id).await;
+
+    let mut team_ids = vec![];
+    for name in ["\t=West", "\r+North"] {
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &json!({ "name": name }).to_string(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    let response = export_league_csv_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "teams.csv",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let teams_file = response.into_string().await.unwrap();
+    assert!(teams_file.contains(&format!("{},'\t=West\n", team_ids[0])));
+    // The carriage return is a line break, so the field is quoted as well
+    assert!(teams_file.contains(&format!("{},\"'\r+North\"\n", team_ids[1])));
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use rocket::{
     http::Status,
     local::asynchronous::{Client},
@@ -6,21 +8,33 @@ use serde_json::json;
 
 use crate::{
     models::{
-        fixture::{Fixture, FixtureDataDTO},
+        fixture::{
+            Fixture, FixtureChangesDTO, FixtureDataDTO, FixturePreviewDTO, ScheduledFixturesDTO,
+            SwissRoundDTO,
+        },
+        league::{LeagueInformationDTO, LeagueStandingsTable, TeamPositionProgression},
     },
     rocket,
     test::{
         register_and_login,
         utilities::{
             fixture_utilities::{
-                delete_all_fixtures_request, edit_fixture_request, generate_fixtures_request,
+                create_fixture_request, delete_all_fixtures_request, delete_fixture_request,
+                edit_fixture_pairing_request, edit_fixture_request, generate_fixtures_request,
+                generate_fixtures_with_query_request, generate_swiss_round_request,
                 get_fixture_by_id_request, get_league_fixtures_request,
+                get_league_fixtures_with_query_request, preview_fixtures_request,
+                schedule_fixtures_request, submit_fixtures_request,
+            },
+            league_utilities::{
+                add_team_to_league_request, create_league_request, edit_league_request,
+                get_league_request, get_league_standing_progression_request,
+                get_league_standings_table_request,
             },
-            league_utilities::{add_team_to_league_request, create_league_request},
             team_utilities::create_team_request,
             tournament_utilities::create_tournament_request,
         },
-        APIResponse, IdData,
+        APIResponse, EditedFixtureData, IdData, PaginatedAPIResponse,
     },
 };
 
@@ -30,7 +44,8 @@ async fn create_teams(
     authorization_token: Option<&String>,
     tournament_id: u64,
     league_id: u64,
-) {
+) -> Vec<u64> {
+    let mut team_ids = vec![];
     for number in 0..quantity {
         let team_name = format!("Team {}", number);
         let team_data = json!({
@@ -63,7 +78,10 @@ async fn create_teams(
             team_id,
         )
         .await;
+        team_ids.push(team_id);
     }
+
+    team_ids
 }
 
 // Creating a tournament and getting it's fixtures and validating the response body
@@ -807,3 +825,1741 @@ async fn editing_fixtures_unauthorized_user() {
         assert_ne!(response.status(), Status::Ok);
     }
 }
+
+// Paginating, filtering and sorting the league fixtures
+#[rocket::async_test]
+async fn paginated_league_fixtures() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+    // Creating a tournament
+    let tournament_data = r#"{
+        "name": "paginated fixtures",
+        "public": true
+    }"#;
+
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .expect("Expected tournament id data")
+        .data
+        .id;
+    // Creating a league
+    let league_data = r#"{
+            "name": "new league",
+            "completed": false
+        }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // 4 teams generates 3 rounds with 2 games each
+    create_teams(
+        &client,
+        4,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // Getting the first page
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "limit=4&offset=0",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.data.len(), 4);
+    assert_eq!(response_body.pagination.total, 6);
+    assert_eq!(response_body.pagination.limit, 4);
+
+    // Getting the last page
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "limit=4&offset=4",
+    )
+    .await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.data.len(), 2);
+
+    // Filtering by round
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "round=2",
+    )
+    .await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 2);
+    assert!(response_body.data.iter().all(|fixture| fixture.round == 2));
+
+    // Filtering by team, every team plays once per round
+    let team_id = response_body.data[0].home_team.id;
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        format!("team_id={}", team_id).as_str(),
+    )
+    .await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 3);
+
+    // No games were played yet
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "played=true",
+    )
+    .await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 0);
+
+    // Sorting by the last round
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "sort=round&order=desc",
+    )
+    .await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.data[0].round, 3);
+
+    // Invalid date filter
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "from=yesterday",
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // A league which does not exist, like the unpaged league routes
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        0,
+        "limit=10",
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// Adding a late entrant after some fixtures were played and regenerating the fixtures
+#[rocket::async_test]
+async fn regenerating_fixtures_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "regenerating",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "regenerating",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    create_teams(
+        &client,
+        4,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let changes = response
+        .into_json::<APIResponse<FixtureChangesDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(changes.created_fixtures.len(), 6);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+
+    // Playing the first round
+    let played_fixtures: Vec<&FixtureDataDTO> = fixtures
+        .iter()
+        .filter(|fixture| fixture.round == 1)
+        .collect();
+    for fixture in &played_fixtures {
+        let edit_data = r#"{ "home_score": 1, "away_score": 0, "played": true }"#;
+        let response = edit_fixture_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture.id,
+            edit_data,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    // A late entrant joins the league
+    let team_data = r#"{ "name": "Late entrant" }"#;
+    let response = create_team_request(
+        &client,
+        Some(&authorization_token),
+        team_data,
+        tournament_id,
+    )
+    .await;
+    let late_team_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+    let _response = add_team_to_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        late_team_id,
+    )
+    .await;
+
+    // Generating again is only allowed when regenerating
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = generate_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "regenerate=true",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let changes = response
+        .into_json::<APIResponse<FixtureChangesDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(changes.kept_fixtures >= played_fixtures.len());
+    assert!(changes.created_fixtures.iter().any(
+        |fixture| fixture.home_team_id == late_team_id || fixture.away_team_id == late_team_id
+    ));
+    assert!(changes
+        .created_fixtures
+        .iter()
+        .all(|fixture| fixture.round > 1));
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+
+    // 5 teams, so every one of the 10 pairings happens exactly once
+    assert_eq!(fixtures.len(), 10);
+    let mut pairings = vec![];
+    for fixture in &fixtures {
+        let pairing = (
+            fixture.home_team.id.min(fixture.away_team.id),
+            fixture.home_team.id.max(fixture.away_team.id),
+        );
+        assert!(!pairings.contains(&pairing));
+        pairings.push(pairing);
+
+        // A team never plays twice on the same round
+        assert_eq!(
+            fixtures
+                .iter()
+                .filter(|other| other.round == fixture.round
+                    && (other.home_team.id == fixture.home_team.id
+                        || other.away_team.id == fixture.home_team.id
+                        || other.home_team.id == fixture.away_team.id
+                        || other.away_team.id == fixture.away_team.id))
+                .count(),
+            1
+        );
+    }
+
+    // The played results are kept
+    for played_fixture in &played_fixtures {
+        let fixture = fixtures
+            .iter()
+            .find(|fixture| fixture.id == played_fixture.id)
+            .unwrap();
+        assert!(fixture.played);
+        assert_eq!(fixture.home_score, 1);
+    }
+}
+
+// Regenerating a partially played league with another seed keeps the round count and the venues alternating
+#[rocket::async_test]
+async fn regenerating_partially_played_fixtures_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "regenerating partially played",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "regenerating partially played",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let team_ids = create_teams(
+        &client,
+        6,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    let response = generate_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "seed=1",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+
+    // Playing the first two rounds
+    for fixture in fixtures.iter().filter(|fixture| fixture.round <= 2) {
+        let edit_data = r#"{ "home_score": 2, "away_score": 2, "played": true }"#;
+        let response = edit_fixture_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture.id,
+            edit_data,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    // Another seed gives a table which doesn't match the played rounds
+    let response = generate_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "regenerate=true&seed=2",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+
+    // The 15 pairings still fit on the 5 rounds of the Berger table
+    assert_eq!(fixtures.len(), 15);
+    assert_eq!(fixtures.iter().map(|fixture| fixture.round).max(), Some(5));
+
+    // No team plays more than two games in a row at home or away
+    for team_id in team_ids {
+        let mut team_fixtures: Vec<&FixtureDataDTO> = fixtures
+            .iter()
+            .filter(|fixture| fixture.home_team.id == team_id || fixture.away_team.id == team_id)
+            .collect();
+        team_fixtures.sort_by_key(|fixture| fixture.round);
+        assert_eq!(team_fixtures.len(), 5);
+
+        let venues: Vec<bool> = team_fixtures
+            .iter()
+            .map(|fixture| fixture.home_team.id == team_id)
+            .collect();
+        assert!(venues
+            .windows(3)
+            .all(|games| games[0] != games[1] || games[1] != games[2]));
+    }
+}
+
+// Generating the fixtures twice with the same seed gives the same draw
+#[rocket::async_test]
+async fn seeded_fixtures_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "seeded draw",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "seeded draw",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    create_teams(
+        &client,
+        8,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    let mut draws = vec![];
+    for _ in 0..2 {
+        let response = generate_fixtures_with_query_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            "seed=42",
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+        let changes = response
+            .into_json::<APIResponse<FixtureChangesDTO>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(changes.seed, 42);
+
+        let mut draw: Vec<(u16, u64, u64)> = changes
+            .created_fixtures
+            .iter()
+            .map(|fixture| (fixture.round, fixture.home_team_id, fixture.away_team_id))
+            .collect();
+        draw.sort();
+        draws.push(draw);
+
+        let response = get_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        let league = response
+            .into_json::<APIResponse<LeagueInformationDTO>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(league.fixture_seed, Some(42));
+
+        let response = delete_all_fixtures_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+    assert_eq!(draws[0].len(), 28);
+    assert_eq!(draws[0], draws[1]);
+
+    // Without a seed a new one is generated and saved on the league
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let changes = response
+        .into_json::<APIResponse<FixtureChangesDTO>>()
+        .await
+        .unwrap()
+        .data;
+
+    let response = get_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(league.fixture_seed, Some(changes.seed));
+
+    // Seeds which a JavaScript number can't hold are rejected
+    let response = generate_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "regenerate=true&seed=9007199254740992",
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// Every league size from 2 to 24 teams gets a draw where no team plays more than two consecutive games at home or away
+#[rocket::async_test]
+async fn balanced_fixtures_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "balanced draw",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "balanced draw",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    create_teams(
+        &client,
+        1,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    for teams_quantity in 2..=24 {
+        // One more team joins the league and the fixtures are drawn again from scratch
+        create_teams(
+            &client,
+            1,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        if teams_quantity > 2 {
+            let response = delete_all_fixtures_request(
+                &client,
+                Some(&authorization_token),
+                tournament_id,
+                league_id,
+            )
+            .await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+
+        let response = generate_fixtures_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+        let mut fixtures = response
+            .into_json::<APIResponse<FixtureChangesDTO>>()
+            .await
+            .unwrap()
+            .data
+            .created_fixtures;
+        assert_eq!(fixtures.len(), teams_quantity * (teams_quantity - 1) / 2);
+        fixtures.sort_by_key(|fixture| fixture.round);
+
+        // The sides each team played on, from the first to the last round
+        let mut team_sides: HashMap<u64, Vec<bool>> = HashMap::new();
+        for fixture in &fixtures {
+            team_sides
+                .entry(fixture.home_team_id)
+                .or_default()
+                .push(true);
+            team_sides
+                .entry(fixture.away_team_id)
+                .or_default()
+                .push(false);
+        }
+
+        assert_eq!(team_sides.len(), teams_quantity);
+        for sides in team_sides.values() {
+            assert_eq!(sides.len(), teams_quantity - 1);
+            assert!(sides
+                .windows(3)
+                .all(|window| !(window[0] == window[1] && window[1] == window[2])));
+
+            // The home games are split evenly, at most one more home or away game
+            let home_games = sides.iter().filter(|home| **home).count();
+            let away_games = sides.len() - home_games;
+            assert!(home_games.abs_diff(away_games) <= 1);
+        }
+    }
+}
+
+// Scheduling the fixtures with constraints, the met ones should be on the fixtures and the impossible ones reported
+#[rocket::async_test]
+async fn scheduling_fixtures_with_constraints_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "constrained schedule",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "constrained schedule",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let team_ids = create_teams(
+        &client,
+        5,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    // The first team can't play on the second round, which is a week after the start date
+    let constraints = json!({
+        "start_date": "2024-03-02",
+        "shared_grounds": [
+            { "first_team_id": team_ids[1], "second_team_id": team_ids[2] }
+        ],
+        "unavailable_dates": [
+            { "team_id": team_ids[0], "date": "2024-03-09" }
+        ],
+        "fixed_rounds": [
+            { "first_team_id": team_ids[3], "second_team_id": team_ids[4], "round": 1 }
+        ]
+    })
+    .to_string();
+
+    // Only the league owner can schedule the fixtures
+    let other_user_token = register_and_login(&client).await;
+    let response = schedule_fixtures_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        &constraints,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // The unavailable dates need a start date to know their rounds
+    let undated_constraints = json!({
+        "unavailable_dates": [
+            { "team_id": team_ids[0], "date": "2024-03-09" }
+        ]
+    })
+    .to_string();
+    let response = schedule_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &undated_constraints,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = schedule_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &constraints,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let scheduled_fixtures = response
+        .into_json::<APIResponse<ScheduledFixturesDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(scheduled_fixtures.unmet_constraints.is_empty());
+
+    let fixtures = scheduled_fixtures.created_fixtures;
+    assert_eq!(fixtures.len(), 10);
+    assert!(fixtures.iter().all(|fixture| fixture.round != 2
+        || (fixture.home_team_id != team_ids[0] && fixture.away_team_id != team_ids[0])));
+    assert!(fixtures.iter().any(|fixture| fixture.round == 1
+        && [fixture.home_team_id, fixture.away_team_id].contains(&team_ids[3])
+        && [fixture.home_team_id, fixture.away_team_id].contains(&team_ids[4])));
+    for round in 1..=5 {
+        let home_teams: Vec<u64> = fixtures
+            .iter()
+            .filter(|fixture| fixture.round == round)
+            .map(|fixture| fixture.home_team_id)
+            .collect();
+        assert!(!(home_teams.contains(&team_ids[1]) && home_teams.contains(&team_ids[2])));
+    }
+
+    // The league already has fixtures, so it can't be scheduled again
+    let response = schedule_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &constraints,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // With four teams everyone plays every round, so the unavailable date can't be met
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+    let team_ids = create_teams(
+        &client,
+        4,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    let constraints = json!({
+        "start_date": "2024-03-02",
+        "unavailable_dates": [
+            { "team_id": team_ids[0], "date": "2024-03-02" }
+        ]
+    })
+    .to_string();
+    let response = schedule_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &constraints,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let scheduled_fixtures = response
+        .into_json::<APIResponse<ScheduledFixturesDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(scheduled_fixtures.created_fixtures.len(), 6);
+    assert_eq!(
+        scheduled_fixtures.unmet_constraints.unavailable_dates[0].team_id,
+        team_ids[0]
+    );
+}
+
+// Previewing the fixtures, editing the draw and submitting it. Only a valid schedule should be saved
+#[rocket::async_test]
+async fn previewing_and_submitting_fixtures_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "previewed schedule",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "previewed schedule",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    create_teams(
+        &client,
+        4,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    // The same seed gives the same preview, and nothing is saved
+    let response = preview_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "seed=99",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let preview = response
+        .into_json::<APIResponse<FixturePreviewDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(preview.seed, 99);
+    assert_eq!(preview.fixtures.len(), 3);
+
+    let response = preview_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "seed=99",
+    )
+    .await;
+    let same_preview = response
+        .into_json::<APIResponse<FixturePreviewDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(
+        serde_json::to_value(&preview.fixtures).unwrap(),
+        serde_json::to_value(&same_preview.fixtures).unwrap()
+    );
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(fixtures.is_empty());
+
+    // Only the league owner can preview the fixtures
+    let other_user_token = register_and_login(&client).await;
+    let response = preview_fixtures_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        "",
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Swapping the first and last rounds and flipping the first fixture
+    let mut rounds = preview.fixtures;
+    rounds.reverse();
+    let first_fixture = &mut rounds[0][0];
+    std::mem::swap(
+        &mut first_fixture.home_team_id,
+        &mut first_fixture.away_team_id,
+    );
+    let flipped_fixture = (first_fixture.home_team_id, first_fixture.away_team_id);
+
+    // A schedule where a pair of teams plays twice is refused
+    let mut invalid_rounds = serde_json::to_value(&rounds).unwrap();
+    invalid_rounds[1] = invalid_rounds[0].clone();
+    let response = submit_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "fixtures": invalid_rounds }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = submit_fixtures_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        &json!({ "fixtures": rounds }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = submit_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "fixtures": rounds }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The rounds are numbered by their position on the submitted schedule
+    let response = get_league_fixtures_with_query_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        "round=1",
+    )
+    .await;
+    let fixtures = response
+        .into_json::<PaginatedAPIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(fixtures.len(), 2);
+    assert!(fixtures
+        .iter()
+        .any(|fixture| (fixture.home_team.id, fixture.away_team.id) == flipped_fixture));
+
+    // The league has fixtures now, so it can't be submitted again
+    let response = submit_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "fixtures": rounds }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// Creating, editing the pairing and deleting single fixtures. The teams must be on the league, not only on the tournament
+#[rocket::async_test]
+async fn manual_fixtures_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "manual fixtures",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "manual fixtures",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let team_ids = create_teams(
+        &client,
+        3,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    // A team of the tournament which did not join the league
+    let response = create_team_request(
+        &client,
+        Some(&authorization_token),
+        r#"{ "name": "Outsider" }"#,
+        tournament_id,
+    )
+    .await;
+    let outsider_team_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let fixture_data = |home_team_id: u64, away_team_id: u64, round: u16| {
+        json!({
+            "home_team_id": home_team_id,
+            "away_team_id": away_team_id,
+            "round": round
+        })
+        .to_string()
+    };
+
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &fixture_data(team_ids[0], outsider_team_id, 1),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &fixture_data(team_ids[0], team_ids[0], 1),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let other_user_token = register_and_login(&client).await;
+    let response = create_fixture_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        &fixture_data(team_ids[0], team_ids[1], 1),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &fixture_data(team_ids[0], team_ids[1], 1),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let fixture_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // The first team already plays on the first round
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &fixture_data(team_ids[2], team_ids[0], 1),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Moving the fixture to the second round against the third team
+    let response = edit_fixture_pairing_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture_id,
+        &json!({ "away_team_id": team_ids[2], "round": 2 }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_fixture_by_id_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture_id,
+    )
+    .await;
+    let fixture = response
+        .into_json::<APIResponse<FixtureDataDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(fixture.home_team.id, team_ids[0]);
+    assert_eq!(fixture.away_team.id, team_ids[2]);
+    assert_eq!(fixture.round, 2);
+
+    let response = edit_fixture_pairing_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture_id,
+        &json!({ "home_team_id": outsider_team_id }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // A played fixture can't change its teams
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture_id,
+        r#"{ "home_score": 1, "away_score": 0, "played": true }"#,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = edit_fixture_pairing_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture_id,
+        &json!({ "round": 3 }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Deleting the only pending fixture completes the league
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &fixture_data(team_ids[1], team_ids[2], 3),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let pending_fixture_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let response = delete_fixture_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        pending_fixture_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = delete_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        pending_fixture_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let deleted_fixture = response
+        .into_json::<APIResponse<EditedFixtureData>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(deleted_fixture.league_completed);
+
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &fixture_data(team_ids[1], team_ids[2], 3),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// Pairing a swiss league one round at a time, with a bye for the odd team and no rematches
+#[rocket::async_test]
+async fn swiss_rounds_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "swiss rounds",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // The number of rounds is only set on swiss leagues
+    let league_data = r#"{
+        "name": "swiss rounds",
+        "completed": false,
+        "swiss_rounds": 3
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let league_data = r#"{
+        "name": "swiss rounds",
+        "completed": false,
+        "format": "swiss",
+        "swiss_rounds": 3
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let team_ids = create_teams(
+        &client,
+        5,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+
+    // The swiss leagues can't draw every round at once
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let other_user_token = register_and_login(&client).await;
+    let response =
+        generate_swiss_round_request(&client, Some(&other_user_token), tournament_id, league_id)
+            .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // A single fixture can't be put on a round which was not paired yet
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "home_team_id": team_ids[0], "away_team_id": team_ids[1], "round": 1 })
+            .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let mut previous_pairs: Vec<(u64, u64)> = vec![];
+    let mut bye_team_ids: Vec<u64> = vec![];
+    // The points of each team before the round is paired
+    let mut points: HashMap<u64, u8> = team_ids.iter().map(|team_id| (*team_id, 0)).collect();
+    for round in 1..=2 {
+        let response = generate_swiss_round_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+        let swiss_round = response
+            .into_json::<APIResponse<SwissRoundDTO>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(swiss_round.round, round);
+        assert_eq!(swiss_round.created_fixtures.len(), 2);
+
+        // Every team plays once, except the one with the bye
+        let bye_team_id = swiss_round.bye_team_id.expect("Expected a bye team");
+        assert!(!bye_team_ids.contains(&bye_team_id));
+        bye_team_ids.push(bye_team_id);
+        let mut playing_team_ids: Vec<u64> = swiss_round
+            .created_fixtures
+            .iter()
+            .flat_map(|fixture| [fixture.home_team_id, fixture.away_team_id])
+            .collect();
+        playing_team_ids.push(bye_team_id);
+        playing_team_ids.sort();
+        assert_eq!(playing_team_ids, team_ids);
+
+        for fixture in &swiss_round.created_fixtures {
+            let pair = (
+                fixture.home_team_id.min(fixture.away_team_id),
+                fixture.home_team_id.max(fixture.away_team_id),
+            );
+            assert!(!previous_pairs.contains(&pair));
+            previous_pairs.push(pair);
+
+            // Every home team won the first round, so the teams on equal points can meet on the second one
+            assert_eq!(points[&fixture.home_team_id], points[&fixture.away_team_id]);
+        }
+
+        // The next round is only paired after every result is known
+        let response = generate_swiss_round_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = get_league_fixtures_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        let fixtures = response
+            .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+            .await
+            .unwrap()
+            .data;
+        for fixture in fixtures.iter().filter(|fixture| !fixture.played) {
+            let response = edit_fixture_request(
+                &client,
+                Some(&authorization_token),
+                tournament_id,
+                league_id,
+                fixture.id,
+                r#"{ "home_score": 1, "away_score": 0, "played": true }"#,
+            )
+            .await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+
+        let response = get_league_standings_table_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        points = response
+            .into_json::<APIResponse<LeagueStandingsTable>>()
+            .await
+            .unwrap()
+            .data
+            .overall
+            .iter()
+            .map(|standing| (standing.team_id, standing.total_points))
+            .collect();
+    }
+
+    // The swiss league is not completed before its last round is played
+    let response = get_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(!league.completed);
+
+    let response = get_league_standings_table_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let standings = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(standings.overall.len(), 5);
+    let mut total_points: Vec<u8> = standings
+        .overall
+        .iter()
+        .map(|standing| standing.total_points)
+        .collect();
+    total_points.sort();
+    assert_eq!(total_points, vec![0, 0, 3, 3, 6]);
+
+    // Buchholz adds the points of every opponent and Sonneborn-Berger the ones of the beaten opponents, every home team won 1 - 0
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    for standing in &standings.overall {
+        let mut buchholz = 0;
+        let mut sonneborn_berger = 0.0;
+        for fixture in &fixtures {
+            if fixture.home_team.id == standing.team_id {
+                buchholz += points[&fixture.away_team.id] as u16;
+                sonneborn_berger += points[&fixture.away_team.id] as f32;
+            } else if fixture.away_team.id == standing.team_id {
+                buchholz += points[&fixture.home_team.id] as u16;
+            }
+        }
+
+        assert_eq!(standing.buchholz, Some(buchholz));
+        assert_eq!(standing.sonneborn_berger, Some(sonneborn_berger));
+    }
+
+    // The leader won both fixtures, so it beat every opponent it played
+    let leader = &standings.overall[0];
+    assert_eq!(leader.total_points, 6);
+    assert_eq!(
+        leader.sonneborn_berger,
+        leader.buchholz.map(|buchholz| buchholz as f32)
+    );
+    assert!(leader.buchholz.unwrap() > 0);
+    assert!(standings
+        .overall
+        .windows(2)
+        .all(|pair| pair[0].total_points >= pair[1].total_points));
+
+    // The progression breaks the ties of each round with the same tiebreaks as the standing table
+    let response = get_league_standing_progression_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let progression = response
+        .into_json::<APIResponse<Vec<TeamPositionProgression>>>()
+        .await
+        .unwrap()
+        .data;
+    for (index, standing) in standings.overall.iter().enumerate() {
+        let team = progression
+            .iter()
+            .find(|team| team.team_id == standing.team_id)
+            .unwrap();
+        assert_eq!(team.positions.len(), 2);
+        assert_eq!(team.positions[1].round, 2);
+        assert_eq!(team.positions[1].position, index as u32 + 1);
+    }
+
+    // The league is completed with the last result of its third round
+    let response = generate_swiss_round_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let swiss_round = response
+        .into_json::<APIResponse<SwissRoundDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(swiss_round.round, 3);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let pending_fixtures: Vec<FixtureDataDTO> = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data
+        .into_iter()
+        .filter(|fixture| !fixture.played)
+        .collect();
+    assert_eq!(pending_fixtures.len(), 2);
+    for (index, fixture) in pending_fixtures.iter().enumerate() {
+        let response = edit_fixture_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture.id,
+            r#"{ "home_score": 2, "away_score": 2, "played": true }"#,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+        let edited = response
+            .into_json::<APIResponse<EditedFixtureData>>()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(edited.league_completed, index == 1);
+    }
+
+    let response = get_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(league.completed);
+    assert_eq!(league.swiss_rounds, Some(3));
+    assert!(league.champion.is_some());
+
+    // The rounds which were already paired can't be removed
+    let response = edit_league_request(
+        &client,
+        Some(&authorization_token),
+        r#"{ "name": "swiss rounds", "completed": false, "swiss_rounds": 2 }"#,
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -0,0 +1,207 @@
+use rocket::{http::Status, local::asynchronous::Client};
+use serde_json::json;
+
+use crate::{
+    models::{
+        fixture::FixtureDataDTO,
+        import::{ImportFile, LeagueImportReportDTO},
+    },
+    rocket,
+    test::{
+        register_and_login,
+        utilities::{
+            fixture_utilities::get_league_fixtures_request,
+            import_utilities::{import_league_request, preview_league_import_request},
+            league_utilities::create_league_request,
+            team_utilities::create_team_request,
+            tournament_utilities::create_tournament_request,
+        },
+        APIResponse, IdData,
+    },
+};
+
+// Imports the teams and the played fixtures of a league, the invalid rows are reported and nothing is saved
+#[rocket::async_test]
+async fn import_league_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "imports",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "imported league",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // A tournament team which is reused by the import
+    let response = create_team_request(
+        &client,
+        Some(&authorization_token),
+        r#"{ "name": "Rovers" }"#,
+        tournament_id,
+    )
+    .await;
+    let existing_team_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let teams = "name\r\nRovers\r\n\"United, FC\"\r\nCity\r\n";
+    let invalid_import = json!({
+        "teams": teams,
+        "fixtures": "home_team,away_team,round,home_score,away_score\nRovers,Rovers,1,,\nCity,Wanderers,1,,\nRovers,City,2,1,\n"
+    })
+    .to_string();
+
+    let response = preview_league_import_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &invalid_import,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report = response
+        .into_json::<APIResponse<LeagueImportReportDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(report.teams.len(), 3);
+    assert_eq!(report.teams[0].team_id, Some(existing_team_id));
+    assert!(!report.teams[0].created);
+    assert_eq!(report.teams[1].name, "United, FC");
+    assert!(report.teams[1].created);
+    let error_rows: Vec<(ImportFile, usize)> = report
+        .errors
+        .iter()
+        .map(|error| (error.file, error.row))
+        .collect();
+    assert_eq!(
+        error_rows,
+        vec![
+            (ImportFile::Fixtures, 2),
+            (ImportFile::Fixtures, 3),
+            (ImportFile::Fixtures, 4)
+        ]
+    );
+
+    let response = import_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &invalid_import,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+    // The invalid rows are sent back like on the preview
+    let report = response
+        .into_json::<APIResponse<LeagueImportReportDTO>>()
+        .await
+        .unwrap()
+        .data;
+    let import_error_rows: Vec<(ImportFile, usize)> = report
+        .errors
+        .iter()
+        .map(|error| (error.file, error.row))
+        .collect();
+    assert_eq!(import_error_rows, error_rows);
+
+    // Nothing was saved by the invalid import
+    let response = preview_league_import_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({ "teams": teams }).to_string(),
+    )
+    .await;
+    let report = response
+        .into_json::<APIResponse<LeagueImportReportDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(report.teams.iter().all(|team| team.added_to_league));
+
+    let other_user_token = register_and_login(&client).await;
+    let valid_import = json!({
+        "teams": teams,
+        "fixtures": "round,home_team,away_team,home_score,away_score\n1,Rovers,\"United, FC\",2,1\n2,City,Rovers,0,0\n3,\"United, FC\",City,1,3\n"
+    })
+    .to_string();
+    let response = import_league_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        &valid_import,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = import_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &valid_import,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let report = response
+        .into_json::<APIResponse<LeagueImportReportDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(report.errors.is_empty());
+    assert!(report.teams.iter().all(|team| team.team_id.is_some()));
+    assert_eq!(report.fixtures.len(), 3);
+    // Every fixture of the round robin league is played
+    assert!(report.league_completed);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(fixtures.len(), 3);
+    assert!(fixtures.iter().all(|fixture| fixture.played));
+    let first_fixture = fixtures.iter().find(|fixture| fixture.round == 1).unwrap();
+    assert_eq!(first_fixture.home_team.id, existing_team_id);
+    assert_eq!(first_fixture.home_score, 2);
+    assert_eq!(first_fixture.away_team.name, "United, FC");
+}
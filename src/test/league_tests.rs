@@ -1,19 +1,31 @@
 use rocket::{http::Status, local::asynchronous::Client};
 
 use crate::{
-    models::league::{LeagueRegisterDTO, TeamStandingTable},
+    models::{
+        fixture::FixtureDataDTO,
+        league::{
+            ArchivedLeagueDTO, ArchivedSeasonDTO, LeagueInformationDTO, LeagueRegisterDTO,
+            LeagueStandingsTable, NewSeasonLeagueDTO, TeamPositionProgression,
+        },
+    },
     rocket,
     test::{
         utilities::{
+            fixture_utilities::{
+                edit_fixture_request, generate_fixtures_request, get_league_fixtures_request,
+            },
             league_utilities::{
-                add_team_to_league_request, create_league_request, delete_league_request,
-                get_league_request, get_league_standings_table_request,
-                remove_team_from_league_request,
+                add_team_to_league_request, create_league_request, create_new_season_request,
+                delete_league_request, get_archived_season_request, get_league_request,
+                get_league_standing_progression_request, get_league_standings_table_request,
+                get_league_standings_table_with_query_request, get_tournament_archive_request,
+                get_tournament_leagues_request, remove_team_from_league_request,
+                withdraw_team_from_league_request,
             },
-            team_utilities::create_team_request,
+            team_utilities::{create_team_request, delete_team_request, edit_team_request},
             tournament_utilities::create_tournament_request,
         },
-        APIResponse, IdData,
+        APIResponse, EditedFixtureData, IdData, PaginatedAPIResponse,
     },
 };
 
@@ -906,9 +918,9 @@ async fn remove_team_from_non_existent_league_test() {
     assert_ne!(response.status(), Status::NoContent);
 }
 
-// Creating a tournament and getting it's standing table
-// Doesn't really matter if the tournament is public or not
-// As the database query logic will return empty if happens an unauthorized access
+// Creating a private tournament and getting it's standing table
+// The route passes the league, user and tournament ids in the order the query expects,
+// so the owner should see the teams of the league
 #[rocket::async_test]
 async fn get_league_standing_table_test() {
     let client = Client::tracked(rocket().await).await.unwrap();
@@ -916,7 +928,7 @@ async fn get_league_standing_table_test() {
     // Creating a tournament
     let tournament_data = r#"{
         "name": "adding team",
-        "public": true
+        "public": false
     }"#;
 
     let response =
@@ -944,6 +956,30 @@ async fn get_league_standing_table_test() {
     // Getting the league id
     let response_body = response.into_json::<APIResponse<IdData>>().await.unwrap();
     let league_id = response_body.data.id;
+    // Adding a team to the league
+    let response = create_team_request(
+        &client,
+        Some(&authorization_token),
+        r#"{ "name": "standing team" }"#,
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let team_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+    let response = add_team_to_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        team_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
     // Getting the league standing table
     let response = get_league_standings_table_request(
         &client,
@@ -953,10 +989,14 @@ async fn get_league_standing_table_test() {
     )
     .await;
     assert_eq!(response.status(), Status::Ok);
-    response
-        .into_json::<APIResponse<Vec<TeamStandingTable>>>()
+    let standing_table = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
         .await
-        .unwrap();
+        .unwrap()
+        .data
+        .overall;
+    assert_eq!(standing_table.len(), 1);
+    assert_eq!(standing_table[0].team_id, team_id);
 }
 
 // Creating more than 24 teams
@@ -1036,3 +1076,1417 @@ async fn creating_more_than_24_teams() {
         }
     }
 }
+
+// Listing the tournament leagues filtered by the completed flag
+#[rocket::async_test]
+async fn list_tournament_leagues() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "league listing",
+        "public": true
+    }"#;
+
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .expect("Expected tournament id data")
+        .data
+        .id;
+
+    // Creating a completed league and two ongoing leagues
+    for (name, completed) in [("2023", true), ("2024", false), ("2024 cup", false)] {
+        let league_data = serde_json::to_string(&LeagueRegisterDTO {
+            name: String::from(name),
+            completed,
+            zones: None,
+            format: None,
+            swiss_rounds: None,
+        })
+        .unwrap();
+        let response = create_league_request(
+            &client,
+            Some(&authorization_token),
+            league_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+    }
+
+    // Getting the ongoing leagues
+    let response = get_tournament_leagues_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        "completed=false",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<LeagueInformationDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 2);
+    assert!(response_body.data.iter().all(|league| !league.completed));
+
+    // Paginating the leagues as an unauthenticated user, the tournament is public
+    let response =
+        get_tournament_leagues_request(&client, None, tournament_id, "limit=1&offset=1").await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<LeagueInformationDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 3);
+    assert_eq!(response_body.data.len(), 1);
+    assert_eq!(response_body.data[0].name, "2024");
+
+    // A tournament which does not exist, like the unpaged tournament route
+    let response = get_tournament_leagues_request(&client, None, 0, "").await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// Playing the first 2 rounds and checking the table after each round
+#[rocket::async_test]
+async fn standing_table_by_round_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "standings by round",
+        "public": true
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "rounds league",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    for team_name in ["Lions", "Tigers", "Bears", "Wolves"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        let response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+
+    // The home teams win on the first round and every fixture of the second round is a draw
+    let mut first_round_winners = vec![];
+    for fixture in fixtures.iter().filter(|fixture| fixture.round <= 2) {
+        let edit_data = if fixture.round == 1 {
+            first_round_winners.push(fixture.home_team.id);
+            r#"{ "home_score": 1, "away_score": 0, "played": true }"#
+        } else {
+            r#"{ "home_score": 1, "away_score": 1, "played": true }"#
+        };
+        let response = edit_fixture_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture.id,
+            edit_data,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    // After the first round, the winners are on the top of the table
+    let response = get_league_standings_table_with_query_request(
+        &client,
+        None,
+        tournament_id,
+        league_id,
+        "after_round=1",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let standing_table = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
+        .await
+        .unwrap()
+        .data
+        .overall;
+    assert_eq!(standing_table.len(), 4);
+    assert!(first_round_winners.contains(&standing_table[0].team_id));
+    assert!(first_round_winners.contains(&standing_table[1].team_id));
+    assert_eq!(standing_table[0].total_points, 3);
+    assert_eq!(standing_table[2].total_points, 0);
+
+    // The current table also counts the draws from the second round
+    let response =
+        get_league_standings_table_request(&client, None, tournament_id, league_id).await;
+    let standing_table = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
+        .await
+        .unwrap()
+        .data
+        .overall;
+    assert_eq!(standing_table[0].total_points, 4);
+    assert_eq!(standing_table[0].matches_played, 2);
+    assert_eq!(standing_table[0].form, "WD");
+    assert_eq!(standing_table[3].total_points, 1);
+    assert_eq!(standing_table[3].form, "LD");
+
+    // Each team has a position for both played rounds
+    let response =
+        get_league_standing_progression_request(&client, None, tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::Ok);
+    let progression = response
+        .into_json::<APIResponse<Vec<TeamPositionProgression>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(progression.len(), 4);
+    for team in &progression {
+        assert_eq!(team.positions.len(), 2);
+        assert_eq!(team.positions[0].round, 1);
+        assert_eq!(team.positions[1].round, 2);
+
+        let first_round = &team.positions[0];
+        if first_round_winners.contains(&team.team_id) {
+            assert!(first_round.position <= 2);
+            assert_eq!(first_round.total_points, 3);
+        } else {
+            assert!(first_round.position > 2);
+        }
+    }
+
+    // The positions after the last round match the standing table
+    for (index, standing) in standing_table.iter().enumerate() {
+        let team = progression
+            .iter()
+            .find(|team| team.team_id == standing.team_id)
+            .unwrap();
+        assert_eq!(team.positions[1].position, index as u32 + 1);
+    }
+
+    // A league that does not exist has no progression
+    let response = get_league_standing_progression_request(&client, None, tournament_id, 0).await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// A home win should only be counted on the overall and home tables for the winner, and on the away table for the loser
+#[rocket::async_test]
+async fn home_and_away_standing_tables_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "home and away",
+        "public": true
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "venues league",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    for team_name in ["Lions", "Tigers"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        let _response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+    }
+
+    let _response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixture = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data
+        .remove(0);
+
+    let edit_data = r#"{ "home_score": 2, "away_score": 1, "played": true }"#;
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture.id,
+        edit_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response =
+        get_league_standings_table_request(&client, None, tournament_id, league_id).await;
+    assert_eq!(response.status(), Status::Ok);
+    let tables = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
+        .await
+        .unwrap()
+        .data;
+
+    // Overall, both teams played once
+    assert_eq!(tables.overall[0].team_id, fixture.home_team.id);
+    assert_eq!(tables.overall[0].matches_played, 1);
+    assert_eq!(tables.overall[0].form, "W");
+    assert_eq!(tables.overall[1].matches_played, 1);
+    assert_eq!(tables.overall[1].form, "L");
+
+    // Only the home team played at home
+    assert_eq!(tables.home[0].team_id, fixture.home_team.id);
+    assert_eq!(tables.home[0].total_points, 3);
+    assert_eq!(tables.home[1].matches_played, 0);
+    assert_eq!(tables.home[1].form, "");
+
+    // Only the away team played away
+    let away_team = tables
+        .away
+        .iter()
+        .find(|standing| standing.team_id == fixture.away_team.id)
+        .unwrap();
+    assert_eq!(away_team.matches_played, 1);
+    assert_eq!(away_team.goals_scored, 1);
+    assert_eq!(away_team.form, "L");
+}
+
+// Zones are stored with the league and mark the overall standing table positions
+#[rocket::async_test]
+async fn standing_zones_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "zones",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "zones league",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    for team_name in ["North", "South", "East", "West"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        let _response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+    }
+
+    // Zones from different sides of the table can't be mixed
+    let edit_data = r##"{
+        "name": "zones league",
+        "completed": false,
+        "zones": [{ "label": "Playoffs", "color": "#FFA500", "start_position": 2, "end_position": -2 }]
+    }"##;
+    let response = edit_league_request(
+        &client,
+        Some(&authorization_token),
+        edit_data,
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let edit_data = r##"{
+        "name": "zones league",
+        "completed": false,
+        "zones": [
+            { "label": "Promotion", "color": "#1E90FF", "start_position": 1, "end_position": 1 },
+            { "label": "Relegation", "color": "#DC143C", "start_position": -1, "end_position": -1 }
+        ]
+    }"##;
+    let response = edit_league_request(
+        &client,
+        Some(&authorization_token),
+        edit_data,
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // Editing without zones keeps the current ones
+    let edit_data = r#"{
+        "name": "zones renamed",
+        "completed": false
+    }"#;
+    let response = edit_league_request(
+        &client,
+        Some(&authorization_token),
+        edit_data,
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    let zones = league.zones.expect("Expected the league zones");
+    assert_eq!(zones.len(), 2);
+    assert_eq!(zones[0].label, "Promotion");
+    assert_eq!(zones[1].start_position, -1);
+
+    let response = get_league_standings_table_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let tables = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(tables.overall.len(), 4);
+    assert_eq!(tables.overall[0].zone.as_ref().unwrap().label, "Promotion");
+    assert!(tables.overall[1].zone.is_none());
+    assert!(tables.overall[2].zone.is_none());
+    assert_eq!(tables.overall[3].zone.as_ref().unwrap().color, "#DC143C");
+    assert!(tables.home.iter().all(|standing| standing.zone.is_none()));
+}
+
+// Starting a new season promotes the top teams and relegates the bottom teams between divisions
+#[rocket::async_test]
+async fn new_season_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "divisions",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // Creating the first and second divisions with 3 teams each
+    let mut league_ids: Vec<u64> = vec![];
+    let mut division_teams: Vec<Vec<u64>> = vec![];
+    for division in ["first", "second"] {
+        let league_data = format!(r#"{{ "name": "{} 2023", "completed": false }}"#, division);
+        let response = create_league_request(
+            &client,
+            Some(&authorization_token),
+            league_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        let league_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        let mut teams = vec![];
+        for index in 0..3 {
+            let team_data = format!(r#"{{ "name": "{} team {}" }}"#, division, index);
+            let response = create_team_request(
+                &client,
+                Some(&authorization_token),
+                team_data.as_str(),
+                tournament_id,
+            )
+            .await;
+            let team_id = response
+                .into_json::<APIResponse<IdData>>()
+                .await
+                .unwrap()
+                .data
+                .id;
+            let _response = add_team_to_league_request(
+                &client,
+                Some(&authorization_token),
+                tournament_id,
+                league_id,
+                team_id,
+            )
+            .await;
+            teams.push(team_id);
+        }
+
+        league_ids.push(league_id);
+        division_teams.push(teams);
+    }
+
+    // The away team wins on the second division, so it's the champion
+    let _response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_ids[1],
+    )
+    .await;
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_ids[1],
+    )
+    .await;
+    let fixture = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data
+        .remove(0);
+    let edit_data = r#"{ "home_score": 0, "away_score": 3, "played": true }"#;
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_ids[1],
+        fixture.id,
+        edit_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let season_data = format!(
+        r#"{{
+            "divisions": [
+                {{ "league_id": {}, "name": "first 2024" }},
+                {{ "league_id": {}, "name": "second 2024" }}
+            ],
+            "exchanged_teams": 1
+        }}"#,
+        league_ids[0], league_ids[1]
+    );
+
+    // The leagues are not completed yet
+    let response = create_new_season_request(
+        &client,
+        Some(&authorization_token),
+        season_data.as_str(),
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    for (league_id, name) in league_ids.iter().zip(["first 2023", "second 2023"]) {
+        let edit_data = format!(r#"{{ "name": "{}", "completed": true }}"#, name);
+        let response = edit_league_request(
+            &client,
+            Some(&authorization_token),
+            edit_data.as_str(),
+            tournament_id,
+            *league_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    // Another user can't start a season on the tournament
+    let other_token = register_and_login(&client).await;
+    let response = create_new_season_request(
+        &client,
+        Some(&other_token),
+        season_data.as_str(),
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = create_new_season_request(
+        &client,
+        Some(&authorization_token),
+        season_data.as_str(),
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let new_leagues = response
+        .into_json::<APIResponse<Vec<NewSeasonLeagueDTO>>>()
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(new_leagues.len(), 2);
+    assert_eq!(new_leagues[0].name, "first 2024");
+    assert_eq!(new_leagues[0].previous_league_id, league_ids[0]);
+    assert_eq!(new_leagues[1].previous_league_id, league_ids[1]);
+
+    // Nobody played on the first division, so the table is ordered by the team id and the last team is relegated
+    let relegated_team = division_teams[0][2];
+    assert_eq!(new_leagues[0].promoted, vec![fixture.away_team.id]);
+    assert!(new_leagues[0].relegated.is_empty());
+    assert_eq!(new_leagues[1].relegated, vec![relegated_team]);
+    assert!(new_leagues[1].promoted.is_empty());
+
+    assert_eq!(new_leagues[0].teams.len(), 3);
+    assert!(new_leagues[0].teams.contains(&fixture.away_team.id));
+    assert!(!new_leagues[0].teams.contains(&relegated_team));
+    assert_eq!(new_leagues[1].teams.len(), 3);
+    assert!(new_leagues[1].teams.contains(&relegated_team));
+    assert!(!new_leagues[1].teams.contains(&fixture.away_team.id));
+
+    // The new leagues are ongoing
+    let response = get_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        new_leagues[1].id,
+    )
+    .await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(!league.completed);
+}
+
+// A team can't be carried over to two divisions of the new season
+#[rocket::async_test]
+async fn new_season_team_on_two_divisions_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "shared team",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let response = create_team_request(
+        &client,
+        Some(&authorization_token),
+        r#"{ "name": "shared team" }"#,
+        tournament_id,
+    )
+    .await;
+    let team_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // The same team plays both leagues, which are completed right away
+    let mut league_ids: Vec<u64> = vec![];
+    for division in ["first", "second"] {
+        let league_data = format!(r#"{{ "name": "{} 2023", "completed": false }}"#, division);
+        let response = create_league_request(
+            &client,
+            Some(&authorization_token),
+            league_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        let league_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        let response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let edit_data = format!(r#"{{ "name": "{} 2023", "completed": true }}"#, division);
+        let response = edit_league_request(
+            &client,
+            Some(&authorization_token),
+            edit_data.as_str(),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+
+        league_ids.push(league_id);
+    }
+
+    let season_data = format!(
+        r#"{{
+            "divisions": [
+                {{ "league_id": {}, "name": "first 2024" }},
+                {{ "league_id": {}, "name": "second 2024" }}
+            ],
+            "exchanged_teams": 0
+        }}"#,
+        league_ids[0], league_ids[1]
+    );
+    let response = create_new_season_request(
+        &client,
+        Some(&authorization_token),
+        season_data.as_str(),
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// Playing every fixture completes the league, which is locked until it's reopened
+#[rocket::async_test]
+async fn league_completion_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "completion",
+        "public": true
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "final",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for team_name in ["Lions", "Tigers", "Bears"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        team_ids.push(
+            response
+                .into_json::<APIResponse<IdData>>()
+                .await
+                .unwrap()
+                .data
+                .id,
+        );
+    }
+
+    // Only two teams play the league
+    for team_id in &team_ids[..2] {
+        let response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            *team_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixture = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data
+        .remove(0);
+
+    // The only fixture is played, so the league is completed and the home team is the champion
+    let edit_data = r#"{ "home_score": 2, "away_score": 0, "played": true }"#;
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture.id,
+        edit_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let edited_fixture = response
+        .into_json::<APIResponse<EditedFixtureData>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(edited_fixture.league_completed);
+
+    let response = get_league_request(&client, None, tournament_id, league_id).await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(league.completed);
+    assert_eq!(league.champion.unwrap().id, fixture.home_team.id);
+
+    // The completed league can't be changed
+    let edit_data = r#"{ "home_score": 0, "away_score": 1, "played": true }"#;
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture.id,
+        edit_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = add_team_to_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        team_ids[2],
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Reopening the league allows the fixture to be edited, completing it again with another champion
+    let league_data = r#"{
+        "name": "final",
+        "completed": false
+    }"#;
+    let response = edit_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_request(&client, None, tournament_id, league_id).await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(!league.completed);
+    assert!(league.champion.is_none());
+
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture.id,
+        edit_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let edited_fixture = response
+        .into_json::<APIResponse<EditedFixtureData>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(edited_fixture.league_completed);
+
+    let response = get_league_request(&client, None, tournament_id, league_id).await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(league.champion.unwrap().id, fixture.away_team.id);
+}
+
+// The archive keeps the final table and results of a completed league after its teams are renamed or deleted
+#[rocket::async_test]
+async fn archived_season_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "archive",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "2023",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    for team_name in ["Lions", "Tigers"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        let _response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+    }
+
+    let _response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixture = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data
+        .remove(0);
+
+    // The team can't be deleted while its league is being played, its fixtures would be deleted with it
+    let response = delete_team_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        fixture.away_team.id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Playing the only fixture completes the league
+    let edit_data = r#"{ "home_score": 3, "away_score": 1, "played": true }"#;
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture.id,
+        edit_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // Renaming the champion and deleting the other team
+    let team_data = r#"{ "name": "Renamed Lions" }"#;
+    let response = edit_team_request(
+        &client,
+        Some(&authorization_token),
+        team_data,
+        tournament_id,
+        fixture.home_team.id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response = delete_team_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        fixture.away_team.id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    // The private archive can only be seen by the owner
+    let response = get_tournament_archive_request(&client, None, tournament_id).await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response =
+        get_tournament_archive_request(&client, Some(&authorization_token), tournament_id).await;
+    assert_eq!(response.status(), Status::Ok);
+    let archive = response
+        .into_json::<PaginatedAPIResponse<Vec<ArchivedLeagueDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(archive.pagination.total, 1);
+    assert_eq!(archive.data[0].id, league_id);
+    assert_eq!(
+        archive.data[0].champion.as_ref().unwrap().name,
+        fixture.home_team.name
+    );
+
+    let response = get_archived_season_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let season = response
+        .into_json::<APIResponse<ArchivedSeasonDTO>>()
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(season.standings.len(), 2);
+    assert_eq!(season.standings[0].position, 1);
+    assert_eq!(season.standings[0].team_name, fixture.home_team.name);
+    assert_eq!(season.standings[0].total_points, 3);
+    assert_eq!(season.standings[1].team_id, fixture.away_team.id);
+    assert_eq!(season.standings[1].team_name, fixture.away_team.name);
+
+    assert_eq!(season.fixtures.len(), 1);
+    assert_eq!(season.fixtures[0].away_team.name, fixture.away_team.name);
+    assert_eq!(season.fixtures[0].home_score, 3);
+    assert!(season.fixtures[0].played);
+}
+
+// Withdrawing teams mid-season, forfeiting the pending fixtures of one and expunging every fixture of the other
+#[rocket::async_test]
+async fn team_withdrawal_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "withdrawal",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "withdrawal",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for team_name in ["Lions", "Tigers", "Bears", "Wolves"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        let _response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    let _response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(fixtures.len(), 6);
+
+    // The forfeiting team wins the only fixture it plays before withdrawing
+    let forfeiting_team = team_ids[0];
+    let played_fixture = fixtures
+        .iter()
+        .find(|fixture| fixture.home_team.id == forfeiting_team)
+        .unwrap();
+    let beaten_team = played_fixture.away_team.id;
+    let edit_data = r#"{ "home_score": 2, "away_score": 0, "played": true }"#;
+    let _response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        played_fixture.id,
+        edit_data,
+    )
+    .await;
+
+    // Without a policy the pending fixtures are forfeited, so the played result is kept
+    let response = remove_team_from_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        forfeiting_team,
+    )
+    .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(fixtures.len(), 6);
+    for fixture in fixtures.iter().filter(|fixture| {
+        fixture.home_team.id == forfeiting_team || fixture.away_team.id == forfeiting_team
+    }) {
+        assert!(fixture.played);
+        if fixture.id == played_fixture.id {
+            assert_eq!((fixture.home_score, fixture.away_score), (2, 0));
+        } else if fixture.home_team.id == forfeiting_team {
+            assert_eq!((fixture.home_score, fixture.away_score), (0, 3));
+        } else {
+            assert_eq!((fixture.home_score, fixture.away_score), (3, 0));
+        }
+    }
+
+    // The withdrawn team leaves the table, but the results against it are kept
+    let response = get_league_standings_table_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let standings = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
+        .await
+        .unwrap()
+        .data
+        .overall;
+    assert_eq!(standings.len(), 3);
+    assert!(standings
+        .iter()
+        .all(|standing| standing.team_id != forfeiting_team));
+    let beaten_standing = standings
+        .iter()
+        .find(|standing| standing.team_id == beaten_team)
+        .unwrap();
+    assert_eq!(beaten_standing.matches_played, 1);
+    assert_eq!(beaten_standing.loss, 1);
+    // The other teams won the fixtures which the withdrawn team forfeited
+    for standing in standings
+        .iter()
+        .filter(|standing| standing.team_id != beaten_team)
+    {
+        assert_eq!(standing.matches_played, 1);
+        assert_eq!(standing.win, 1);
+        assert_eq!(standing.goals_scored, 3);
+    }
+
+    // Expunging removes every fixture of the team, including the ones it forfeited
+    let expunged_team = beaten_team;
+    let response = withdraw_team_from_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        expunged_team,
+        "expunge",
+    )
+    .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(fixtures.len(), 3);
+    assert!(fixtures.iter().all(|fixture| {
+        fixture.home_team.id != expunged_team && fixture.away_team.id != expunged_team
+    }));
+
+    let response = get_league_standings_table_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let standings = response
+        .into_json::<APIResponse<LeagueStandingsTable>>()
+        .await
+        .unwrap()
+        .data
+        .overall;
+    assert_eq!(standings.len(), 2);
+    for standing in &standings {
+        // Only the forfeit win over the first withdrawn team is left
+        assert_eq!(standing.matches_played, 1);
+        assert_eq!(standing.win, 1);
+    }
+}
@@ -3,7 +3,10 @@ use rocket::http::{ContentType, Status};
 use rocket::local::asynchronous::Client;
 use serde::Deserialize;
 
-use crate::models::user::{UserLoginDTO, UserSignUpDTO};
+use crate::models::{
+    pagination::PaginationData,
+    user::{UserLoginDTO, UserSignUpDTO},
+};
 
 #[derive(Debug, Deserialize)]
 struct LoginData {
@@ -15,6 +18,11 @@ struct IdData {
     id: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct EditedFixtureData {
+    league_completed: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct APIResponse<T> {
     #[allow(dead_code)]
@@ -22,6 +30,14 @@ struct APIResponse<T> {
     data: T,
 }
 
+#[derive(Debug, Deserialize)]
+struct PaginatedAPIResponse<T> {
+    #[allow(dead_code)]
+    message: String,
+    data: T,
+    pagination: PaginationData,
+}
+
 async fn simulate_login(client: &Client, login_dto: UserLoginDTO) -> String {
     let json_input = serde_json::to_string(&login_dto).unwrap();
 
@@ -84,8 +100,15 @@ async fn register_and_login(client: &Client) -> String {
 
 mod utilities;
 
+mod authentication_tests;
 mod index;
 mod league_tests;
+mod oidc_tests;
 mod team_tests;
 mod tournament_tests;
-mod fixture_tests;
\ No newline at end of file
+mod fixture_tests;
+mod bracket_tests;
+mod tie_tests;
+mod import_tests;
+mod export_tests;
+mod calendar_tests;
\ No newline at end of file
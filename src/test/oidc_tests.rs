@@ -0,0 +1,418 @@
+use std::{
+    net::Ipv4Addr,
+    sync::{Mutex, OnceLock},
+};
+
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::Url;
+use rocket::{
+    fairing::AdHoc,
+    form::Form,
+    http::{ContentType, Header as HttpHeader, Status},
+    local::asynchronous::Client,
+    serde::json::Json,
+    tokio::sync::oneshot,
+    State,
+};
+use serde_json::json;
+
+use crate::{
+    build_rocket,
+    jwt_auth_handler::{decode_token, generate_link_token, JwtConfig},
+    models::user::{UserLoginDTO, UserSignUpDTO},
+    oidc_handler::{OidcConfig, OidcDiscovery, OidcTokenResponse},
+    test::{
+        register_and_login, simulate_login,
+        utilities::tournament_utilities::create_tournament_request, APIResponse, LoginData,
+    },
+};
+
+const MOCK_CLIENT_ID: &str = "tournament-manager";
+const MOCK_CLIENT_SECRET: &str = "mock-client-secret";
+const MOCK_KEY_ID: &str = "idp-key";
+
+// The mock identity provider listens on a port picked by the system, so its issuer is only known after it starts
+struct MockIdp {
+    issuer: OnceLock<String>,
+}
+
+impl MockIdp {
+    fn issuer(&self) -> String {
+        self.issuer.get().cloned().unwrap_or_default()
+    }
+}
+
+#[get("/.well-known/openid-configuration")]
+fn mock_discovery(mock_idp: &State<MockIdp>) -> Json<OidcDiscovery> {
+    let issuer = mock_idp.issuer();
+
+    Json(OidcDiscovery {
+        authorization_endpoint: format!("{}/authorize", issuer),
+        token_endpoint: format!("{}/token", issuer),
+        jwks_uri: format!("{}/jwks", issuer),
+        id_token_signing_alg_values_supported: vec![String::from("RS256")],
+        issuer,
+    })
+}
+
+#[get("/jwks")]
+fn mock_jwks() -> (ContentType, &'static str) {
+    (ContentType::JSON, include_str!("keys/idp-jwks.json"))
+}
+
+#[derive(FromForm)]
+struct MockTokenRequest {
+    code: String,
+    client_secret: String,
+}
+
+// The mock identity provider skips the sign in page, so the code carries the user it should sign in
+// and the algorithm of the `id_token`: "<algorithm>|<subject>|<email>|<nonce>"
+#[post("/token", data = "<token_request>")]
+fn mock_token(
+    token_request: Form<MockTokenRequest>,
+    mock_idp: &State<MockIdp>,
+) -> Result<Json<OidcTokenResponse>, Status> {
+    if token_request.client_secret != MOCK_CLIENT_SECRET {
+        return Err(Status::Unauthorized);
+    }
+
+    let code: Vec<&str> = token_request.code.split('|').collect();
+    if code.len() != 4 {
+        return Err(Status::BadRequest);
+    }
+
+    let now = Utc::now().timestamp();
+    let claims = json!({
+        "iss": mock_idp.issuer(),
+        "sub": code[1],
+        "aud": MOCK_CLIENT_ID,
+        "iat": now,
+        "exp": now + 300,
+        "nonce": code[3],
+        "email": code[2],
+        "name": code[1],
+    });
+
+    // RS256 tokens are verified with the published key set, HS256 ones with the client secret
+    let (header, encoding_key) = match code[0] {
+        "RS256" => {
+            let mut header = Header::new(Algorithm::RS256);
+            header.kid = Some(String::from(MOCK_KEY_ID));
+
+            (
+                header,
+                EncodingKey::from_rsa_pem(include_bytes!("keys/idp-key.pem")).unwrap(),
+            )
+        }
+        _ => (
+            Header::new(Algorithm::HS256),
+            EncodingKey::from_secret(MOCK_CLIENT_SECRET.as_ref()),
+        ),
+    };
+
+    let id_token = jsonwebtoken::encode(&header, &claims, &encoding_key).unwrap();
+
+    Ok(Json(OidcTokenResponse { id_token }))
+}
+
+// Starts the mock identity provider on a free port and returns its issuer
+async fn launch_mock_idp() -> String {
+    let config = rocket::Config {
+        address: Ipv4Addr::LOCALHOST.into(),
+        port: 0,
+        ..rocket::Config::debug_default()
+    };
+
+    let (sender, receiver) = oneshot::channel::<String>();
+    let sender = Mutex::new(Some(sender));
+
+    let mock_idp = rocket::custom(config)
+        .mount("/", routes![mock_discovery, mock_jwks, mock_token])
+        .manage(MockIdp {
+            issuer: OnceLock::new(),
+        })
+        .attach(AdHoc::on_liftoff("Mock issuer", move |rocket| {
+            let issuer = format!("http://127.0.0.1:{}", rocket.config().port);
+            if let Some(mock_idp) = rocket.state::<MockIdp>() {
+                mock_idp.issuer.set(issuer.clone()).ok();
+            }
+            if let Some(sender) = sender.lock().unwrap().take() {
+                sender.send(issuer).ok();
+            }
+
+            Box::pin(async {})
+        }));
+    rocket::tokio::spawn(mock_idp.launch());
+
+    receiver
+        .await
+        .expect("The mock identity provider did not start")
+}
+
+// Builds the server with the OpenID Connect login pointing to the mock identity provider
+async fn oidc_client(issuer: &str) -> Client {
+    let jwt_config = JwtConfig::from_env().expect("Invalid JWT settings on env variables");
+    let oidc_config = OidcConfig {
+        issuer_url: issuer.to_string(),
+        client_id: String::from(MOCK_CLIENT_ID),
+        client_secret: String::from(MOCK_CLIENT_SECRET),
+        redirect_url: String::from("http://localhost/authentication/oidc/callback"),
+        scopes: String::from("openid email profile"),
+        id_token_algorithms: None,
+    };
+
+    Client::tracked(build_rocket(jwt_config, Some(oidc_config)).await)
+        .await
+        .unwrap()
+}
+
+struct OidcLogin<'a> {
+    issuer: &'a str,
+    algorithm: &'a str,
+    subject: &'a str,
+    email: &'a str,
+    state_override: Option<&'a str>,
+    // The token of the signed in user when linking the identity provider account
+    link_token: Option<&'a String>,
+}
+
+// Goes through the login (or link) redirect and callback, returning the callback response status and token
+async fn simulate_oidc_login(client: &Client, login: OidcLogin<'_>) -> (Status, Option<String>) {
+    let response = match login.link_token {
+        Some(link_token) => {
+            client
+                .get("/authentication/oidc/link")
+                .header(HttpHeader::new(
+                    "Authorization",
+                    format!("Bearer {}", link_token),
+                ))
+                .dispatch()
+                .await
+        }
+        None => client.get("/authentication/oidc/login").dispatch().await,
+    };
+    assert_eq!(response.status(), Status::SeeOther);
+
+    let location = response
+        .headers()
+        .get_one("Location")
+        .expect("Expected a redirect to the identity provider");
+    let authorization_url = Url::parse(location).unwrap();
+    assert!(authorization_url.as_str().starts_with(login.issuer));
+
+    let query_value = |name: &str| {
+        authorization_url
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.to_string())
+            .expect("Expected the value on the authorization URL")
+    };
+    let state = query_value("state");
+    let nonce = query_value("nonce");
+    assert_eq!(query_value("client_id"), MOCK_CLIENT_ID);
+
+    let code = format!(
+        "{}|{}|{}|{}",
+        login.algorithm, login.subject, login.email, nonce
+    );
+    let callback_url = Url::parse_with_params(
+        "http://localhost/authentication/oidc/callback",
+        &[
+            ("code", code.as_str()),
+            ("state", login.state_override.unwrap_or(&state)),
+        ],
+    )
+    .unwrap();
+
+    let response = client
+        .get(format!(
+            "{}?{}",
+            callback_url.path(),
+            callback_url.query().unwrap()
+        ))
+        .dispatch()
+        .await;
+
+    let status = response.status();
+    let token = response
+        .into_json::<APIResponse<LoginData>>()
+        .await
+        .map(|body| body.data.token);
+
+    (status, token)
+}
+
+fn token_user_id(client: &Client, token: String) -> u64 {
+    let jwt_config = client.rocket().state::<JwtConfig>().unwrap();
+
+    decode_token(token, jwt_config).unwrap().claims.user_id
+}
+
+// Signing in through a mock identity provider, the user is created on the first login and reused afterwards
+#[rocket::async_test]
+async fn oidc_login_test() {
+    let issuer = launch_mock_idp().await;
+    let client = oidc_client(&issuer).await;
+
+    let charset = "ABCDEFGHIJKLMNOPQRSTUVWXYZ123456789";
+    let subject = random_string::generate(20, charset);
+    let email = format!("{}@oidc.test.com", subject);
+    let login = || OidcLogin {
+        issuer: &issuer,
+        algorithm: "RS256",
+        subject: &subject,
+        email: &email,
+        state_override: None,
+        link_token: None,
+    };
+
+    // First login, the id_token is verified with the identity provider key set and the user should be created
+    let (status, token) = simulate_oidc_login(&client, login()).await;
+    assert_eq!(status, Status::Ok);
+    let first_token = token.expect("Expected the login token");
+
+    // Second login, it should be the same user
+    let (status, token) = simulate_oidc_login(&client, login()).await;
+    assert_eq!(status, Status::Ok);
+    let second_token = token.expect("Expected the login token");
+
+    assert_eq!(
+        token_user_id(&client, first_token.clone()),
+        token_user_id(&client, second_token)
+    );
+
+    // The token can be used like the one from the password login
+    let tournament_data = r#"{
+        "name": "oidc tournament",
+        "public": false
+    }"#;
+    let response = create_tournament_request(&client, Some(&first_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+
+    // The token kept on the cookie while an account is linked should not be accepted on the API
+    let jwt_config = client.rocket().state::<JwtConfig>().unwrap();
+    let link_token =
+        generate_link_token(token_user_id(&client, first_token.clone()), jwt_config).unwrap();
+    let response = create_tournament_request(&client, Some(&link_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // The identity provider only advertises RS256, so a token signed with another algorithm should be rejected
+    let (status, _token) = simulate_oidc_login(
+        &client,
+        OidcLogin {
+            algorithm: "HS256",
+            ..login()
+        },
+    )
+    .await;
+    assert_eq!(status, Status::Unauthorized);
+
+    // A callback with a state that doesn't match the login request should be rejected
+    let (status, _token) = simulate_oidc_login(
+        &client,
+        OidcLogin {
+            state_override: Some("invalid state"),
+            ..login()
+        },
+    )
+    .await;
+    assert_eq!(status, Status::Unauthorized);
+}
+
+// An account registered with a password is only linked to the identity provider by its signed in owner
+#[rocket::async_test]
+async fn oidc_link_test() {
+    let issuer = launch_mock_idp().await;
+    let client = oidc_client(&issuer).await;
+
+    let charset = "ABCDEFGHIJKLMNOPQRSTUVWXYZ123456789";
+    let subject = random_string::generate(20, charset);
+    let email = format!("{}@oidc.test.com", subject.to_lowercase());
+
+    let register_dto = UserSignUpDTO {
+        email: email.clone(),
+        password: String::from("12345678"),
+        name: subject.clone(),
+    };
+    let response = client
+        .post("/authentication/register")
+        .header(ContentType::JSON)
+        .body(serde_json::to_string(&register_dto).unwrap())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Created);
+
+    let password_token = simulate_login(
+        &client,
+        UserLoginDTO {
+            email: email.clone(),
+            password: register_dto.password,
+        },
+    )
+    .await;
+    let login = || OidcLogin {
+        issuer: &issuer,
+        algorithm: "RS256",
+        subject: &subject,
+        email: &email,
+        state_override: None,
+        link_token: None,
+    };
+
+    // Signing in with the same e-mail should not take over the password account
+    let (status, _token) = simulate_oidc_login(&client, login()).await;
+    assert_eq!(status, Status::Conflict);
+
+    // Linking should only be started by a signed in user
+    let response = client.get("/authentication/oidc/link").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Linking while signed in, the callback should return a token of the same user
+    let (status, token) = simulate_oidc_login(
+        &client,
+        OidcLogin {
+            link_token: Some(&password_token),
+            ..login()
+        },
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let password_user_id = token_user_id(&client, password_token.clone());
+    assert_eq!(
+        token_user_id(&client, token.expect("Expected the link token")),
+        password_user_id
+    );
+
+    // After linking, the identity provider login should sign in the password account
+    let (status, token) = simulate_oidc_login(&client, login()).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(
+        token_user_id(&client, token.expect("Expected the login token")),
+        password_user_id
+    );
+
+    // Another user should not be able to link the same identity provider account
+    let other_user = register_and_login(&client).await;
+    let (status, _token) = simulate_oidc_login(
+        &client,
+        OidcLogin {
+            link_token: Some(&other_user),
+            ..login()
+        },
+    )
+    .await;
+    assert_eq!(status, Status::Conflict);
+}
+
+// A discovery document issued by another identity provider should be rejected before redirecting the user
+#[rocket::async_test]
+async fn oidc_discovery_issuer_test() {
+    let issuer = launch_mock_idp().await;
+    // Same server, but the discovery document advertises the 127.0.0.1 issuer
+    let client = oidc_client(&issuer.replace("127.0.0.1", "localhost")).await;
+
+    let response = client.get("/authentication/oidc/login").dispatch().await;
+    assert_eq!(response.status(), Status::InternalServerError);
+}
@@ -2,14 +2,25 @@ use rocket::http::Status;
 use rocket::local::asynchronous::Client;
 
 use crate::{
-    models::team::TeamInfoDTO,
+    models::{
+        fixture::FixtureDataDTO,
+        team::{HeadToHeadDTO, MatchResult, TeamInfoDTO, TeamProfileDTO},
+    },
     rocket,
     test::{
         register_and_login,
-        utilities::team_utilities::{
-            create_team_request, delete_team_request, edit_team_request, get_team_request,
+        utilities::{
+            fixture_utilities::{
+                edit_fixture_request, generate_fixtures_request, get_league_fixtures_request,
+            },
+            league_utilities::{add_team_to_league_request, create_league_request},
+            team_utilities::{
+                create_team_request, delete_team_request, edit_team_request,
+                get_head_to_head_request, get_team_profile_request, get_team_request,
+                get_tournament_teams_request,
+            },
         },
-        APIResponse, IdData,
+        APIResponse, IdData, PaginatedAPIResponse,
     },
 };
 
@@ -328,6 +339,14 @@ async fn delete_team_unauthorized() {
         delete_team_request(&client, Some(&authorization_token), tournament_id, team_id).await;
     // Should not be allowed as the user did not created the tournament
     assert_eq!(response.status(), Status::BadRequest);
+
+    // Owning another tournament doesn't allow it either
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let response =
+        delete_team_request(&client, Some(&authorization_token), tournament_id, team_id).await;
+    assert_eq!(response.status(), Status::BadRequest);
 }
 
 // Creating a team and accessing it(authenticated/unauthenticated user) on a public tournament
@@ -434,3 +453,372 @@ async fn get_team_unauthorized() {
         get_team_request(&client, Some(&authorization_token), tournament_id, team_id).await;
     assert_eq!(response.status(), Status::BadRequest);
 }
+
+// Listing the tournament teams with pagination and a name search
+#[rocket::async_test]
+async fn list_tournament_teams() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "team listing",
+        "public": false
+    }"#;
+
+    // Sending the request to create the tournament
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let response_body = response.into_json::<APIResponse<IdData>>().await.unwrap();
+    let tournament_id = response_body.data.id;
+
+    // Creating the teams
+    for team_name in ["Lions", "Tigers", "Lions B"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+    }
+
+    // Getting the first page sorted by name
+    let response = get_tournament_teams_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        "limit=2&sort=name&order=desc",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<TeamInfoDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 3);
+    assert_eq!(response_body.data.len(), 2);
+    assert_eq!(response_body.data[0].name, "Tigers");
+
+    // Searching by name
+    let response = get_tournament_teams_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        "name=Lions",
+    )
+    .await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<TeamInfoDTO>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 2);
+
+    // The LIKE wildcards are searched as they were typed
+    for query in ["name=_ions", "name=%25"] {
+        let response =
+            get_tournament_teams_request(&client, Some(&authorization_token), tournament_id, query)
+                .await;
+        let response_body = response
+            .into_json::<PaginatedAPIResponse<Vec<TeamInfoDTO>>>()
+            .await
+            .unwrap();
+        assert_eq!(response_body.pagination.total, 0);
+    }
+
+    // The tournament is private, so other users should not see the teams
+    let response = get_tournament_teams_request(&client, None, tournament_id, "limit=10").await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// Playing a fixture and checking the team profile shows the result
+#[rocket::async_test]
+async fn get_team_profile_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "team profile",
+        "public": true
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "profile league",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    // Creating 4 teams and adding them to the league
+    for team_name in ["Lions", "Tigers", "Bears", "Wolves"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        let response = add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = generate_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_league_fixtures_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let fixtures = response
+        .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    let fixture = &fixtures[0];
+
+    // The home team wins the first fixture
+    let edit_data = r#"{
+        "home_score": 2,
+        "away_score": 0,
+        "played": true
+    }"#;
+    let response = edit_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        fixture.id,
+        edit_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    // The profile is public as the tournament is public
+    let response =
+        get_team_profile_request(&client, None, tournament_id, fixture.home_team.id).await;
+    assert_eq!(response.status(), Status::Ok);
+    let profile = response
+        .into_json::<APIResponse<TeamProfileDTO>>()
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(profile.id, fixture.home_team.id);
+    assert_eq!(profile.leagues.len(), 1);
+    assert_eq!(profile.leagues[0].id, league_id);
+    assert_eq!(profile.leagues[0].position, Some(1));
+    assert_eq!(profile.form.len(), 1);
+    assert_eq!(profile.form[0].fixture_id, fixture.id);
+    assert_eq!(profile.form[0].opponent.id, fixture.away_team.id);
+    assert_eq!(profile.form[0].result, MatchResult::Win);
+    assert_eq!(profile.record.win, 1);
+    assert_eq!(profile.record.goal_difference, 2);
+    // Each team plays 3 fixtures on a league with 4 teams
+    assert_eq!(profile.next_fixtures.len(), 2);
+}
+
+// Two teams playing each other on two leagues of the same tournament
+#[rocket::async_test]
+async fn get_head_to_head_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "head to head",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for team_name in ["Lions", "Tigers"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, team_name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            team_data.as_str(),
+            tournament_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+        team_ids.push(team_id);
+    }
+    let (lions_id, tigers_id) = (team_ids[0], team_ids[1]);
+
+    // The Lions score 2 goals on both leagues, the Tigers score 2 on the first league and 1 on the second
+    for (league_name, tigers_score) in [("first league", 2), ("second league", 1)] {
+        let league_data = format!(r#"{{ "name": "{}", "completed": false }}"#, league_name);
+        let response = create_league_request(
+            &client,
+            Some(&authorization_token),
+            &league_data,
+            tournament_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+        let league_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        for team_id in [lions_id, tigers_id] {
+            let response = add_team_to_league_request(
+                &client,
+                Some(&authorization_token),
+                tournament_id,
+                league_id,
+                team_id,
+            )
+            .await;
+            assert_eq!(response.status(), Status::Ok);
+        }
+
+        let response = generate_fixtures_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = get_league_fixtures_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+        let fixture = response
+            .into_json::<APIResponse<Vec<FixtureDataDTO>>>()
+            .await
+            .unwrap()
+            .data
+            .remove(0);
+
+        let (home_score, away_score) = if fixture.home_team.id == lions_id {
+            (2, tigers_score)
+        } else {
+            (tigers_score, 2)
+        };
+        let edit_data = format!(
+            r#"{{ "home_score": {}, "away_score": {}, "played": true }}"#,
+            home_score, away_score
+        );
+        let response = edit_fixture_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture.id,
+            &edit_data,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    let response = get_head_to_head_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        lions_id,
+        tigers_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let head_to_head = response
+        .into_json::<APIResponse<HeadToHeadDTO>>()
+        .await
+        .unwrap()
+        .data;
+
+    assert_eq!(head_to_head.team.id, lions_id);
+    assert_eq!(head_to_head.opponent.id, tigers_id);
+    assert_eq!(head_to_head.team_record.played, 2);
+    assert_eq!(head_to_head.team_record.win, 1);
+    assert_eq!(head_to_head.team_record.draw, 1);
+    assert_eq!(head_to_head.team_record.goals_scored, 4);
+    assert_eq!(head_to_head.team_record.goal_difference, 1);
+    assert_eq!(head_to_head.opponent_record.loss, 1);
+    assert_eq!(head_to_head.opponent_record.goals_scored, 3);
+    assert_eq!(head_to_head.last_meetings.len(), 2);
+
+    // A team can't be compared against itself
+    let response = get_head_to_head_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        lions_id,
+        lions_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // The tournament is private
+    let response =
+        get_head_to_head_request(&client, None, tournament_id, lions_id, tigers_id).await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
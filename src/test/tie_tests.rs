@@ -0,0 +1,718 @@
+use rocket::{http::Status, local::asynchronous::Client};
+use serde_json::json;
+
+use crate::{
+    models::{
+        league::LeagueInformationDTO,
+        tie::{TieDTO, TieDecider},
+    },
+    rocket,
+    test::{
+        register_and_login,
+        utilities::{
+            fixture_utilities::{
+                create_fixture_request, delete_fixture_request, edit_fixture_pairing_request,
+                edit_fixture_request, get_fixture_by_id_request,
+            },
+            league_utilities::{
+                add_team_to_league_request, create_league_request, get_league_request,
+                remove_team_from_league_request, withdraw_team_from_league_request,
+            },
+            team_utilities::create_team_request,
+            tie_utilities::{
+                create_tie_request, edit_tie_request, get_league_ties_request, get_tie_request,
+            },
+            tournament_utilities::create_tournament_request,
+        },
+        APIResponse, IdData,
+    },
+};
+
+async fn get_tie(
+    client: &Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    tie_id: u64,
+) -> TieDTO {
+    let response = get_tie_request(
+        client,
+        authorization_token,
+        tournament_id,
+        league_id,
+        tie_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    response
+        .into_json::<APIResponse<TieDTO>>()
+        .await
+        .expect("Expected the tie data")
+        .data
+}
+
+async fn play_leg(
+    client: &Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    fixture_id: u64,
+    home_score: u8,
+    away_score: u8,
+) -> Status {
+    let result = json!({
+        "home_score": home_score,
+        "away_score": away_score,
+        "played": true
+    })
+    .to_string();
+
+    edit_fixture_request(
+        client,
+        authorization_token,
+        tournament_id,
+        league_id,
+        fixture_id,
+        &result,
+    )
+    .await
+    .status()
+}
+
+// A two legged tie decided by the aggregate, the away goals and the penalties
+#[rocket::async_test]
+async fn two_legged_tie_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "ties",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "cup",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for number in 0..3 {
+        let team_data = json!({ "name": format!("Team {}", number) }).to_string();
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &team_data,
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    // A pending fixture, so the league is not completed once both legs are played
+    let fixture_data = json!({
+        "home_team_id": team_ids[0],
+        "away_team_id": team_ids[2],
+        "round": 3
+    })
+    .to_string();
+    let response = create_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &fixture_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+
+    // The second leg must be played after the first leg
+    let response = create_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &json!({
+            "home_team_id": team_ids[0],
+            "away_team_id": team_ids[1],
+            "first_leg_round": 2,
+            "second_leg_round": 1
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let tie_data = json!({
+        "home_team_id": team_ids[0],
+        "away_team_id": team_ids[1],
+        "first_leg_round": 1,
+        "second_leg_round": 2,
+        "away_goals": true
+    })
+    .to_string();
+    let response = create_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &tie_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let tie_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let current_tie = || {
+        get_tie(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            tie_id,
+        )
+    };
+    let play = |fixture_id: u64, home_score: u8, away_score: u8| {
+        play_leg(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture_id,
+            home_score,
+            away_score,
+        )
+    };
+
+    // The second leg is played at the first leg away team ground
+    let tie = current_tie().await;
+    assert_eq!(tie.first_leg.home_team.id, team_ids[0]);
+    assert_eq!(tie.second_leg.home_team.id, team_ids[1]);
+    assert_eq!(tie.second_leg.round, 2);
+    assert_eq!(tie.aggregate.winner_team_id, None);
+    let first_leg_id = tie.first_leg.id;
+    let second_leg_id = tie.second_leg.id;
+
+    // Both legs keep the same teams
+    let response = edit_fixture_pairing_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        second_leg_id,
+        &json!({ "home_team_id": team_ids[2] }).to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // 2-2 on aggregate, the first leg home team scored once away
+    assert_eq!(play(first_leg_id, 1, 0).await, Status::Ok);
+    assert_eq!(play(second_leg_id, 2, 1).await, Status::Ok);
+    let tie = current_tie().await;
+    assert_eq!(tie.aggregate.home_score, 2);
+    assert_eq!(tie.aggregate.away_score, 2);
+    assert_eq!(tie.aggregate.winner_team_id, Some(team_ids[0]));
+    assert_eq!(tie.aggregate.decided_by, Some(TieDecider::AwayGoals));
+
+    // The extra time is only played when the tie is level
+    let extra_time_data = json!({
+        "extra_time_home_score": 0,
+        "extra_time_away_score": 0,
+        "penalties_home_score": 4,
+        "penalties_away_score": 3
+    })
+    .to_string();
+    let response = edit_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &extra_time_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    assert_eq!(play(second_leg_id, 1, 0).await, Status::Ok);
+    let tie = current_tie().await;
+    assert_eq!(tie.aggregate.winner_team_id, None);
+
+    // The penalties come after the extra time
+    let response = edit_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &json!({
+            "extra_time_home_score": null,
+            "extra_time_away_score": null,
+            "penalties_home_score": 4,
+            "penalties_away_score": 3
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = edit_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &json!({
+            "extra_time_home_score": 0,
+            "extra_time_away_score": 0,
+            "penalties_home_score": 3,
+            "penalties_away_score": 3
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let other_user_token = register_and_login(&client).await;
+    let response = edit_tie_request(
+        &client,
+        Some(&other_user_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &extra_time_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // The penalties are counted by the second leg teams
+    let response = edit_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &extra_time_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let tie = current_tie().await;
+    assert_eq!(tie.aggregate.winner_team_id, Some(team_ids[1]));
+    assert_eq!(tie.aggregate.decided_by, Some(TieDecider::Penalties));
+
+    let response = get_league_ties_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let ties = response
+        .into_json::<APIResponse<Vec<TieDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(ties.len(), 1);
+
+    // Deleting a leg deletes the other leg and the tie
+    let response = delete_fixture_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        first_leg_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let response = get_fixture_by_id_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        second_leg_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = get_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+    )
+    .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+// A league is not completed while a tie is level after both legs
+#[rocket::async_test]
+async fn level_tie_completion_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "level tie",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "final",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for number in 0..2 {
+        let team_data = json!({ "name": format!("Team {}", number) }).to_string();
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &team_data,
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    let tie_data = json!({
+        "home_team_id": team_ids[0],
+        "away_team_id": team_ids[1],
+        "first_leg_round": 1,
+        "second_leg_round": 2
+    })
+    .to_string();
+    let response = create_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        &tie_data,
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+    let tie_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+    let tie = get_tie(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+    )
+    .await;
+
+    let league_completed = || async {
+        let response = get_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+        )
+        .await;
+
+        response
+            .into_json::<APIResponse<LeagueInformationDTO>>()
+            .await
+            .unwrap()
+            .data
+            .completed
+    };
+
+    // 1-1 on aggregate, every fixture is played but the tie is not decided
+    for fixture_id in [tie.first_leg.id, tie.second_leg.id] {
+        let status = play_leg(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            fixture_id,
+            1,
+            0,
+        )
+        .await;
+        assert_eq!(status, Status::Ok);
+    }
+    assert!(!league_completed().await);
+
+    // The extra time decides the tie, so the league is completed
+    let response = edit_tie_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_id,
+        &json!({
+            "extra_time_home_score": 1,
+            "extra_time_away_score": 0,
+            "penalties_home_score": null,
+            "penalties_away_score": null
+        })
+        .to_string(),
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    assert!(league_completed().await);
+}
+
+// Withdrawing a team awards its undecided tie to the opponent as a whole, and expunging a team deletes its tie
+#[rocket::async_test]
+async fn tie_withdrawal_test() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "tie withdrawal",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let league_data = r#"{
+        "name": "cup",
+        "completed": false
+    }"#;
+    let response = create_league_request(
+        &client,
+        Some(&authorization_token),
+        league_data,
+        tournament_id,
+    )
+    .await;
+    let league_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for number in 0..4 {
+        let team_data = json!({ "name": format!("Team {}", number) }).to_string();
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &team_data,
+            tournament_id,
+        )
+        .await;
+        let team_id = response
+            .into_json::<APIResponse<IdData>>()
+            .await
+            .unwrap()
+            .data
+            .id;
+
+        add_team_to_league_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            team_id,
+        )
+        .await;
+        team_ids.push(team_id);
+    }
+
+    let mut tie_ids = vec![];
+    for (home_team_id, away_team_id) in [(team_ids[0], team_ids[1]), (team_ids[2], team_ids[3])] {
+        let tie_data = json!({
+            "home_team_id": home_team_id,
+            "away_team_id": away_team_id,
+            "first_leg_round": 1,
+            "second_leg_round": 2
+        })
+        .to_string();
+        let response = create_tie_request(
+            &client,
+            Some(&authorization_token),
+            tournament_id,
+            league_id,
+            &tie_data,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+        tie_ids.push(
+            response
+                .into_json::<APIResponse<IdData>>()
+                .await
+                .unwrap()
+                .data
+                .id,
+        );
+    }
+
+    // The withdrawn team wins the first leg by 5, forfeiting only the second leg would leave it the tie
+    let tie = get_tie(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_ids[0],
+    )
+    .await;
+    let status = play_leg(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie.first_leg.id,
+        5,
+        0,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let response = remove_team_from_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        team_ids[0],
+    )
+    .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let tie = get_tie(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        tie_ids[0],
+    )
+    .await;
+    assert!(tie.first_leg.played && tie.second_leg.played);
+    assert_eq!((tie.first_leg.home_score, tie.first_leg.away_score), (0, 3));
+    assert_eq!(
+        (tie.second_leg.home_score, tie.second_leg.away_score),
+        (3, 0)
+    );
+    assert_eq!(tie.aggregate.winner_team_id, Some(team_ids[1]));
+
+    // Expunging a team deletes both legs of its tie and the tie with them, so the league is completed with the decided tie
+    let response = withdraw_team_from_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+        team_ids[2],
+        "expunge",
+    )
+    .await;
+    assert_eq!(response.status(), Status::NoContent);
+
+    let response = get_league_ties_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let ties = response
+        .into_json::<APIResponse<Vec<TieDTO>>>()
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(ties.len(), 1);
+    assert_eq!(ties[0].id, tie_ids[0]);
+
+    let response = get_league_request(
+        &client,
+        Some(&authorization_token),
+        tournament_id,
+        league_id,
+    )
+    .await;
+    let league = response
+        .into_json::<APIResponse<LeagueInformationDTO>>()
+        .await
+        .unwrap()
+        .data;
+    assert!(league.completed);
+}
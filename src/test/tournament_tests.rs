@@ -1,11 +1,19 @@
 use super::{
-    utilities::tournament_utilities::{
-        create_tournament_request, delete_tournament_request, edit_tournament_request,
-        get_tournament_request,
+    utilities::{
+        team_utilities::create_team_request,
+        tournament_utilities::{
+            create_tournament_request, delete_tournament_request, edit_tournament_request,
+            get_public_tournaments_request, get_tournament_page_request, get_tournament_request,
+            get_user_tournaments_request,
+        },
     },
-    APIResponse, IdData,
+    APIResponse, IdData, PaginatedAPIResponse,
+};
+use crate::{
+    models::tournament::{TournamentInformationData, TournamentSummaryData},
+    rocket,
+    test::register_and_login,
 };
-use crate::{models::tournament::TournamentInformationData, rocket, test::register_and_login};
 use rocket::{
     http::{ContentType, Status},
     local::asynchronous::Client,
@@ -429,3 +437,141 @@ async fn accessing_tournament_as_other_user() {
     .await;
     assert_eq!(response.status(), Status::BadRequest);
 }
+
+/// Listing the user tournaments and discovering the public tournaments from other users
+#[rocket::async_test]
+async fn listing_tournaments() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    // A random tag on the names, so the search only matches the tournaments created by this test
+    let charset = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let tag = random_string::generate(10, charset);
+
+    let mut tournament_ids = vec![];
+    for (name, public) in [("public", true), ("private", false)] {
+        let tournament_data = format!(r#"{{ "name": "{} {}", "public": {} }}"#, tag, name, public);
+        let response =
+            create_tournament_request(&client, Some(&authorization_token), &tournament_data).await;
+        assert_eq!(response.status(), Status::Created);
+        let response_body = response.into_json::<APIResponse<IdData>>().await.unwrap();
+        tournament_ids.push(response_body.data.id);
+    }
+
+    // Adding a team to the public tournament, the listings only show how many teams it has
+    let response = create_team_request(
+        &client,
+        Some(&authorization_token),
+        r#"{ "name": "listed team" }"#,
+        tournament_ids[0],
+    )
+    .await;
+    assert_eq!(response.status(), Status::Created);
+
+    // The owner sees both tournaments, public and private
+    let response =
+        get_user_tournaments_request(&client, Some(&authorization_token), "sort=name&order=desc")
+            .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<TournamentSummaryData>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 2);
+    assert_eq!(response_body.data[0].id, tournament_ids[0]);
+    assert_eq!(response_body.data[1].id, tournament_ids[1]);
+    assert!(response_body.data[0].public);
+    assert!(!response_body.data[1].public);
+    assert_eq!(response_body.data[0].teams_total, 1);
+    assert_eq!(response_body.data[0].leagues_total, 0);
+    assert_eq!(response_body.data[1].teams_total, 0);
+
+    // Listing the user tournaments requires authentication
+    let response = get_user_tournaments_request(&client, None, "").await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Other users only discover the public tournament
+    let query = format!("name={}", tag);
+    let response = get_public_tournaments_request(&client, &query).await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<TournamentSummaryData>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 1);
+    assert_eq!(response_body.data[0].id, tournament_ids[0]);
+
+    // The other user doesn't own any tournament
+    let other_user_authorization_token = register_and_login(&client).await;
+    let response =
+        get_user_tournaments_request(&client, Some(&other_user_authorization_token), "").await;
+    let response_body = response
+        .into_json::<PaginatedAPIResponse<Vec<TournamentSummaryData>>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.pagination.total, 0);
+    assert!(response_body.data.is_empty());
+}
+
+/// Getting a tournament with a page of its leagues and teams
+#[rocket::async_test]
+async fn tournament_information_pages() {
+    let client = Client::tracked(rocket().await).await.unwrap();
+    let authorization_token = register_and_login(&client).await;
+
+    let tournament_data = r#"{
+        "name": "paged tournament",
+        "public": false
+    }"#;
+    let response =
+        create_tournament_request(&client, Some(&authorization_token), tournament_data).await;
+    assert_eq!(response.status(), Status::Created);
+    let tournament_id = response
+        .into_json::<APIResponse<IdData>>()
+        .await
+        .unwrap()
+        .data
+        .id;
+
+    let mut team_ids = vec![];
+    for name in ["first team", "second team", "third team"] {
+        let team_data = format!(r#"{{ "name": "{}" }}"#, name);
+        let response = create_team_request(
+            &client,
+            Some(&authorization_token),
+            &team_data,
+            tournament_id,
+        )
+        .await;
+        assert_eq!(response.status(), Status::Created);
+        let response_body = response.into_json::<APIResponse<IdData>>().await.unwrap();
+        team_ids.push(response_body.data.id);
+    }
+
+    // The second page of one team
+    let response = get_tournament_page_request(
+        &client,
+        tournament_id,
+        Some(&authorization_token),
+        "limit=1&offset=1",
+    )
+    .await;
+    assert_eq!(response.status(), Status::Ok);
+    let response_body = response
+        .into_json::<APIResponse<TournamentInformationData>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.data.teams.len(), 1);
+    assert_eq!(response_body.data.teams[0].id, team_ids[1]);
+    assert_eq!(response_body.data.teams_total, 3);
+    assert!(response_body.data.leagues.is_empty());
+    assert_eq!(response_body.data.leagues_total, 0);
+
+    // Without the page, the first page has every team
+    let response = get_tournament_request(&client, tournament_id, Some(&authorization_token)).await;
+    let response_body = response
+        .into_json::<APIResponse<TournamentInformationData>>()
+        .await
+        .unwrap();
+    assert_eq!(response_body.data.teams.len(), 3);
+}
@@ -0,0 +1,46 @@
+use rocket::local::asynchronous::{Client, LocalResponse};
+
+use super::base_request_test;
+
+pub async fn get_league_bracket_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/bracket",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn generate_league_bracket_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    generation_data: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/bracket",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        generation_data,
+    )
+    .await
+}
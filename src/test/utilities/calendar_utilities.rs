@@ -0,0 +1,45 @@
+use rocket::local::asynchronous::{Client, LocalResponse};
+
+use super::base_request_test;
+
+pub async fn get_league_calendar_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/calendar.ics",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn get_team_calendar_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    team_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/teams/{}/calendar.ics",
+        tournament_id, team_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
@@ -0,0 +1,78 @@
+use rocket::local::asynchronous::{Client, LocalResponse};
+
+use super::base_request_test;
+
+pub async fn export_league_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/leagues/{}/export", tournament_id, league_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn export_league_csv_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    file_name: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/export/{}",
+        tournament_id, league_id, file_name
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn export_tournament_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/export", tournament_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn export_tournament_csv_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    file_name: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/export/{}", tournament_id, file_name);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
@@ -23,6 +23,28 @@ pub async fn get_league_fixtures_request<'a>(
     .await
 }
 
+pub async fn get_league_fixtures_with_query_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures?{}",
+        tournament_id, league_id, query
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
 pub async fn generate_fixtures_request<'a>(
     client: &'a Client,
     authorization_token: Option<&String>,
@@ -44,6 +66,28 @@ pub async fn generate_fixtures_request<'a>(
     .await
 }
 
+pub async fn generate_fixtures_with_query_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures?{}",
+        tournament_id, league_id, query
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
 pub async fn get_fixture_by_id_request<'a>(
     client: &'a Client,
     authorization_token: Option<&String>,
@@ -109,3 +153,157 @@ pub async fn edit_fixture_request<'a>(
     )
     .await
 }
+
+pub async fn schedule_fixtures_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    constraints: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures/schedule",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        constraints,
+    )
+    .await
+}
+
+pub async fn preview_fixtures_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures/preview?{}",
+        tournament_id, league_id, query
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn submit_fixtures_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    schedule: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Put,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        schedule,
+    )
+    .await
+}
+
+pub async fn create_fixture_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    fixture_data: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures/new",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        fixture_data,
+    )
+    .await
+}
+
+pub async fn edit_fixture_pairing_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    fixture_id: u64,
+    pairing_data: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures/{}/pairing",
+        tournament_id, league_id, fixture_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Put,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        pairing_data,
+    )
+    .await
+}
+
+pub async fn delete_fixture_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    fixture_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures/{}",
+        tournament_id, league_id, fixture_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Delete,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn generate_swiss_round_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/fixtures/swiss",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
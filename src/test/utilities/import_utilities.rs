@@ -0,0 +1,44 @@
+use rocket::local::asynchronous::{Client, LocalResponse};
+
+use super::base_request_test;
+
+pub async fn preview_league_import_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    import_data: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/import/preview",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        import_data,
+    )
+    .await
+}
+
+pub async fn import_league_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    import_data: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/leagues/{}/import", tournament_id, league_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        import_data,
+    )
+    .await
+}
@@ -20,6 +20,24 @@ pub async fn get_league_request<'a>(
     .await
 }
 
+pub async fn get_tournament_leagues_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/leagues?{}", tournament_id, query);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
 pub async fn create_league_request<'a>(
     client: &'a Client,
     authorization_token: Option<&String>,
@@ -121,6 +139,29 @@ pub async fn remove_team_from_league_request<'a>(
 
 
 
+pub async fn withdraw_team_from_league_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    team_id: u64,
+    policy: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/teams/{}?policy={}",
+        tournament_id, league_id, team_id, policy
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Delete,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
 pub async fn get_league_standings_table_request<'a>(client: &'a Client, authorization_token: Option<&String>, tournament_id: u64, league_id: u64) -> LocalResponse<'a> {
     let route = format!("/tournament/{}/leagues/{}/standing-table", tournament_id, league_id);
 
@@ -132,4 +173,99 @@ pub async fn get_league_standings_table_request<'a>(client: &'a Client, authoriz
         "",
     )
     .await
-}
\ No newline at end of file
+}
+pub async fn get_league_standings_table_with_query_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/standing-table?{}",
+        tournament_id, league_id, query
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn get_league_standing_progression_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/standing-progression",
+        tournament_id, league_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn create_new_season_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    season_data: &str,
+    tournament_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/seasons", tournament_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        season_data,
+    )
+    .await
+}
+
+pub async fn get_tournament_archive_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/archive", tournament_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn get_archived_season_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/archive/{}", tournament_id, league_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
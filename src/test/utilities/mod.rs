@@ -32,4 +32,9 @@ async fn base_request_test<'a>(
 pub mod league_utilities;
 pub mod team_utilities;
 pub mod tournament_utilities;
-pub mod fixture_utilities;
\ No newline at end of file
+pub mod fixture_utilities;
+pub mod bracket_utilities;
+pub mod tie_utilities;
+pub mod import_utilities;
+pub mod export_utilities;
+pub mod calendar_utilities;
\ No newline at end of file
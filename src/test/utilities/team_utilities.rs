@@ -20,6 +20,64 @@ pub async fn get_team_request<'a>(
     .await
 }
 
+pub async fn get_team_profile_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    team_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/teams/{}/profile", tournament_id, team_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn get_head_to_head_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    team_id: u64,
+    opponent_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/teams/{}/vs/{}",
+        tournament_id, team_id, opponent_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn get_tournament_teams_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/teams?{}", tournament_id, query);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
 pub async fn create_team_request<'a>(
     client: &'a Client,
     authorization_token: Option<&String>,
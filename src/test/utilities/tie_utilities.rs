@@ -0,0 +1,85 @@
+use rocket::local::asynchronous::{Client, LocalResponse};
+
+use super::base_request_test;
+
+pub async fn get_league_ties_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/leagues/{}/ties", tournament_id, league_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn get_tie_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    tie_id: u64,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/ties/{}",
+        tournament_id, league_id, tie_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+pub async fn create_tie_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    tie_data: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}/leagues/{}/ties", tournament_id, league_id);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Post,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        tie_data,
+    )
+    .await
+}
+
+pub async fn edit_tie_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    tournament_id: u64,
+    league_id: u64,
+    tie_id: u64,
+    extra_time_data: &str,
+) -> LocalResponse<'a> {
+    let route = format!(
+        "/tournament/{}/leagues/{}/ties/{}",
+        tournament_id, league_id, tie_id
+    );
+
+    base_request_test(
+        client,
+        rocket::http::Method::Put,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        extra_time_data,
+    )
+    .await
+}
@@ -95,3 +95,65 @@ pub async fn get_tournament_request<'a>(
     )
     .await
 }
+
+/// Responsible to make a request to the API to get the tournament information with a page of its leagues and teams
+///
+/// # Arguments
+/// * `client` - `rocket::local::asynchronous::client`, Rocket client to make requests
+/// * `tournament_id` - `u64` integer which represents the tournament id
+/// * `authorization_token` - Optional `String` that represents the user authorization token
+/// * `query` - `str` reference with the query string, such as `limit=10&offset=10`
+pub async fn get_tournament_page_request<'a>(
+    client: &'a Client,
+    tournament_id: u64,
+    authorization_token: Option<&String>,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/{}?{}", tournament_id, query);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+/// Responsible to make a request to the API to list the user tournaments
+///
+/// # Arguments
+/// * `client` - `rocket::local::asynchronous::client`, Rocket client to make requests
+/// * `authorization_token` - Optional `String` that represents the user authorization token
+/// * `query` - `str` reference with the query string, such as `name=cup&limit=10`
+pub async fn get_user_tournaments_request<'a>(
+    client: &'a Client,
+    authorization_token: Option<&String>,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament?{}", query);
+
+    base_request_test(
+        client,
+        rocket::http::Method::Get,
+        authorization_token.unwrap_or(&String::new()),
+        route,
+        "",
+    )
+    .await
+}
+
+/// Responsible to make a request to the API to list the public tournaments
+///
+/// # Arguments
+/// * `client` - `rocket::local::asynchronous::client`, Rocket client to make requests
+/// * `query` - `str` reference with the query string, such as `name=cup&limit=10`
+pub async fn get_public_tournaments_request<'a>(
+    client: &'a Client,
+    query: &str,
+) -> LocalResponse<'a> {
+    let route = format!("/tournament/public?{}", query);
+
+    base_request_test(client, rocket::http::Method::Get, &String::new(), route, "").await
+}